@@ -0,0 +1,28 @@
+Feature: Add a task and create its GitHub Issue
+
+  Scenario: Add a task under a section
+    Given the user is logged in via GitHub App for tests
+    And the config file content is '{"repositories":["toms74209200/atat-test"]}'
+    And the TODO.md file contains:
+      """
+      ## Features
+      - [ ] Existing task
+
+      ## Bugs
+      - [ ] Existing bug
+      """
+    When I run `atat add "New task to implement" --section Features`
+    Then a new GitHub issue should be created with title "New task to implement"
+    And the TODO.md file should be updated with the issue number
+    And cleanup remaining open issues
+
+  Scenario: Error when not logged in
+    Given the user is not logged in
+    And the config file content is '{"repositories":["toms74209200/atat-test"]}'
+    And the TODO.md file contains:
+      """
+      - [ ] Existing task
+      """
+    When I run `atat add "New task to implement"`
+    Then the error should be "X Authentication required\nℹ To get started, please run:  atat login"
+    And the TODO.md file should remain unchanged
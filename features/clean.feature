@@ -114,7 +114,7 @@ Feature: Clean completed items from TODO.md
       - [x] Completed task (#100)
       """
     When I run `atat clean`
-    Then the error should be "Error: Authentication required"
+    Then the error should be "X Authentication required\nℹ To get started, please run:  atat login"
 
   Scenario: Error when no repository configured
     Given the user is logged in via GitHub App for tests
@@ -124,11 +124,11 @@ Feature: Clean completed items from TODO.md
       - [x] Completed task (#100)
       """
     When I run `atat clean`
-    Then the error should be "Error: No repository configured"
+    Then the error should be "X No repository configured\nℹ Run `atat init` or `atat remote add <owner>/<repo>` to add one"
 
   Scenario: Error when TODO.md file does not exist
     Given the user is logged in via GitHub App for tests
     And the config file content is '{"repositories":["toms74209200/atat-test"]}'
     And the TODO.md file does not exist
     When I run `atat clean`
-    Then the error should be "Error: TODO.md file not found"
+    Then the error should be "X TODO.md file not found\nℹ Run `atat init` to create TODO.md, or pass the task file with --file"
@@ -0,0 +1,14 @@
+Feature: Log out of GitHub
+
+  @serial
+  Scenario: Log out removes the stored token
+    Given a token is stored
+    When I run `atat logout`
+    Then the output should be "✓ Logged out of github.com"
+    And no token should be stored
+
+  @serial
+  Scenario: Log out when not logged in
+    Given the user is not logged in
+    When I run `atat logout`
+    Then the error should be "X Not logged in to github.com\nℹ Run `atat whoami` to see the accounts you are logged in with"
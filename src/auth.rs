@@ -1,4 +1,10 @@
+use crate::i18n::t;
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Refresh this long before the access token expires, to cover clock skew and slow commands
+const REFRESH_MARGIN_SECS: i64 = 5 * 60;
 
 /// Request parameters for device code
 #[derive(Serialize, Debug)]
@@ -18,7 +24,7 @@ pub struct DeviceCodeResponse {
 }
 
 /// Response from access token request
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 #[allow(dead_code)]
 pub struct AccessTokenResponse {
     pub access_token: Option<String>,
@@ -28,6 +34,154 @@ pub struct AccessTokenResponse {
     pub error_description: Option<String>,
     pub error_uri: Option<String>,
     pub interval: Option<u32>,
+    /// Lifetime of the access token in seconds (GitHub App tokens with expiration enabled)
+    pub expires_in: Option<u64>,
+    pub refresh_token: Option<String>,
+    pub refresh_token_expires_in: Option<u64>,
+}
+
+/// Token saved by `atat login`, with the refresh token of an expiring GitHub App token
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Credentials {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token_expires_at: Option<DateTime<Utc>>,
+}
+
+impl Credentials {
+    /// Build credentials from a successful token response received at `now`.
+    pub fn from_response(response: &AccessTokenResponse, now: DateTime<Utc>) -> Option<Self> {
+        let after = |secs: Option<u64>| {
+            secs.and_then(|secs| i64::try_from(secs).ok())
+                .map(|secs| now + Duration::seconds(secs))
+        };
+        Some(Credentials {
+            access_token: response.access_token.clone()?,
+            expires_at: after(response.expires_in),
+            refresh_token: response.refresh_token.clone(),
+            refresh_token_expires_at: after(response.refresh_token_expires_in),
+        })
+    }
+
+    /// Parse the token file. Files written by earlier versions contain only the token.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let content = content.trim();
+        if content.starts_with('{') {
+            serde_json::from_str(content).map_err(|e| t!("auth.parse_token_failed", error = e))
+        } else {
+            Ok(Credentials {
+                access_token: content.to_string(),
+                expires_at: None,
+                refresh_token: None,
+                refresh_token_expires_at: None,
+            })
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Credentials serialize to JSON")
+    }
+
+    /// Whether the access token has expired or expires within the refresh margin
+    pub fn is_expiring(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| now + Duration::seconds(REFRESH_MARGIN_SECS) >= expires_at)
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// The refresh token, if there is one and it has not expired
+    pub fn usable_refresh_token(&self, now: DateTime<Utc>) -> Option<&str> {
+        match self.refresh_token_expires_at {
+            Some(expires_at) if expires_at <= now => None,
+            _ => self.refresh_token.as_deref(),
+        }
+    }
+}
+
+/// A GitHub App installation from the `/user/installations` response
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Installation {
+    pub account: InstallationAccount,
+    #[serde(default)]
+    pub app_slug: Option<String>,
+    /// Settings page of the installation, where repositories and permissions are granted
+    #[serde(default)]
+    pub html_url: Option<String>,
+    /// `all` or `selected` repositories of the account
+    #[serde(default)]
+    pub repository_selection: Option<String>,
+    #[serde(default)]
+    pub permissions: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct InstallationAccount {
+    pub login: String,
+}
+
+/// Parse a `/user/installations` response
+pub fn parse_installations(json: &str) -> Result<Vec<Installation>, String> {
+    #[derive(Deserialize)]
+    struct Installations {
+        installations: Vec<Installation>,
+    }
+
+    serde_json::from_str::<Installations>(json)
+        .map(|response| response.installations)
+        .map_err(|e| t!("auth.parse_installations_failed", error = e))
+}
+
+/// Describe the permissions granted to the GitHub App installations.
+///
+/// Installations are merged, keeping the highest access level of each permission.
+/// `metadata` is left out since every installation grants read access to it.
+pub fn describe_installation_permissions(installations: &[Installation]) -> Vec<String> {
+    let rank = |level: &str| match level {
+        "read" => 1,
+        "write" => 2,
+        "admin" => 3,
+        _ => 0,
+    };
+    let mut merged = BTreeMap::<&str, &str>::new();
+    for (name, level) in installations
+        .iter()
+        .flat_map(|installation| &installation.permissions)
+    {
+        if name == "metadata" {
+            continue;
+        }
+        let current = merged.get(name.as_str()).map_or(0, |current| rank(current));
+        if rank(level) > current {
+            merged.insert(name, level);
+        }
+    }
+
+    merged
+        .iter()
+        .map(|(name, level)| describe_permission(name, level))
+        .collect()
+}
+
+/// Human readable form of a GitHub App permission, e.g. `Read and write issues`
+pub fn describe_permission(name: &str, level: &str) -> String {
+    let name = match name {
+        "contents" => t!("permission.contents"),
+        "metadata" => t!("permission.metadata"),
+        other => other.replace('_', " "),
+    };
+    match level {
+        "read" => t!("permission.read", name = name),
+        "write" => t!("permission.write", name = name),
+        "admin" => t!("permission.admin", name = name),
+        other => format!("{other} {name}"),
+    }
 }
 
 /// Handle polling state for access token acquisition
@@ -55,15 +209,13 @@ pub fn handle_polling_response(response: &AccessTokenResponse) -> PollingResult
                     PollingResult::Wait(Some(5))
                 }
             }
-            "expired_token" => PollingResult::Error(
-                "The device code has expired. Please run `login` again.".to_string(),
-            ),
-            "access_denied" => PollingResult::Error("Login cancelled by user.".to_string()),
-            _ => PollingResult::Error(format!("Unknown error: {error}")),
+            "expired_token" => PollingResult::Error(t!("auth.code_expired")),
+            "access_denied" => PollingResult::Error(t!("auth.access_denied")),
+            _ => PollingResult::Error(t!("auth.unknown_error", error = error)),
         }
     } else {
         // No error and no access token (this case should not normally occur)
-        PollingResult::Error("Invalid response from GitHub API".to_string())
+        PollingResult::Error(t!("github.invalid_response"))
     }
 }
 
@@ -92,6 +244,7 @@ mod tests {
             error_description: None,
             error_uri: None,
             interval: None,
+            ..Default::default()
         };
 
         let result = handle_polling_response(&response);
@@ -108,6 +261,7 @@ mod tests {
             error_description: None,
             error_uri: None,
             interval: None,
+            ..Default::default()
         };
 
         let result = handle_polling_response(&response);
@@ -124,6 +278,7 @@ mod tests {
             error_description: None,
             error_uri: None,
             interval: Some(10),
+            ..Default::default()
         };
 
         let result = handle_polling_response(&response);
@@ -140,6 +295,7 @@ mod tests {
             error_description: None,
             error_uri: None,
             interval: None,
+            ..Default::default()
         };
 
         let result = handle_polling_response(&response);
@@ -156,6 +312,7 @@ mod tests {
             error_description: None,
             error_uri: None,
             interval: None,
+            ..Default::default()
         };
 
         let result = handle_polling_response(&response);
@@ -177,6 +334,7 @@ mod tests {
             error_description: None,
             error_uri: None,
             interval: None,
+            ..Default::default()
         };
 
         let result = handle_polling_response(&response);
@@ -196,6 +354,7 @@ mod tests {
             error_description: None,
             error_uri: None,
             interval: None,
+            ..Default::default()
         };
 
         let result = handle_polling_response(&response);
@@ -204,4 +363,107 @@ mod tests {
             PollingResult::Error("Unknown error: unknown_error".to_string())
         );
     }
+
+    fn token_response(access_token: Option<&str>) -> AccessTokenResponse {
+        AccessTokenResponse {
+            access_token: access_token.map(str::to_string),
+            token_type: Some("bearer".to_string()),
+            scope: Some("".to_string()),
+            expires_in: Some(28800),
+            refresh_token: Some("ghr_refresh".to_string()),
+            refresh_token_expires_in: Some(15897600),
+            ..Default::default()
+        }
+    }
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_credentials_from_response() {
+        let now = at("2026-10-18T00:00:00Z");
+
+        let credentials =
+            Credentials::from_response(&token_response(Some("ghu_token")), now).unwrap();
+
+        assert_eq!(credentials.access_token, "ghu_token");
+        assert_eq!(credentials.expires_at, Some(at("2026-10-18T08:00:00Z")));
+        assert_eq!(credentials.refresh_token.as_deref(), Some("ghr_refresh"));
+        assert_eq!(
+            credentials.refresh_token_expires_at,
+            Some(at("2027-04-20T00:00:00Z"))
+        );
+        assert_eq!(Credentials::from_response(&token_response(None), now), None);
+    }
+
+    #[test]
+    fn test_credentials_roundtrip_and_legacy_format() {
+        let credentials = Credentials::from_response(
+            &token_response(Some("ghu_token")),
+            at("2026-10-18T00:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(Credentials::parse(&credentials.to_json()), Ok(credentials));
+
+        let legacy = Credentials::parse("gho_plain\n").unwrap();
+        assert_eq!(legacy.access_token, "gho_plain");
+        assert_eq!(legacy.expires_at, None);
+        assert_eq!(legacy.refresh_token, None);
+
+        assert!(Credentials::parse("{not json").is_err());
+    }
+
+    #[test]
+    fn test_credentials_expiry() {
+        let credentials = Credentials::from_response(
+            &token_response(Some("ghu_token")),
+            at("2026-10-18T00:00:00Z"),
+        )
+        .unwrap();
+
+        assert!(!credentials.is_expiring(at("2026-10-18T07:00:00Z")));
+        assert!(credentials.is_expiring(at("2026-10-18T07:56:00Z")));
+        assert_eq!(
+            credentials.usable_refresh_token(at("2026-10-18T09:00:00Z")),
+            Some("ghr_refresh")
+        );
+        assert_eq!(
+            credentials.usable_refresh_token(at("2027-05-01T00:00:00Z")),
+            None
+        );
+        assert!(
+            !Credentials::parse("gho_plain")
+                .unwrap()
+                .is_expiring(at("2030-01-01T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_describe_installation_permissions() {
+        let json = r#"{"total_count":2,"installations":[
+            {"id":1,"account":{"login":"octocat"},"app_slug":"atat",
+             "permissions":{"contents":"read","issues":"read","metadata":"read"}},
+            {"id":2,"account":{"login":"octo-org"},"repository_selection":"selected",
+             "permissions":{"issues":"write","pull_requests":"read"}}
+        ]}"#;
+
+        let installations = parse_installations(json).unwrap();
+        assert_eq!(installations[0].account.login, "octocat");
+        assert_eq!(installations[0].app_slug.as_deref(), Some("atat"));
+        assert_eq!(
+            installations[1].repository_selection.as_deref(),
+            Some("selected")
+        );
+        assert_eq!(
+            describe_installation_permissions(&installations),
+            vec![
+                "Read repository contents".to_string(),
+                "Read and write issues".to_string(),
+                "Read pull requests".to_string(),
+            ]
+        );
+        assert_eq!(describe_installation_permissions(&[]), Vec::<String>::new());
+        assert!(parse_installations("not json").is_err());
+    }
 }
@@ -0,0 +1,512 @@
+use crate::auth::{Credentials, Installation};
+use crate::i18n::t;
+use crate::output::{self, Mark};
+use crate::token_source::TokenSource;
+use chrono::{DateTime, Utc};
+
+/// Outcome of a single `atat auth status` check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Warning,
+    Error,
+    Info,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Warning => "warning",
+            Status::Error => "error",
+            Status::Info => "info",
+        }
+    }
+
+    fn mark(self) -> Mark {
+        match self {
+            Status::Ok => Mark::Success,
+            Status::Warning => Mark::Warning,
+            Status::Error => Mark::Failure,
+            Status::Info => Mark::Info,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub status: Status,
+    pub message: String,
+    /// What the user has to do to fix the problem
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn ok(message: impl Into<String>) -> Self {
+        Check {
+            status: Status::Ok,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn info(message: impl Into<String>) -> Self {
+        Check {
+            status: Status::Info,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn problem(status: Status, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Check {
+            status,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    /// Indented output lines, with the fix on its own line
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "  {}",
+            output::status(self.status.mark(), &self.message)
+        )];
+        lines.extend(
+            self.fix
+                .iter()
+                .map(|fix| format!("    {}", t!("auth_status.fix", fix = fix))),
+        );
+        lines
+    }
+}
+
+/// Access to a repository as reported by `GET /repos/{owner}/{repo}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoAccess {
+    /// The repository is visible; `can_triage` tells whether issues of others can be closed and edited
+    Accessible { can_triage: bool },
+    /// 404 or 403: the repository does not exist or the token cannot see it
+    NotFound,
+}
+
+/// Parse the `permissions` of a `GET /repos/{owner}/{repo}` response
+pub fn parse_repo_access(json: &str) -> Result<RepoAccess, String> {
+    let repository: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| t!("auth_status.parse_repository_failed", error = e))?;
+    let permissions = &repository["permissions"];
+    let can_triage = ["admin", "maintain", "push", "triage"]
+        .iter()
+        .any(|role| permissions[role].as_bool() == Some(true));
+    Ok(RepoAccess::Accessible { can_triage })
+}
+
+/// `atat login` command for the account, with `--account` for named accounts
+pub fn login_command(account: &str) -> String {
+    if account == crate::storage::DEFAULT_ACCOUNT {
+        "atat login".to_string()
+    } else {
+        format!("atat login --account {account}")
+    }
+}
+
+/// Fix for a token from `source` that GitHub rejects or that is missing
+fn token_fix(source: Option<TokenSource>, account: &str) -> String {
+    match source {
+        Some(TokenSource::Flag) => t!("auth_status.fix_flag"),
+        Some(TokenSource::Env(name)) => t!("auth_status.fix_env", name = name),
+        Some(TokenSource::GhCli) => t!("auth_status.fix_gh"),
+        Some(TokenSource::Stored) | None => {
+            t!("auth_status.fix_run", command = login_command(account))
+        }
+    }
+}
+
+/// Check the token used for `account`.
+///
+/// `login` is the result of `GET /user` (`None` for a rejected token); `credentials`
+/// are the stored credentials when the token comes from `atat login`.
+pub fn check_token(
+    account: &str,
+    source: Option<TokenSource>,
+    login: Option<&str>,
+    credentials: Option<&Credentials>,
+    now: DateTime<Utc>,
+) -> Vec<Check> {
+    let Some(source) = source else {
+        return vec![Check::problem(
+            Status::Error,
+            t!("auth_status.not_logged_in"),
+            token_fix(None, account),
+        )];
+    };
+    let Some(login) = login else {
+        return vec![Check::problem(
+            Status::Error,
+            t!("auth_status.token_invalid", source = source),
+            token_fix(Some(source), account),
+        )];
+    };
+
+    let mut checks = vec![Check::ok(t!(
+        "auth_status.logged_in",
+        login = login,
+        source = source
+    ))];
+    if let Some(expires_at) = credentials.and_then(|credentials| credentials.expires_at) {
+        let expires = expires_at.format("%Y-%m-%d %H:%M UTC");
+        match credentials.and_then(|credentials| credentials.usable_refresh_token(now)) {
+            Some(_) => checks.push(Check::ok(t!(
+                "auth_status.token_refreshed",
+                expires = expires
+            ))),
+            None => checks.push(Check::problem(
+                Status::Warning,
+                t!("auth_status.token_expires", expires = expires),
+                t!(
+                    "auth_status.fix_run_after_expiry",
+                    command = login_command(account)
+                ),
+            )),
+        }
+    }
+    checks
+}
+
+/// Page where the atat GitHub App can be installed, if its slug is known
+fn install_url(installations: &[Installation], app_slug: Option<&str>) -> Option<String> {
+    app_slug
+        .or_else(|| {
+            installations
+                .iter()
+                .find_map(|installation| installation.app_slug.as_deref())
+        })
+        .map(|slug| format!("https://github.com/apps/{slug}/installations/new"))
+}
+
+/// The installation that covers `repo`'s owner
+pub fn installation_for<'a>(
+    repo: &str,
+    installations: &'a [Installation],
+) -> Option<&'a Installation> {
+    let owner = repo.split('/').next().unwrap_or(repo);
+    installations
+        .iter()
+        .find(|installation| installation.account.login.eq_ignore_ascii_case(owner))
+}
+
+/// Check that the atat GitHub App is installed on the owner of `repo` and may write issues.
+///
+/// `installations` is `None` when the token is not a GitHub App user token
+/// (a personal access token or a `gh` token), which has no installations to check.
+pub fn check_installation(
+    repo: &str,
+    installations: Option<&[Installation]>,
+    app_slug: Option<&str>,
+) -> Vec<Check> {
+    let Some(installations) = installations else {
+        return vec![Check::info(t!("auth_status.not_app_token"))];
+    };
+    let owner = repo.split('/').next().unwrap_or(repo);
+
+    let Some(installation) = installation_for(repo, installations) else {
+        let fix = match install_url(installations, app_slug) {
+            Some(url) => t!("auth_status.fix_install_at", owner = owner, url = url),
+            None => t!("auth_status.fix_install", owner = owner),
+        };
+        return vec![Check::problem(
+            Status::Error,
+            t!("auth_status.not_installed", owner = owner),
+            fix,
+        )];
+    };
+
+    let settings = installation_settings(installation);
+    let mut checks = vec![Check::ok(t!("auth_status.installed", owner = owner))];
+    match installation.permissions.get("issues").map(String::as_str) {
+        Some("write") | Some("admin") => checks.push(Check::ok(t!("auth_status.issues_write"))),
+        level => checks.push(Check::problem(
+            Status::Error,
+            t!(
+                "auth_status.issues_permission",
+                owner = owner,
+                access = level.map_or(t!("auth_status.no_issues_access"), |level| format!(
+                    "issues: {level}"
+                ))
+            ),
+            t!("auth_status.fix_permissions", settings = settings),
+        )),
+    }
+    checks
+}
+
+/// Where the installation settings are, to append to a fix
+fn installation_settings(installation: &Installation) -> String {
+    installation
+        .html_url
+        .as_deref()
+        .map(|url| t!("auth_status.settings_at", url = url))
+        .unwrap_or_default()
+}
+
+/// Failed check for a repository whose access could not be looked up
+pub fn check_repository_failed(repo: &str, error: &str) -> Check {
+    Check::problem(
+        Status::Error,
+        t!(
+            "auth_status.repository_check_failed",
+            repo = repo,
+            error = error
+        ),
+        t!("auth_status.fix_retry"),
+    )
+}
+
+/// Check that the token can see `repo` and manage its issues.
+pub fn check_repository(
+    repo: &str,
+    installation: Option<&Installation>,
+    access: RepoAccess,
+) -> Check {
+    match access {
+        RepoAccess::Accessible { can_triage: true } => {
+            Check::ok(t!("auth_status.repository_accessible", repo = repo))
+        }
+        RepoAccess::Accessible { can_triage: false } => Check::problem(
+            Status::Warning,
+            t!("auth_status.cannot_triage", repo = repo),
+            t!("auth_status.fix_triage"),
+        ),
+        RepoAccess::NotFound => match installation {
+            Some(installation)
+                if installation.repository_selection.as_deref() == Some("selected") =>
+            {
+                Check::problem(
+                    Status::Error,
+                    t!("auth_status.repository_not_selected", repo = repo),
+                    t!(
+                        "auth_status.fix_repository_access",
+                        settings = installation_settings(installation)
+                    ),
+                )
+            }
+            _ => Check::problem(
+                Status::Error,
+                t!("auth_status.repository_not_found", repo = repo),
+                t!("auth_status.fix_repository_name"),
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::InstallationAccount;
+
+    fn installation(owner: &str, selection: &str, issues: &str) -> Installation {
+        Installation {
+            account: InstallationAccount {
+                login: owner.to_string(),
+            },
+            app_slug: Some("atat-app".to_string()),
+            html_url: Some("https://github.com/settings/installations/1".to_string()),
+            repository_selection: Some(selection.to_string()),
+            permissions: [("issues".to_string(), issues.to_string())]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_check_token_missing_or_invalid() {
+        let now = at("2026-10-18T00:00:00Z");
+
+        let checks = check_token("work", None, None, None, now);
+        assert_eq!(checks[0].status, Status::Error);
+        assert_eq!(
+            checks[0].fix.as_deref(),
+            Some("run `atat login --account work`")
+        );
+
+        let checks = check_token(
+            "default",
+            Some(TokenSource::Env("GH_TOKEN")),
+            None,
+            None,
+            now,
+        );
+        assert_eq!(
+            checks[0].message,
+            "Token from GH_TOKEN environment variable is invalid or expired"
+        );
+        assert_eq!(
+            checks[0].fix.as_deref(),
+            Some("update or unset the GH_TOKEN environment variable")
+        );
+    }
+
+    #[test]
+    fn test_check_token_expiry() {
+        let credentials = Credentials {
+            access_token: "ghu_token".to_string(),
+            expires_at: Some(at("2026-10-18T08:00:00Z")),
+            refresh_token: Some("ghr_refresh".to_string()),
+            refresh_token_expires_at: Some(at("2027-04-20T00:00:00Z")),
+        };
+
+        let checks = check_token(
+            "default",
+            Some(TokenSource::Stored),
+            Some("octocat"),
+            Some(&credentials),
+            at("2026-10-18T00:00:00Z"),
+        );
+        assert_eq!(
+            checks,
+            vec![
+                Check::ok("Logged in as octocat (token from atat login)"),
+                Check::ok("Token expires at 2026-10-18 08:00 UTC and is refreshed automatically"),
+            ]
+        );
+
+        let checks = check_token(
+            "default",
+            Some(TokenSource::Stored),
+            Some("octocat"),
+            Some(&credentials),
+            at("2027-05-01T00:00:00Z"),
+        );
+        assert_eq!(checks[1].status, Status::Warning);
+    }
+
+    #[test]
+    fn test_check_installation() {
+        let installations = vec![installation("octo-org", "all", "write")];
+
+        let checks = check_installation("Octo-Org/repo", Some(&installations), None);
+        assert!(checks.iter().all(|check| check.status == Status::Ok));
+
+        let checks = check_installation("someone/repo", Some(&installations), None);
+        assert_eq!(
+            checks,
+            vec![Check::problem(
+                Status::Error,
+                "atat GitHub App is not installed on someone",
+                "install the atat GitHub App on someone at https://github.com/apps/atat-app/installations/new",
+            )]
+        );
+
+        let checks = check_installation("someone/repo", Some(&[]), None);
+        assert_eq!(
+            checks[0].fix.as_deref(),
+            Some("install the atat GitHub App on someone")
+        );
+
+        assert_eq!(
+            check_installation("someone/repo", None, None)[0].status,
+            Status::Info
+        );
+    }
+
+    #[test]
+    fn test_check_installation_read_only_issues() {
+        let installations = vec![installation("octo-org", "all", "read")];
+
+        let checks = check_installation("octo-org/repo", Some(&installations), None);
+
+        assert_eq!(
+            checks[1],
+            Check::problem(
+                Status::Error,
+                "atat needs issues: write on octo-org but has issues: read",
+                "approve the requested permissions in the installation settings at https://github.com/settings/installations/1",
+            )
+        );
+    }
+
+    #[test]
+    fn test_check_repository() {
+        let selected = installation("octo-org", "selected", "write");
+
+        assert_eq!(
+            check_repository(
+                "octo-org/repo",
+                Some(&selected),
+                RepoAccess::Accessible { can_triage: true }
+            )
+            .status,
+            Status::Ok
+        );
+        assert_eq!(
+            check_repository(
+                "octo-org/repo",
+                Some(&selected),
+                RepoAccess::Accessible { can_triage: false }
+            )
+            .status,
+            Status::Warning
+        );
+        assert_eq!(
+            check_repository("octo-org/repo", Some(&selected), RepoAccess::NotFound).message,
+            "octo-org/repo is not among the repositories atat can access"
+        );
+        assert_eq!(
+            check_repository("octo-org/repo", None, RepoAccess::NotFound).message,
+            "Repository octo-org/repo not found"
+        );
+    }
+
+    #[test]
+    fn test_check_repository_failed() {
+        let check = check_repository_failed("octo-org/repo", "HTTP 502 Bad Gateway");
+        assert_eq!(check.status, Status::Error);
+        assert_eq!(
+            check.message,
+            "Could not check octo-org/repo: HTTP 502 Bad Gateway"
+        );
+        assert!(check.fix.unwrap().contains("atat auth status"));
+    }
+
+    #[test]
+    fn test_parse_repo_access() {
+        assert_eq!(
+            parse_repo_access(r#"{"permissions":{"admin":false,"triage":true,"pull":true}}"#),
+            Ok(RepoAccess::Accessible { can_triage: true })
+        );
+        assert_eq!(
+            parse_repo_access(r#"{"permissions":{"pull":true}}"#),
+            Ok(RepoAccess::Accessible { can_triage: false })
+        );
+        assert!(parse_repo_access("<html>").is_err());
+    }
+
+    #[test]
+    fn test_check_lines() {
+        let check = Check::problem(Status::Error, "Not logged in", "run `atat login`");
+        assert_eq!(
+            check.lines(),
+            vec!["  X Not logged in", "    Fix: run `atat login`"]
+        );
+        assert_eq!(Check::ok("Fine").lines(), vec!["  ✓ Fine"]);
+    }
+
+    #[test]
+    fn test_check_lines_in_japanese() {
+        let lines = crate::i18n::in_language(crate::i18n::Language::Japanese, || {
+            let now = at("2026-10-18T00:00:00Z");
+            check_token("work", None, None, None, now)[0].lines()
+        });
+        assert_eq!(
+            lines,
+            vec![
+                "  X ログインしていません",
+                "    対処: `atat login --account work` を実行してください"
+            ]
+        );
+    }
+}
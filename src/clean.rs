@@ -45,6 +45,7 @@ mod tests {
             text: text.to_string(),
             is_checked: true,
             issue_number: Some(issue_number),
+            ..Default::default()
         }
     }
 
@@ -53,6 +54,7 @@ mod tests {
             text: text.to_string(),
             is_checked: false,
             issue_number: Some(issue_number),
+            ..Default::default()
         }
     }
 
@@ -61,6 +63,7 @@ mod tests {
             text: text.to_string(),
             is_checked: true,
             issue_number: None,
+            ..Default::default()
         }
     }
 
@@ -69,6 +72,7 @@ mod tests {
             number,
             title: String::new(),
             state: IssueState::Open,
+            closed_at: None,
         }
     }
 
@@ -77,6 +81,7 @@ mod tests {
             number,
             title: String::new(),
             state: IssueState::Closed,
+            closed_at: None,
         }
     }
 
@@ -1 +1,2 @@
+pub mod completion;
 pub mod parser;
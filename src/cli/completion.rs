@@ -0,0 +1,154 @@
+use clap_complete::engine::CompletionCandidate;
+
+use crate::config;
+use crate::git_remote;
+use crate::storage;
+use crate::todo;
+
+/// Environment variable the shell scripts set to ask atat for completions
+pub const COMPLETE_VAR: &str = "ATAT_COMPLETE";
+
+/// Settings of the project config file, or none if it cannot be read.
+///
+/// Only the project file is read: completion does not see `--config` flags or
+/// `ATAT_*` variables, and the global config rarely sets these settings.
+fn project_config() -> Option<std::collections::HashMap<config::ConfigKey, serde_json::Value>> {
+    let storage = storage::LocalConfigStorage::new().ok()?;
+    storage::ConfigStorage::load_config(&storage).ok()
+}
+
+/// Configured repositories, by remote name and by `owner/repo`
+pub fn remotes() -> Vec<CompletionCandidate> {
+    let repositories = project_config()
+        .and_then(|config| config::repositories(&config).ok())
+        .unwrap_or_default();
+    remote_candidates(&repositories)
+}
+
+fn remote_candidates(repositories: &[config::Repository]) -> Vec<CompletionCandidate> {
+    let mut candidates = Vec::new();
+    for repository in repositories {
+        if let Some(remote) = &repository.remote {
+            candidates
+                .push(CompletionCandidate::new(remote).help(Some(repository.name.clone().into())));
+        }
+        candidates.push(CompletionCandidate::new(&repository.name));
+    }
+    candidates
+}
+
+/// Remotes of the git repository containing the project
+pub fn git_remotes() -> Vec<CompletionCandidate> {
+    let Ok(root) = storage::project_root() else {
+        return Vec::new();
+    };
+    let git_config = root
+        .ancestors()
+        .find_map(git_remote::git_config_path)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    git_remote::remote_names(&git_config)
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Issue numbers of the tasks in the task file, described by their text
+pub fn issue_numbers() -> Vec<CompletionCandidate> {
+    let Ok(root) = storage::project_root() else {
+        return Vec::new();
+    };
+    let todo_file = config::todo_file(&project_config().unwrap_or_default());
+    let todo_path = root.join(todo_file);
+    let items = std::fs::read_to_string(&todo_path)
+        .ok()
+        .and_then(|content| todo::TodoFormat::from_path(&todo_path).parse(&content).ok())
+        .unwrap_or_default();
+    issue_candidates(&items)
+}
+
+/// Checked tasks come first, as those are the ones `clean` removes
+fn issue_candidates(items: &[todo::TodoItem]) -> Vec<CompletionCandidate> {
+    let mut items: Vec<_> = items
+        .iter()
+        .filter(|item| item.issue_number.is_some())
+        .collect();
+    items.sort_by_key(|item| !item.is_checked);
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let number = item.issue_number?;
+            Some(CompletionCandidate::new(number.to_string()).help(Some(item.text.clone().into())))
+        })
+        .collect()
+}
+
+/// Setting names, described
+pub fn config_keys() -> Vec<CompletionCandidate> {
+    config::ConfigKey::all()
+        .iter()
+        .map(|key| CompletionCandidate::new(key.as_str()).help(Some(key.description().into())))
+        .collect()
+}
+
+/// Logged-in accounts
+pub fn accounts() -> Vec<CompletionCandidate> {
+    let mut accounts = vec![storage::DEFAULT_ACCOUNT.to_string()];
+    if let Ok(registry) = storage::AccountRegistry::new() {
+        accounts.extend(registry.list().unwrap_or_default());
+    }
+    accounts.into_iter().map(CompletionCandidate::new).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_candidates() {
+        let repositories = vec![
+            config::Repository {
+                name: "owner/a".to_string(),
+                account: None,
+                remote: Some("origin".to_string()),
+                host: None,
+            },
+            config::Repository {
+                name: "owner/b".to_string(),
+                account: Some("work".to_string()),
+                remote: None,
+                host: None,
+            },
+        ];
+
+        let values: Vec<_> = remote_candidates(&repositories)
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(values, vec!["origin", "owner/a", "owner/b"]);
+    }
+
+    #[test]
+    fn test_issue_candidates() {
+        let items = todo::TodoFormat::Markdown
+            .parse("- [ ] Open (#1)\n- [ ] No issue\n- [x] Done (#2)\n")
+            .unwrap();
+
+        let candidates = issue_candidates(&items);
+
+        let values: Vec<_> = candidates
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(values, vec!["2", "1"]);
+        assert_eq!(candidates[0].get_help().unwrap().to_string(), "Done");
+    }
+
+    #[test]
+    fn test_config_keys() {
+        let candidates = config_keys();
+        assert_eq!(candidates.len(), config::ConfigKey::all().len());
+        assert_eq!(candidates[0].get_value(), "repositories");
+        assert!(candidates[0].get_help().is_some());
+    }
+}
@@ -1,85 +1,671 @@
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{ArgAction, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::engine::ArgValueCandidates;
+
+use crate::cli::completion;
+use crate::config;
+use crate::git_remote;
+use crate::i18n::t;
+use crate::output;
+
 /// Enum representing CLI commands
 #[derive(Debug, PartialEq)]
 pub enum Command {
-    Login,
+    Login {
+        no_browser: bool,
+    },
+    Logout {
+        hostname: String,
+        revoke: bool,
+    },
     Whoami,
-    Push,
+    AuthStatus,
+    /// `import` is `None` when neither `--import` nor `--no-import` is given
+    Init {
+        import: Option<bool>,
+    },
+    ConfigList {
+        show_origin: bool,
+    },
+    ConfigGet {
+        key: String,
+    },
+    ConfigSet {
+        key: String,
+        value: String,
+        global: bool,
+    },
+    ConfigUnset {
+        key: String,
+        global: bool,
+    },
+    Push {
+        dry_run: bool,
+    },
     Pull,
-    Clean { dry_run: bool },
+    /// `section` is the heading to add the task under
+    Add {
+        text: String,
+        section: Option<String>,
+        labels: Vec<String>,
+        assignees: Vec<String>,
+        body: Option<String>,
+    },
+    /// `issues` limits the tasks removed to those of these issues
+    Clean {
+        dry_run: bool,
+        issues: Vec<u64>,
+    },
+    Scan {
+        dry_run: bool,
+    },
     RemoteList,
-    RemoteAdd { repo: String },
-    RemoteRemove { repo: String },
+    RemoteAdd {
+        /// Name given to the remote (`atat remote add upstream owner/repo`)
+        name: Option<String>,
+        target: RemoteTarget,
+    },
+    RemoteRemove {
+        repo: String,
+    },
+    Completions {
+        shell: clap_complete::Shell,
+    },
+    /// `output_dir` is `None` to print the page of `atat` itself
+    Man {
+        output_dir: Option<std::path::PathBuf>,
+    },
+    /// No command given
     Help,
-    Unknown(String),
 }
 
-/// Valid remote subcommands
-const VALID_REMOTE_SUBCOMMANDS: &[&str] = &["add", "remove"];
+/// Repository given to `remote add`
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteTarget {
+    /// `owner/repo`, also when given as an HTTPS or SSH URL
+    Repository {
+        name: String,
+        /// GitHub Enterprise Server host of a URL; `None` for github.com
+        host: Option<String>,
+    },
+    /// Name of a git remote whose URL points to the repository
+    GitRemote(String),
+}
+
+/// Options accepted before or after any command
+#[derive(Debug, Default, PartialEq)]
+pub struct GlobalOptions {
+    /// Token given with `--token`
+    pub token: Option<String>,
+    /// Login account given with `--account`
+    pub account: Option<String>,
+    /// `<key>=<value>` settings given with `--config` or `--file`, overriding the config files
+    pub config: Vec<String>,
+    /// Format given with `--output`
+    pub output: output::OutputFormat,
+    /// Number of `-v` flags
+    pub verbose: u8,
+    /// `--no-color` given
+    pub no_color: bool,
+}
+
+/// Host atat authenticates against
+pub const DEFAULT_HOSTNAME: &str = crate::github::GITHUB_HOST;
+
+/// Sync TODO.md with GitHub Issues
+#[derive(Debug, Parser)]
+#[command(name = "atat", version, about)]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(Debug, Args)]
+struct GlobalArgs {
+    /// GitHub token to use instead of the stored one
+    #[arg(long, global = true, value_name = "TOKEN")]
+    token: Option<String>,
+
+    /// Login account to use
+    #[arg(long, global = true, value_name = "NAME", add = ArgValueCandidates::new(completion::accounts))]
+    account: Option<String>,
+
+    /// Override a setting for this run; can be repeated
+    #[arg(id = CONFIG_ARG, long, value_name = "KEY=VALUE", action = ArgAction::Append)]
+    config: Vec<String>,
+
+    /// Task file to use, same as `--config todo_file=<FILE>`
+    #[arg(long, global = true, value_name = "FILE")]
+    file: Option<String>,
+
+    /// Print results as text or as one JSON object per line
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "text")]
+    output: output::OutputFormat,
+
+    /// Log GitHub API calls on stderr; repeat (-vv) to include headers and bodies
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Print without colours, same as setting NO_COLOR
+    #[arg(long, global = true)]
+    no_color: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum CliCommand {
+    /// Log in to GitHub in the browser
+    Login {
+        /// Only print the URL to visit instead of offering to open it
+        #[arg(long)]
+        no_browser: bool,
+    },
+    /// Log out and delete the stored token
+    Logout {
+        /// Host to log out of
+        #[arg(long, value_name = "HOST", default_value = DEFAULT_HOSTNAME)]
+        hostname: String,
+
+        /// Also revoke the authorization of the app on GitHub
+        #[arg(long)]
+        revoke: bool,
+    },
+    /// Show the logged-in GitHub user
+    Whoami,
+    /// Check authentication
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommand,
+    },
+    /// Set up the project from the origin git remote
+    Init {
+        /// Import the open issues of the repository without asking
+        #[arg(long, conflicts_with = "no_import")]
+        import: bool,
+
+        /// Do not import the open issues of the repository
+        #[arg(long)]
+        no_import: bool,
+    },
+    /// Show or change settings
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommand>,
+    },
+    /// Create and close GitHub issues from the task file
+    Push {
+        /// Show the changes without making them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Update the task file from GitHub issues
+    Pull,
+    /// Add a task to the task file and create its issue
+    Add {
+        /// Text of the task
+        #[arg(value_name = "TEXT")]
+        text: String,
+
+        /// Heading to add the task under; it is created if missing
+        #[arg(long, value_name = "HEADING")]
+        section: Option<String>,
+
+        /// Label for the issue; can be repeated
+        #[arg(long = "label", value_name = "LABEL")]
+        labels: Vec<String>,
+
+        /// GitHub user to assign the issue to; can be repeated
+        #[arg(long = "assignee", value_name = "LOGIN")]
+        assignees: Vec<String>,
+
+        /// Description of the issue
+        #[arg(long, value_name = "TEXT")]
+        body: Option<String>,
+    },
+    /// Remove checked tasks whose issues are closed
+    Clean {
+        /// Find the tasks without removing them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only remove the tasks of these issues, e.g. `12` or `#12`
+        #[arg(
+            value_name = "ISSUE",
+            value_parser = parse_issue_number,
+            add = ArgValueCandidates::new(completion::issue_numbers)
+        )]
+        issues: Vec<u64>,
+    },
+    /// Create issues from TODO comments in the source code
+    Scan {
+        /// List the comments without creating issues
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List, add or remove the repositories to sync with
+    Remote {
+        #[command(subcommand)]
+        command: Option<RemoteCommand>,
+    },
+    /// Print the shell completion script
+    ///
+    /// Load it from the shell startup file, e.g. `source <(atat completions bash)` in ~/.bashrc.
+    /// Completions suggest configured remotes, git remotes, setting names, accounts and the
+    /// issue numbers of tasks.
+    Completions {
+        #[arg(value_name = "SHELL")]
+        shell: clap_complete::Shell,
+    },
+    /// Print the man page, or write the pages of all commands to a directory
+    Man {
+        /// Directory to write `atat.1` and a page per command to
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum AuthCommand {
+    /// Check the token, the app installation and access to each repository
+    Status,
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigCommand {
+    /// List the effective settings
+    List {
+        /// Show where each setting comes from
+        #[arg(long)]
+        show_origin: bool,
+    },
+    /// Print the value of a setting
+    Get {
+        /// Name of the setting
+        #[arg(add = ArgValueCandidates::new(completion::config_keys))]
+        key: String,
+    },
+    /// Change a setting
+    Set {
+        /// Change the global config instead of the project one
+        #[arg(long)]
+        global: bool,
+
+        /// Name of the setting
+        #[arg(add = ArgValueCandidates::new(completion::config_keys))]
+        key: String,
+
+        /// New value; lists are separated by commas
+        value: String,
+    },
+    /// Remove a setting
+    Unset {
+        /// Change the global config instead of the project one
+        #[arg(long)]
+        global: bool,
 
-/// Parse command line arguments and return a Command
+        /// Name of the setting
+        #[arg(add = ArgValueCandidates::new(completion::config_keys))]
+        key: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum RemoteCommand {
+    /// Add a repository to sync with
+    #[command(override_usage = "atat remote add [OPTIONS] [NAME] <REPOSITORY>")]
+    Add {
+        /// `owner/repo`, a github.com URL or the name of a git remote,
+        /// optionally preceded by a name for the remote
+        #[arg(
+            value_name = "REPOSITORY",
+            num_args = 1..=2,
+            required = true,
+            add = ArgValueCandidates::new(completion::git_remotes)
+        )]
+        args: Vec<String>,
+    },
+    /// Remove a repository
+    Remove {
+        /// Name or `owner/repo` of the repository
+        #[arg(
+            value_name = "REMOTE",
+            value_parser = parse_remote_reference,
+            add = ArgValueCandidates::new(completion::remotes)
+        )]
+        remote: String,
+    },
+}
+
+/// Definition of the command line, for help and completions
+pub fn command() -> clap::Command {
+    let command = Cli::command();
+    let config_arg = command
+        .get_arguments()
+        .find(|arg| arg.get_id() == CONFIG_ARG)
+        .cloned()
+        .expect("--config is defined on the top-level command");
+    with_arg(command, &config_arg)
+}
+
+/// Id of `--config`
+const CONFIG_ARG: &str = "config";
+
+/// Add `arg` to every subcommand, however deeply nested.
+///
+/// `--config` is added this way rather than made `global`: clap keeps only the values given
+/// to the innermost command when a global option is repeated on several levels, while each
+/// level's own values can be read back in order (see `config_values`).
+fn with_arg(command: clap::Command, arg: &clap::Arg) -> clap::Command {
+    command.mut_subcommands(|subcommand| with_arg(subcommand.arg(arg.clone()), arg))
+}
+
+/// Values of every `--config`, in order: each command's before its subcommand's
+fn config_values(matches: &clap::ArgMatches) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut level = Some(matches);
+    while let Some(matches) = level {
+        values.extend(
+            matches
+                .try_get_many::<String>(CONFIG_ARG)
+                .ok()
+                .flatten()
+                .into_iter()
+                .flatten()
+                .cloned(),
+        );
+        level = matches.subcommand().map(|(_, subcommand)| subcommand);
+    }
+    values
+}
+
+/// Help shown when atat is run without a command
+pub fn help() -> String {
+    command().render_help().to_string()
+}
+
+/// Parse command line arguments into global options and a Command
 ///
 /// # Arguments
 /// * `args` - Command line arguments (including program name)
 ///
 /// # Returns
-/// * `Command` - The parsed command
-pub fn parse_args(args: &[String]) -> Command {
-    match args.len() {
-        0 | 1 => Command::Help,
-        2 => match args[1].as_str() {
-            "login" => Command::Login,
-            "whoami" => Command::Whoami,
-            "push" => Command::Push,
-            "pull" => Command::Pull,
-            "clean" => Command::Clean { dry_run: false },
-            "remote" => Command::RemoteList,
-            "help" => Command::Help,
-            cmd => Command::Unknown(cmd.to_string()),
+/// * `Err` - Usage errors, and also the output of `--help` and `--version`,
+///   which [`clap::Error::use_stderr`] tells apart
+pub fn parse_args(args: &[String]) -> Result<(GlobalOptions, Command), clap::Error> {
+    let matches = command().try_get_matches_from(args)?;
+    let cli = Cli::from_arg_matches(&matches)?;
+
+    let GlobalArgs {
+        token,
+        account,
+        config: _,
+        file,
+        output,
+        verbose,
+        no_color,
+    } = cli.global;
+    let mut config = config_values(&matches);
+    config.extend(file.map(|file| format!("{}={file}", config::ConfigKey::TodoFile.as_str())));
+    let global_options = GlobalOptions {
+        token,
+        account,
+        config,
+        output,
+        verbose,
+        no_color,
+    };
+
+    let command = match cli.command {
+        None => Command::Help,
+        Some(CliCommand::Login { no_browser }) => Command::Login { no_browser },
+        Some(CliCommand::Logout { hostname, revoke }) => Command::Logout { hostname, revoke },
+        Some(CliCommand::Whoami) => Command::Whoami,
+        Some(CliCommand::Auth {
+            command: AuthCommand::Status,
+        }) => Command::AuthStatus,
+        Some(CliCommand::Init { import, no_import }) => Command::Init {
+            import: (import || no_import).then_some(import),
         },
-        3 => match (args[1].as_str(), args[2].as_str()) {
-            ("clean", "--dry-run") => Command::Clean { dry_run: true },
-            ("remote", sub_cmd) => {
-                if VALID_REMOTE_SUBCOMMANDS.contains(&sub_cmd) {
-                    Command::Unknown(format!(
-                        "Missing repository argument. Usage: atat remote {sub_cmd} <owner>/<repo>"
-                    ))
-                } else {
-                    Command::Unknown(format!("remote {sub_cmd}"))
-                }
+        Some(CliCommand::Config { command }) => match command {
+            None => Command::ConfigList { show_origin: false },
+            Some(ConfigCommand::List { show_origin }) => Command::ConfigList { show_origin },
+            Some(ConfigCommand::Get { key }) => Command::ConfigGet { key },
+            Some(ConfigCommand::Set { global, key, value }) => {
+                Command::ConfigSet { key, value, global }
             }
-            (cmd, _) => Command::Unknown(cmd.to_string()),
+            Some(ConfigCommand::Unset { global, key }) => Command::ConfigUnset { key, global },
         },
-        _ => match (args[1].as_str(), args[2].as_str()) {
-            ("remote", sub_cmd) => {
-                if !VALID_REMOTE_SUBCOMMANDS.contains(&sub_cmd) {
-                    return Command::Unknown(format!("remote {sub_cmd}"));
-                }
+        Some(CliCommand::Push { dry_run }) => Command::Push { dry_run },
+        Some(CliCommand::Pull) => Command::Pull,
+        Some(CliCommand::Add {
+            text,
+            section,
+            labels,
+            assignees,
+            body,
+        }) => Command::Add {
+            text,
+            section,
+            labels,
+            assignees,
+            body,
+        },
+        Some(CliCommand::Clean { dry_run, issues }) => Command::Clean { dry_run, issues },
+        Some(CliCommand::Scan { dry_run }) => Command::Scan { dry_run },
+        Some(CliCommand::Remote { command }) => match command {
+            None => Command::RemoteList,
+            Some(RemoteCommand::Add { args }) => parse_remote_add(&args)?,
+            Some(RemoteCommand::Remove { remote }) => Command::RemoteRemove { repo: remote },
+        },
+        Some(CliCommand::Completions { shell }) => Command::Completions { shell },
+        Some(CliCommand::Man { output_dir }) => Command::Man { output_dir },
+    };
 
-                let repo_arg = &args[3];
-                let parts: Vec<&str> = repo_arg.split('/').collect();
-                if parts.len() == 2
-                    && !parts[0].is_empty()
-                    && !parts[1].is_empty()
-                    && !parts[0].contains('/')
-                    && !parts[1].contains('/')
-                {
-                    match sub_cmd {
-                        "add" => Command::RemoteAdd {
-                            repo: repo_arg.clone(),
-                        },
-                        "remove" => Command::RemoteRemove {
-                            repo: repo_arg.clone(),
-                        },
-                        _ => unreachable!(),
-                    }
-                } else {
-                    Command::Unknown(
-                        "Invalid repository format. Please use <owner>/<repo>.".to_string(),
-                    )
+    Ok((global_options, command))
+}
+
+/// Text of a usage error from [`parse_args`], with the wording from the message catalog.
+///
+/// Errors that print help (such as a command group run without a subcommand) and
+/// the kinds atat's arguments cannot raise keep clap's English text.
+pub fn usage_error_message(error: &clap::Error) -> String {
+    let context = |kind| error.get(kind).map(ToString::to_string);
+    let quoted = |kind| match error.get(kind) {
+        Some(ContextValue::Strings(values)) => Some(format!("'{}'", values.join("', '"))),
+        Some(value) => Some(format!("'{value}'")),
+        None => None,
+    };
+    let listed = |values: Option<&ContextValue>| match values {
+        Some(ContextValue::Strings(values)) if !values.is_empty() => Some(values.join(", ")),
+        _ => None,
+    };
+
+    let message = match error.kind() {
+        ErrorKind::InvalidSubcommand => context(ContextKind::InvalidSubcommand)
+            .map(|name| t!("usage.unrecognized_subcommand", name = name)),
+        ErrorKind::UnknownArgument => {
+            context(ContextKind::InvalidArg).map(|arg| t!("usage.unexpected_argument", arg = arg))
+        }
+        ErrorKind::InvalidValue => match (
+            context(ContextKind::InvalidArg),
+            context(ContextKind::InvalidValue),
+        ) {
+            (Some(arg), Some(value)) if value.is_empty() => {
+                Some(t!("usage.value_required", arg = arg))
+            }
+            (Some(arg), Some(value)) => {
+                let mut message = t!("usage.invalid_value", value = value, arg = arg);
+                if let Some(values) = listed(error.get(ContextKind::ValidValue)) {
+                    message.push_str(&format!(
+                        "\n  {}",
+                        t!("usage.possible_values", values = values)
+                    ));
                 }
+                Some(message)
             }
-            (cmd1, cmd2) => Command::Unknown(format!("{cmd1} {cmd2}")),
+            _ => None,
         },
+        ErrorKind::ValueValidation => match (
+            context(ContextKind::InvalidArg),
+            context(ContextKind::InvalidValue),
+            context(ContextKind::Custom),
+        ) {
+            (Some(arg), Some(value), _) => {
+                let mut message = t!("usage.invalid_value", value = value, arg = arg);
+                if let Some(reason) = std::error::Error::source(error) {
+                    message.push_str(&format!(": {reason}"));
+                }
+                Some(message)
+            }
+            (_, _, custom) => custom,
+        },
+        ErrorKind::TooManyValues => match (
+            context(ContextKind::InvalidArg),
+            context(ContextKind::InvalidValue),
+        ) {
+            (Some(arg), Some(value)) => {
+                Some(t!("usage.unexpected_value", value = value, arg = arg))
+            }
+            _ => None,
+        },
+        ErrorKind::MissingRequiredArgument => match error.get(ContextKind::InvalidArg) {
+            Some(ContextValue::Strings(args)) => Some(
+                std::iter::once(t!("usage.missing_arguments"))
+                    .chain(args.iter().map(|arg| format!("  {arg}")))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            _ => None,
+        },
+        ErrorKind::MissingSubcommand => context(ContextKind::InvalidSubcommand).map(|name| {
+            let mut message = t!("usage.missing_subcommand", name = name);
+            if let Some(values) = listed(error.get(ContextKind::ValidSubcommand)) {
+                message.push_str(&format!(
+                    "\n  {}",
+                    t!("usage.possible_subcommands", values = values)
+                ));
+            }
+            message
+        }),
+        ErrorKind::ArgumentConflict => match (
+            context(ContextKind::InvalidArg),
+            context(ContextKind::PriorArg),
+        ) {
+            (Some(arg), Some(prior)) if arg == prior => {
+                Some(t!("usage.repeated_argument", arg = arg))
+            }
+            (Some(arg), Some(_)) => Some(t!(
+                "usage.conflicting_arguments",
+                arg = arg,
+                other = quoted(ContextKind::PriorArg).unwrap_or_default()
+            )),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(mut message) = message else {
+        let rendered = error.render().to_string();
+        return rendered
+            .strip_prefix("error: ")
+            .unwrap_or(&rendered)
+            .trim_end()
+            .to_string();
+    };
+
+    let tips: Vec<String> = [
+        quoted(ContextKind::SuggestedSubcommand)
+            .map(|names| t!("usage.similar_subcommand", names = names)),
+        quoted(ContextKind::SuggestedArg).map(|names| t!("usage.similar_argument", names = names)),
+        quoted(ContextKind::SuggestedValue).map(|names| t!("usage.similar_value", names = names)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !tips.is_empty() {
+        message.push('\n');
+        for tip in tips {
+            message.push_str(&format!("\n  {}", t!("usage.tip", tip = tip)));
+        }
+    }
+    if let Some(usage) = context(ContextKind::Usage) {
+        let usage = usage.strip_prefix("Usage: ").unwrap_or(&usage);
+        message.push_str(&format!("\n\n{}", t!("usage.usage", usage = usage)));
+    }
+    message.push_str(&format!("\n\n{}", t!("usage.help_tip", flag = "--help")));
+    message
+}
+
+/// Build `remote add [<name>] <repository>`
+fn parse_remote_add(args: &[String]) -> Result<Command, clap::Error> {
+    let (name, target) = match args {
+        [target] => (None, target),
+        [name, target] if config::is_remote_name(name) => (Some(name.clone()), target),
+        [name, _] => return Err(remote_add_error(t!("remote.invalid_name", name = name))),
+        _ => unreachable!("clap accepts one or two arguments"),
+    };
+    let target = parse_remote_target(target).map_err(remote_add_error)?;
+    Ok(Command::RemoteAdd { name, target })
+}
+
+/// Usage error of `remote add`, checked after clap has parsed the arguments
+fn remote_add_error(message: String) -> clap::Error {
+    let mut command = command();
+    command.build();
+    let add = command
+        .find_subcommand_mut("remote")
+        .and_then(|remote| remote.find_subcommand_mut("add"))
+        .expect("remote add is defined");
+    let mut error = clap::Error::new(ErrorKind::ValueValidation).with_cmd(add);
+    error.insert(ContextKind::Custom, ContextValue::String(message));
+    error.insert(
+        ContextKind::Usage,
+        ContextValue::StyledStr(add.render_usage()),
+    );
+    error
+}
+
+/// Normalise a `remote add` argument: `owner/repo`, a repository URL, or a git remote name
+fn parse_remote_target(spec: &str) -> Result<RemoteTarget, String> {
+    let is_url = spec.contains("://")
+        || spec
+            .split_once(':')
+            .is_some_and(|(authority, _)| !authority.contains('/'));
+    if is_url {
+        let remote = git_remote::parse_remote_url(spec)
+            .ok_or_else(|| t!("remote.invalid_url", url = spec))?;
+        Ok(RemoteTarget::Repository {
+            name: remote.name(),
+            host: remote.enterprise_host(),
+        })
+    } else if spec.contains('/') {
+        if config::is_repository_name(spec) {
+            Ok(RemoteTarget::Repository {
+                name: spec.to_string(),
+                host: None,
+            })
+        } else {
+            Err(t!("remote.invalid_format"))
+        }
+    } else {
+        Ok(RemoteTarget::GitRemote(spec.to_string()))
+    }
+}
+
+/// `remote remove` accepts a remote name or `owner/repo`
+fn parse_issue_number(spec: &str) -> Result<u64, String> {
+    let number = spec.strip_prefix('#').unwrap_or(spec);
+    number
+        .parse()
+        .map_err(|_| t!("clean.invalid_issue", value = spec))
+}
+
+fn parse_remote_reference(spec: &str) -> Result<String, String> {
+    if spec.contains('/') && !config::is_repository_name(spec) {
+        Err(t!("remote.invalid_format"))
+    } else {
+        Ok(spec.to_string())
     }
 }
 
@@ -87,320 +673,596 @@ pub fn parse_args(args: &[String]) -> Command {
 mod tests {
     use super::*;
 
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn parse(words: &[&str]) -> Command {
+        parse_args(&args(words)).unwrap().1
+    }
+
+    fn parse_error(words: &[&str]) -> clap::Error {
+        parse_args(&args(words)).unwrap_err()
+    }
+
+    /// First line of the message of a usage error
+    fn error_message(words: &[&str]) -> String {
+        let message = usage_error_message(&parse_error(words));
+        message.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn test_command_definition() {
+        command().debug_assert();
+    }
+
     #[test]
     fn test_parse_login_command() {
-        let args = vec!["program".to_string(), "login".to_string()];
-        assert_eq!(parse_args(&args), Command::Login);
+        assert_eq!(
+            parse(&["atat", "login"]),
+            Command::Login { no_browser: false }
+        );
+        assert_eq!(
+            parse(&["atat", "login", "--no-browser"]),
+            Command::Login { no_browser: true }
+        );
+        assert_eq!(
+            parse_error(&["atat", "login", "extra_arg"]).kind(),
+            ErrorKind::UnknownArgument
+        );
     }
 
     #[test]
     fn test_parse_whoami_command() {
-        let args = vec!["program".to_string(), "whoami".to_string()];
-        assert_eq!(parse_args(&args), Command::Whoami);
+        assert_eq!(parse(&["atat", "whoami"]), Command::Whoami);
     }
 
     #[test]
-    fn test_parse_push_command() {
-        let args = vec!["program".to_string(), "push".to_string()];
-        assert_eq!(parse_args(&args), Command::Push);
+    fn test_parse_auth_status_command() {
+        assert_eq!(parse(&["atat", "auth", "status"]), Command::AuthStatus);
+        assert_eq!(
+            parse_error(&["atat", "auth"]).kind(),
+            ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        );
+        assert_eq!(
+            parse_error(&["atat", "auth", "refresh"]).kind(),
+            ErrorKind::InvalidSubcommand
+        );
     }
 
     #[test]
-    fn test_parse_remote_list_command() {
-        let args = vec!["program".to_string(), "remote".to_string()];
-        assert_eq!(parse_args(&args), Command::RemoteList);
+    fn test_parse_config_list_command() {
+        assert_eq!(
+            parse(&["atat", "config"]),
+            Command::ConfigList { show_origin: false }
+        );
+        assert_eq!(
+            parse(&["atat", "config", "list"]),
+            Command::ConfigList { show_origin: false }
+        );
+        assert_eq!(
+            parse(&["atat", "config", "list", "--show-origin"]),
+            Command::ConfigList { show_origin: true }
+        );
+        assert_eq!(
+            parse_error(&["atat", "config", "list", "--all"]).kind(),
+            ErrorKind::UnknownArgument
+        );
     }
 
     #[test]
-    fn test_parse_remote_add_command() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "add".to_string(),
-            "owner/repo".to_string(),
-        ];
-        assert_eq!(
-            parse_args(&args),
-            Command::RemoteAdd {
-                repo: "owner/repo".to_string()
+    fn test_parse_config_get_set_unset() {
+        assert_eq!(
+            parse(&["atat", "config", "get", "todo_file"]),
+            Command::ConfigGet {
+                key: "todo_file".to_string()
+            }
+        );
+        assert_eq!(
+            parse(&["atat", "config", "set", "todo_file", "TODO.org"]),
+            Command::ConfigSet {
+                key: "todo_file".to_string(),
+                value: "TODO.org".to_string(),
+                global: false
             }
         );
+        assert_eq!(
+            parse(&["atat", "config", "set", "--global", "labels", "todo"]),
+            Command::ConfigSet {
+                key: "labels".to_string(),
+                value: "todo".to_string(),
+                global: true
+            }
+        );
+        assert_eq!(
+            parse(&["atat", "config", "unset", "labels", "--global"]),
+            Command::ConfigUnset {
+                key: "labels".to_string(),
+                global: true
+            }
+        );
+        assert_eq!(
+            parse_error(&["atat", "config", "set", "labels"]).kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        assert_eq!(
+            parse_error(&["atat", "config", "get"]).kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        assert_eq!(
+            parse_error(&["atat", "config", "list", "--global"]).kind(),
+            ErrorKind::UnknownArgument
+        );
     }
 
     #[test]
-    fn test_parse_remote_add_missing_repo() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "add".to_string(),
-        ];
+    fn test_parse_init_command() {
+        assert_eq!(parse(&["atat", "init"]), Command::Init { import: None });
+        assert_eq!(
+            parse(&["atat", "init", "--import"]),
+            Command::Init { import: Some(true) }
+        );
+        assert_eq!(
+            parse(&["atat", "init", "--no-import"]),
+            Command::Init {
+                import: Some(false)
+            }
+        );
+        assert_eq!(
+            parse_error(&["atat", "init", "--import", "--no-import"]).kind(),
+            ErrorKind::ArgumentConflict
+        );
         assert_eq!(
-            parse_args(&args),
-            Command::Unknown(
-                "Missing repository argument. Usage: atat remote add <owner>/<repo>".to_string()
-            )
+            parse_error(&["atat", "init", "--force"]).kind(),
+            ErrorKind::UnknownArgument
         );
     }
 
     #[test]
-    fn test_parse_remote_unknown_subcommand_with_two_args() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "unknown_sub".to_string(),
-        ];
+    fn test_parse_push_pull_commands() {
+        assert_eq!(parse(&["atat", "push"]), Command::Push { dry_run: false });
         assert_eq!(
-            parse_args(&args),
-            Command::Unknown("remote unknown_sub".to_string())
+            parse(&["atat", "push", "--dry-run"]),
+            Command::Push { dry_run: true }
         );
+        assert_eq!(parse(&["atat", "pull"]), Command::Pull);
     }
 
     #[test]
-    fn test_parse_too_many_args_for_known_command() {
-        let args = vec![
-            "program".to_string(),
-            "login".to_string(),
-            "extra_arg".to_string(),
-        ];
-        assert_eq!(parse_args(&args), Command::Unknown("login".to_string()));
+    fn test_parse_add_command() {
+        assert_eq!(
+            parse(&["atat", "add", "Write docs"]),
+            Command::Add {
+                text: "Write docs".to_string(),
+                section: None,
+                labels: vec![],
+                assignees: vec![],
+                body: None,
+            }
+        );
+        assert_eq!(
+            parse(&[
+                "atat",
+                "add",
+                "Fix login",
+                "--section",
+                "Bugs",
+                "--label",
+                "bug",
+                "--label",
+                "auth",
+                "--assignee",
+                "octocat",
+                "--body",
+                "Fails on Safari",
+            ]),
+            Command::Add {
+                text: "Fix login".to_string(),
+                section: Some("Bugs".to_string()),
+                labels: vec!["bug".to_string(), "auth".to_string()],
+                assignees: vec!["octocat".to_string()],
+                body: Some("Fails on Safari".to_string()),
+            }
+        );
+        assert!(parse_args(&args(&["atat", "add"])).is_err());
     }
 
     #[test]
-    fn test_parse_remote_add_with_extra_args() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "add".to_string(),
-            "owner/repo".to_string(),
-            "extra".to_string(),
-        ];
-        assert_eq!(
-            parse_args(&args),
-            Command::RemoteAdd {
-                repo: "owner/repo".to_string()
+    fn test_parse_clean_and_scan_commands() {
+        assert_eq!(
+            parse(&["atat", "clean"]),
+            Command::Clean {
+                dry_run: false,
+                issues: vec![]
+            }
+        );
+        assert_eq!(
+            parse(&["atat", "clean", "--dry-run"]),
+            Command::Clean {
+                dry_run: true,
+                issues: vec![]
+            }
+        );
+        assert_eq!(
+            parse(&["atat", "clean", "12", "#34"]),
+            Command::Clean {
+                dry_run: false,
+                issues: vec![12, 34]
             }
         );
+        assert_eq!(
+            parse_error(&["atat", "clean", "twelve"]).kind(),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(parse(&["atat", "scan"]), Command::Scan { dry_run: false });
+        assert_eq!(
+            parse(&["atat", "scan", "--dry-run"]),
+            Command::Scan { dry_run: true }
+        );
     }
 
     #[test]
-    fn test_parse_clean_command() {
-        let args = vec!["program".to_string(), "clean".to_string()];
-        assert_eq!(parse_args(&args), Command::Clean { dry_run: false });
-    }
+    fn test_parse_help_and_version() {
+        assert_eq!(parse(&["atat"]), Command::Help);
 
-    #[test]
-    fn test_parse_clean_dry_run_command() {
-        let args = vec![
-            "program".to_string(),
-            "clean".to_string(),
-            "--dry-run".to_string(),
-        ];
-        assert_eq!(parse_args(&args), Command::Clean { dry_run: true });
+        let help = parse_error(&["atat", "help"]);
+        assert_eq!(help.kind(), ErrorKind::DisplayHelp);
+        assert!(!help.use_stderr());
+
+        let push_help = parse_error(&["atat", "push", "--help"]);
+        assert_eq!(push_help.kind(), ErrorKind::DisplayHelp);
+        assert!(push_help.render().to_string().contains("--dry-run"));
+
+        let version = parse_error(&["atat", "--version"]);
+        assert_eq!(version.kind(), ErrorKind::DisplayVersion);
+        assert!(!version.use_stderr());
     }
 
     #[test]
-    fn test_parse_help_command() {
-        let args = vec!["program".to_string(), "help".to_string()];
-        assert_eq!(parse_args(&args), Command::Help);
+    fn test_parse_unknown_command_suggests() {
+        let error = parse_error(&["atat", "pshu"]);
+        assert_eq!(error.kind(), ErrorKind::InvalidSubcommand);
+        assert!(error.use_stderr());
+        assert!(
+            error
+                .render()
+                .to_string()
+                .contains("a similar subcommand exists: 'push'")
+        );
+
+        assert_eq!(
+            parse_error(&["atat", "unknown"]).kind(),
+            ErrorKind::InvalidSubcommand
+        );
+        assert_eq!(
+            parse_error(&["atat", "clean", "--dry-rn"]).kind(),
+            ErrorKind::UnknownArgument
+        );
     }
 
     #[test]
-    fn test_parse_no_command() {
-        let args = vec!["program".to_string()];
-        assert_eq!(parse_args(&args), Command::Help);
+    fn test_parse_completions_and_man_commands() {
+        assert_eq!(
+            parse(&["atat", "completions", "zsh"]),
+            Command::Completions {
+                shell: clap_complete::Shell::Zsh
+            }
+        );
+        assert_eq!(
+            parse(&["atat", "completions", "powershell"]),
+            Command::Completions {
+                shell: clap_complete::Shell::PowerShell
+            }
+        );
+        assert_eq!(
+            parse_error(&["atat", "completions", "tcsh"]).kind(),
+            ErrorKind::InvalidValue
+        );
+
+        assert_eq!(parse(&["atat", "man"]), Command::Man { output_dir: None });
+        assert_eq!(
+            parse(&["atat", "man", "--output-dir", "man"]),
+            Command::Man {
+                output_dir: Some("man".into())
+            }
+        );
     }
 
     #[test]
-    fn test_parse_unknown_command() {
-        let args = vec!["program".to_string(), "unknown".to_string()];
-        assert_eq!(parse_args(&args), Command::Unknown("unknown".to_string()));
+    fn test_parse_remote_list_command() {
+        assert_eq!(parse(&["atat", "remote"]), Command::RemoteList);
+        assert_eq!(
+            parse_error(&["atat", "remote", "unknown_sub"]).kind(),
+            ErrorKind::InvalidSubcommand
+        );
     }
 
     #[test]
-    fn test_parse_remote_add_invalid_format_no_slash() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "add".to_string(),
-            "ownerrepo".to_string(),
-        ];
+    fn test_parse_remote_add_command() {
+        assert_eq!(
+            parse(&["atat", "remote", "add", "owner/repo"]),
+            Command::RemoteAdd {
+                name: None,
+                target: RemoteTarget::Repository {
+                    name: "owner/repo".to_string(),
+                    host: None
+                }
+            }
+        );
+        assert_eq!(
+            parse(&["atat", "remote", "add", "upstream", "owner/repo"]),
+            Command::RemoteAdd {
+                name: Some("upstream".to_string()),
+                target: RemoteTarget::Repository {
+                    name: "owner/repo".to_string(),
+                    host: None
+                }
+            }
+        );
+        assert_eq!(
+            parse_error(&["atat", "remote", "add"]).kind(),
+            ErrorKind::MissingRequiredArgument
+        );
         assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
+            parse_error(&["atat", "remote", "add", "upstream", "owner/repo", "extra"]).kind(),
+            ErrorKind::TooManyValues
         );
     }
 
     #[test]
-    fn test_parse_remote_add_invalid_format_empty_owner() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "add".to_string(),
-            "/repo".to_string(),
-        ];
+    fn test_parse_remote_add_urls() {
+        for url in [
+            "https://github.com/owner/repo",
+            "https://github.com/owner/repo.git",
+            "git@github.com:owner/repo.git",
+        ] {
+            assert_eq!(
+                parse(&["atat", "remote", "add", url]),
+                Command::RemoteAdd {
+                    name: None,
+                    target: RemoteTarget::Repository {
+                        name: "owner/repo".to_string(),
+                        host: None
+                    }
+                },
+                "{url}"
+            );
+        }
+
         assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
+            parse(&[
+                "atat",
+                "remote",
+                "add",
+                "git@GHE.example.com:owner/repo.git"
+            ]),
+            Command::RemoteAdd {
+                name: None,
+                target: RemoteTarget::Repository {
+                    name: "owner/repo".to_string(),
+                    host: Some("ghe.example.com".to_string())
+                }
+            }
         );
     }
 
     #[test]
-    fn test_parse_remote_add_invalid_format_empty_repo() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "add".to_string(),
-            "owner/".to_string(),
-        ];
+    fn test_parse_remote_add_invalid_name() {
         assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
+            error_message(&["atat", "remote", "add", "up/stream", "owner/repo"]),
+            "Invalid remote name: up/stream"
         );
     }
 
     #[test]
-    fn test_parse_remote_add_invalid_format_too_many_slashes() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "add".to_string(),
-            "owner/repo/extra".to_string(),
-        ];
+    fn test_usage_error_message() {
+        assert_eq!(
+            usage_error_message(&parse_error(&["atat", "pus"])),
+            "unrecognized subcommand 'pus'\n\n  tip: similar subcommands: 'pull', 'push'\n\n\
+             Usage: atat [OPTIONS] [COMMAND]\n\nFor more information, try '--help'."
+        );
+        assert_eq!(
+            usage_error_message(&parse_error(&["atat", "remote", "add", "up/stream", "o/r"])),
+            "Invalid remote name: up/stream\n\n\
+             Usage: atat remote add [OPTIONS] [NAME] <REPOSITORY>\n\n\
+             For more information, try '--help'."
+        );
+        assert_eq!(
+            error_message(&["atat", "--output", "xml", "push"]),
+            "invalid value 'xml' for '--output <FORMAT>'"
+        );
         assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
+            error_message(&["atat", "login", "--no-browser", "--no-browser"]),
+            "the argument '--no-browser' cannot be used multiple times"
         );
     }
 
     #[test]
-    fn test_parse_remote_add_invalid_format_owner_contains_slash() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "add".to_string(),
-            "ow/ner/repo".to_string(),
-        ];
+    fn test_parse_remote_add_git_remote_name() {
         assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
+            parse(&["atat", "remote", "add", "origin"]),
+            Command::RemoteAdd {
+                name: None,
+                target: RemoteTarget::GitRemote("origin".to_string())
+            }
         );
     }
 
+    #[test]
+    fn test_parse_remote_add_invalid_format() {
+        for repo in ["/repo", "owner/", "owner/repo/extra", "ow/ner/repo"] {
+            assert_eq!(
+                error_message(&["atat", "remote", "add", repo]),
+                "Invalid repository format. Please use <owner>/<repo>.",
+                "{repo}"
+            );
+        }
+    }
+
     #[test]
     fn test_parse_remote_remove_command() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "remove".to_string(),
-            "owner/repo".to_string(),
-        ];
-        assert_eq!(
-            parse_args(&args),
+        assert_eq!(
+            parse(&["atat", "remote", "remove", "owner/repo"]),
             Command::RemoteRemove {
                 repo: "owner/repo".to_string()
             }
         );
+        assert_eq!(
+            parse(&["atat", "remote", "remove", "upstream"]),
+            Command::RemoteRemove {
+                repo: "upstream".to_string()
+            }
+        );
+        assert_eq!(
+            parse_error(&["atat", "remote", "remove"]).kind(),
+            ErrorKind::MissingRequiredArgument
+        );
+        assert_eq!(
+            parse_error(&["atat", "remote", "remove", "owner/repo", "extra"]).kind(),
+            ErrorKind::UnknownArgument
+        );
     }
 
     #[test]
-    fn test_parse_remote_remove_missing_repo() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "remove".to_string(),
-        ];
-        assert_eq!(
-            parse_args(&args),
-            Command::Unknown(
-                "Missing repository argument. Usage: atat remote remove <owner>/<repo>".to_string()
-            )
-        );
+    fn test_parse_remote_remove_invalid_format() {
+        for repo in ["/repo", "owner/", "owner/repo/extra", "ow/ner/repo"] {
+            let error = error_message(&["atat", "remote", "remove", repo]);
+            assert!(
+                error.ends_with("Invalid repository format. Please use <owner>/<repo>."),
+                "{repo}: {error}"
+            );
+        }
     }
 
     #[test]
-    fn test_parse_remote_remove_with_extra_args() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "remove".to_string(),
-            "owner/repo".to_string(),
-            "extra".to_string(),
-        ];
-        assert_eq!(
-            parse_args(&args),
-            Command::RemoteRemove {
-                repo: "owner/repo".to_string()
+    fn test_parse_logout_command() {
+        assert_eq!(
+            parse(&["atat", "logout"]),
+            Command::Logout {
+                hostname: "github.com".to_string(),
+                revoke: false
+            }
+        );
+        assert_eq!(
+            parse(&["atat", "logout", "--revoke", "--hostname", "github.com"]),
+            Command::Logout {
+                hostname: "github.com".to_string(),
+                revoke: true
             }
         );
+        assert_eq!(
+            parse_error(&["atat", "logout", "--hostname"]).kind(),
+            ErrorKind::InvalidValue
+        );
+        assert_eq!(
+            parse_error(&["atat", "logout", "--force"]).kind(),
+            ErrorKind::UnknownArgument
+        );
     }
 
     #[test]
-    fn test_parse_remote_remove_invalid_format_no_slash() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "remove".to_string(),
-            "ownerrepo".to_string(),
-        ];
-        assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
-        );
+    fn test_parse_global_options() {
+        let (options, command) = parse_args(&args(&["atat", "--token", "abc", "push"])).unwrap();
+        assert_eq!(options.token.as_deref(), Some("abc"));
+        assert_eq!(command, Command::Push { dry_run: false });
+
+        let (options, command) = parse_args(&args(&["atat", "pull", "--token=xyz"])).unwrap();
+        assert_eq!(options.token.as_deref(), Some("xyz"));
+        assert_eq!(command, Command::Pull);
+
+        assert!(parse_args(&args(&["atat", "push", "--token"])).is_err());
+
+        let (options, command) =
+            parse_args(&args(&["atat", "login", "--account", "work"])).unwrap();
+        assert_eq!(options.account.as_deref(), Some("work"));
+        assert_eq!(options.token, None);
+        assert_eq!(command, Command::Login { no_browser: false });
+
+        let (options, _) = parse_args(&args(&["atat", "clean", "--dry-run"])).unwrap();
+        assert_eq!(options, GlobalOptions::default());
     }
 
     #[test]
-    fn test_parse_remote_remove_invalid_format_empty_owner() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "remove".to_string(),
-            "/repo".to_string(),
-        ];
+    fn test_parse_global_config_and_file() {
+        let (options, command) = parse_args(&args(&[
+            "atat",
+            "--config",
+            "todo_file=TODO.org",
+            "push",
+            "--config=obsidian_tasks=true",
+        ]))
+        .unwrap();
         assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
+            options.config,
+            vec!["todo_file=TODO.org", "obsidian_tasks=true"]
         );
-    }
+        assert_eq!(command, Command::Push { dry_run: false });
 
-    #[test]
-    fn test_parse_remote_remove_invalid_format_empty_repo() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "remove".to_string(),
-            "owner/".to_string(),
-        ];
+        let (options, _) = parse_args(&args(&[
+            "atat",
+            "--config",
+            "a=1",
+            "remote",
+            "--config=b=2",
+            "add",
+            "--config",
+            "c=3",
+            "owner/repo",
+        ]))
+        .unwrap();
+        assert_eq!(options.config, vec!["a=1", "b=2", "c=3"]);
+
+        let (options, command) =
+            parse_args(&args(&["atat", "add", "--", "--config=todo_file=x.md"])).unwrap();
+        assert!(options.config.is_empty());
+        assert!(matches!(command, Command::Add { text, .. } if text == "--config=todo_file=x.md"));
+
+        let (options, command) =
+            parse_args(&args(&["atat", "clean", "--dry-run", "--file", "x.md"])).unwrap();
+        assert_eq!(options.config, vec!["todo_file=x.md"]);
         assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
+            command,
+            Command::Clean {
+                dry_run: true,
+                issues: vec![]
+            }
         );
     }
+    #[test]
+    fn test_parse_output_format() {
+        let (options, _) = parse_args(&args(&["atat", "pull"])).unwrap();
+        assert_eq!(options.output, output::OutputFormat::Text);
+
+        let (options, command) =
+            parse_args(&args(&["atat", "--output", "json", "whoami"])).unwrap();
+        assert_eq!(options.output, output::OutputFormat::Json);
+        assert_eq!(command, Command::Whoami);
+
+        let (options, _) = parse_args(&args(&["atat", "push", "--output=json"])).unwrap();
+        assert_eq!(options.output, output::OutputFormat::Json);
+
+        assert!(parse_args(&args(&["atat", "push", "--output", "yaml"])).is_err());
+    }
 
     #[test]
-    fn test_parse_remote_remove_invalid_format_too_many_slashes() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "remove".to_string(),
-            "owner/repo/extra".to_string(),
-        ];
-        assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
-        );
+    fn test_parse_verbose() {
+        let (options, _) = parse_args(&args(&["atat", "pull"])).unwrap();
+        assert_eq!(options.verbose, 0);
+
+        let (options, command) = parse_args(&args(&["atat", "pull", "-v"])).unwrap();
+        assert_eq!(options.verbose, 1);
+        assert_eq!(command, Command::Pull);
+
+        let (options, _) = parse_args(&args(&["atat", "-vv", "push"])).unwrap();
+        assert_eq!(options.verbose, 2);
     }
 
     #[test]
-    fn test_parse_remote_remove_invalid_format_owner_contains_slash() {
-        let args = vec![
-            "program".to_string(),
-            "remote".to_string(),
-            "remove".to_string(),
-            "ow/ner/repo".to_string(),
-        ];
-        assert_eq!(
-            parse_args(&args),
-            Command::Unknown("Invalid repository format. Please use <owner>/<repo>.".to_string())
-        );
+    fn test_parse_no_color() {
+        let (options, _) = parse_args(&args(&["atat", "pull"])).unwrap();
+        assert!(!options.no_color);
+
+        let (options, _) = parse_args(&args(&["atat", "remote", "--no-color"])).unwrap();
+        assert!(options.no_color);
     }
 }
@@ -0,0 +1,637 @@
+use crate::i18n::t;
+use crate::todo::TodoItem;
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Keywords that mark a comment as a task
+const KEYWORDS: &[&str] = &["TODO", "FIXME"];
+/// Directories never scanned, even if they are not ignored
+const SKIPPED_DIRS: &[&str] = &[".git", crate::config::PROJECT_CONFIG_DIR];
+
+/// How comments and string literals are written in a language
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comments: &'static [(&'static str, &'static str)],
+    /// String delimiters, longest first, and whether the string may span lines
+    strings: &'static [(&'static str, bool)],
+    /// Raw strings (`r#"..."#`) and char literals, whose `'` is shared with lifetimes
+    rust: bool,
+}
+
+const RUST: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", true)],
+    rust: true,
+};
+const C_LIKE: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", false), ("'", false), ("`", true)],
+    rust: false,
+};
+const PHP: Syntax = Syntax {
+    line_comments: &["//", "#"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", false), ("'", false)],
+    rust: false,
+};
+const CSS: Syntax = Syntax {
+    line_comments: &[],
+    block_comments: &[("/*", "*/")],
+    strings: &[("\"", false), ("'", false)],
+    rust: false,
+};
+const HASH: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    strings: &[("\"\"\"", true), ("'''", true), ("\"", false), ("'", false)],
+    rust: false,
+};
+const SQL: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("/*", "*/")],
+    strings: &[("'", false), ("\"", false)],
+    rust: false,
+};
+const LUA: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("--[[", "]]")],
+    strings: &[("\"", false), ("'", false)],
+    rust: false,
+};
+const HASKELL: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("{-", "-}")],
+    strings: &[("\"", false)],
+    rust: false,
+};
+const LISP: Syntax = Syntax {
+    line_comments: &[";"],
+    block_comments: &[],
+    strings: &[("\"", true)],
+    rust: false,
+};
+const INI: Syntax = Syntax {
+    line_comments: &[";", "#"],
+    block_comments: &[],
+    strings: &[],
+    rust: false,
+};
+const PERCENT: Syntax = Syntax {
+    line_comments: &["%"],
+    block_comments: &[],
+    strings: &[],
+    rust: false,
+};
+const MARKUP: Syntax = Syntax {
+    line_comments: &[],
+    block_comments: &[("<!--", "-->")],
+    strings: &[],
+    rust: false,
+};
+
+/// Comment syntax of a file, from its name or extension. Other files are not scanned.
+fn syntax_for(path: &Path) -> Option<&'static Syntax> {
+    let name = path.file_name()?.to_str()?;
+    if matches!(name, "Makefile" | "Dockerfile" | "CMakeLists.txt") {
+        return Some(&HASH);
+    }
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "rs" => Some(&RUST),
+        "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "cs" | "java" | "kt" | "kts" | "scala"
+        | "swift" | "go" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "dart" | "groovy"
+        | "gradle" | "scss" | "less" | "proto" | "zig" => Some(&C_LIKE),
+        "php" => Some(&PHP),
+        "css" => Some(&CSS),
+        "py" | "rb" | "sh" | "bash" | "zsh" | "fish" | "pl" | "pm" | "r" | "yaml" | "yml"
+        | "toml" | "cmake" | "mk" | "ex" | "exs" | "nix" | "ps1" | "tf" | "feature" => Some(&HASH),
+        "sql" => Some(&SQL),
+        "lua" => Some(&LUA),
+        "hs" => Some(&HASKELL),
+        "el" | "lisp" | "clj" | "scm" => Some(&LISP),
+        "ini" | "cfg" | "conf" => Some(&INI),
+        "tex" | "erl" => Some(&PERCENT),
+        "md" | "markdown" | "html" | "htm" | "xml" | "svg" | "vue" | "svelte" => Some(&MARKUP),
+        _ => None,
+    }
+}
+
+/// Where the lexer is at the start of a line
+#[derive(Clone, Copy)]
+enum State {
+    Code,
+    Block(&'static str, &'static str),
+    Str(&'static str, bool),
+    /// Raw string closed by `"` and this many `#`
+    RawStr(usize),
+}
+
+/// Length of a Rust char literal or raw string opening at the start of `rest`
+fn rust_literal(rest: &str, previous: Option<char>) -> Option<(usize, State)> {
+    if let Some(after) = rest.strip_prefix('\'') {
+        let mut chars = after.char_indices();
+        let (_, first) = chars.next()?;
+        let end = if first == '\\' {
+            2 + after.get(2..)?.find('\'')?
+        } else {
+            let (index, next) = chars.next()?;
+            (next == '\'').then_some(index)?
+        };
+        return Some((end + 2, State::Code));
+    }
+    if previous.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let after = rest.strip_prefix("br").or_else(|| rest.strip_prefix('r'))?;
+    let hashes = after.len() - after.trim_start_matches('#').len();
+    after[hashes..]
+        .starts_with('"')
+        .then(|| (rest.len() - after.len() + hashes + 1, State::RawStr(hashes)))
+}
+
+/// Advance `state` over one line and return where its first comment starts.
+///
+/// The result is the byte offset right after the comment marker, and the
+/// marker itself. A line that continues a block comment starts with it.
+fn scan_line(syntax: &Syntax, line: &str, state: &mut State) -> Option<(usize, &'static str)> {
+    let mut found = match *state {
+        State::Block(open, _) => Some((0, open)),
+        _ => None,
+    };
+    // Markers only start a comment at the beginning of a word
+    let at_word_start = |i: usize| line[..i].chars().last().is_none_or(char::is_whitespace);
+
+    let mut i = 0;
+    while i < line.len() {
+        let rest = &line[i..];
+        match *state {
+            State::Block(_, close) if rest.starts_with(close) => {
+                *state = State::Code;
+                i += close.len();
+                continue;
+            }
+            State::Str(_, _) if rest.starts_with('\\') => {
+                i += 1;
+            }
+            State::Str(delimiter, _) if rest.starts_with(delimiter) => {
+                *state = State::Code;
+                i += delimiter.len();
+                continue;
+            }
+            State::RawStr(hashes)
+                if rest.starts_with('"')
+                    && rest[1..].bytes().take_while(|&b| b == b'#').count() >= hashes =>
+            {
+                *state = State::Code;
+                i += 1 + hashes;
+                continue;
+            }
+            State::Code => {
+                if let Some(&(open, close)) = syntax
+                    .block_comments
+                    .iter()
+                    .find(|(open, _)| rest.starts_with(open) && at_word_start(i))
+                {
+                    found.get_or_insert((i + open.len(), open));
+                    *state = State::Block(open, close);
+                    i += open.len();
+                    continue;
+                }
+                if let Some(marker) = syntax
+                    .line_comments
+                    .iter()
+                    .find(|marker| rest.starts_with(*marker) && at_word_start(i))
+                {
+                    found.get_or_insert((i + marker.len(), marker));
+                    break;
+                }
+                if syntax.rust
+                    && let Some((length, next)) = rust_literal(rest, line[..i].chars().last())
+                {
+                    *state = next;
+                    i += length;
+                    continue;
+                }
+                if let Some(&(delimiter, multiline)) = syntax
+                    .strings
+                    .iter()
+                    .find(|(delimiter, _)| rest.starts_with(delimiter))
+                {
+                    *state = State::Str(delimiter, multiline);
+                    i += delimiter.len();
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += line[i..].chars().next().map_or(1, char::len_utf8);
+    }
+
+    if let State::Str(_, false) = *state {
+        *state = State::Code;
+    }
+    found
+}
+
+/// A `TODO:`/`FIXME:` comment found in a source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoComment {
+    pub path: PathBuf,
+    /// Zero-based line index
+    pub line: usize,
+    pub keyword: String,
+    pub text: String,
+    pub issue_number: Option<u64>,
+}
+
+impl TodoComment {
+    fn location(&self) -> String {
+        format!("{}:{}", self.path.display(), self.line + 1)
+    }
+}
+
+/// Byte range of the keyword and its optional `(...)` annotation within a line
+#[derive(Debug, Clone, PartialEq)]
+struct KeywordMatch {
+    keyword: &'static str,
+    start: usize,
+    annotation: Option<(usize, usize)>,
+    text_start: usize,
+}
+
+/// Match `KEYWORD(...):` at the start of the comment text after `marker`
+fn find_keyword(line: &str, text_start: usize, marker: &str) -> Option<KeywordMatch> {
+    let rest = line[text_start..].trim_start_matches(|c: char| {
+        c.is_whitespace() || marker.contains(c) || c == '!' || c == '/'
+    });
+    let start = line.len() - rest.len();
+    let keyword = KEYWORDS.iter().find(|k| rest.starts_with(*k))?;
+    let mut cursor = start + keyword.len();
+
+    let annotation = if line[cursor..].starts_with('(') {
+        let close = cursor + line[cursor..].find(')')?;
+        let range = (cursor + 1, close);
+        cursor = close + 1;
+        Some(range)
+    } else {
+        None
+    };
+
+    line[cursor..].starts_with(':').then(|| KeywordMatch {
+        keyword,
+        start,
+        annotation,
+        text_start: cursor + 1,
+    })
+}
+
+/// Task comment keywords in `content`, with the index of their line
+fn keyword_matches<'a>(path: &Path, content: &'a str) -> Vec<(usize, &'a str, KeywordMatch)> {
+    let Some(syntax) = syntax_for(path) else {
+        return Vec::new();
+    };
+    let mut state = State::Code;
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let (text_start, marker) = scan_line(syntax, line, &mut state)?;
+            find_keyword(line, text_start, marker).map(|found| (index, line, found))
+        })
+        .collect()
+}
+
+fn annotation_issue_number(annotation: &str) -> Option<u64> {
+    annotation
+        .split(',')
+        .filter_map(|part| part.trim().strip_prefix('#'))
+        .find_map(|number| number.parse().ok())
+}
+
+fn comment_text(text: &str) -> String {
+    text.trim()
+        .trim_end_matches("-->")
+        .trim_end_matches("*/")
+        .trim()
+        .to_string()
+}
+
+/// Find all task comments in the content of one file.
+///
+/// The comment syntax is chosen from the file extension, and markers inside
+/// string literals are skipped. Files of unknown types have no comments.
+pub fn find_todo_comments(path: &Path, content: &str) -> Vec<TodoComment> {
+    keyword_matches(path, content)
+        .into_iter()
+        .filter_map(|(index, line, found)| {
+            let text = comment_text(&line[found.text_start..]);
+            if text.is_empty() {
+                return None;
+            }
+            Some(TodoComment {
+                path: path.to_path_buf(),
+                line: index,
+                keyword: found.keyword.to_string(),
+                text,
+                issue_number: found
+                    .annotation
+                    .and_then(|(start, end)| annotation_issue_number(&line[start..end])),
+            })
+        })
+        .collect()
+}
+
+/// Add an `(#n)` reference after the comment keyword on line `line` of `content`,
+/// e.g. `// TODO(#12): text`.
+///
+/// The content is returned unchanged if that line has no task comment.
+pub fn insert_issue_reference(
+    path: &Path,
+    content: &str,
+    line: usize,
+    issue_number: u64,
+) -> String {
+    let Some((_, _, found)) = keyword_matches(path, content)
+        .into_iter()
+        .find(|(index, _, _)| *index == line)
+    else {
+        return content.to_string();
+    };
+
+    content
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(index, text)| {
+            if index != line {
+                return text.to_string();
+            }
+            match found.annotation {
+                Some((start, end)) => {
+                    let annotation = text[start..end].trim();
+                    let separator = if annotation.is_empty() { "" } else { ", " };
+                    format!(
+                        "{}{annotation}{separator}#{issue_number}{}",
+                        &text[..start],
+                        &text[end..]
+                    )
+                }
+                None => {
+                    let at = found.start + found.keyword.len();
+                    format!("{}(#{issue_number}){}", &text[..at], &text[at..])
+                }
+            }
+        })
+        .collect()
+}
+
+/// Build push items from the current comments.
+///
+/// Issues that were referenced by a comment in a previous scan but are no
+/// longer referenced are returned as checked items, so push closes them.
+pub fn comment_todo_items(comments: &[TodoComment], tracked_issues: &[u64]) -> Vec<TodoItem> {
+    let current: BTreeSet<u64> = comments.iter().filter_map(|c| c.issue_number).collect();
+
+    let open_items = comments.iter().map(|comment| TodoItem {
+        text: comment.text.clone(),
+        is_checked: false,
+        issue_number: comment.issue_number,
+        body: Some(format!(
+            "{} comment in `{}`",
+            comment.keyword,
+            comment.location()
+        )),
+        ..Default::default()
+    });
+
+    let removed_items = tracked_issues
+        .iter()
+        .filter(|number| !current.contains(number))
+        .map(|&number| TodoItem {
+            text: String::new(),
+            is_checked: true,
+            issue_number: Some(number),
+            ..Default::default()
+        });
+
+    open_items.chain(removed_items).collect()
+}
+
+/// Parse the list of issue numbers referenced by comments in the last scan.
+pub fn parse_tracked_issues(content: &[u8]) -> Result<Vec<u64>> {
+    if content.iter().all(|b| b.is_ascii_whitespace()) {
+        return Ok(Vec::new());
+    }
+    serde_json::from_slice(content).with_context(|| t!("scan.parse_tracked_failed"))
+}
+
+/// Walk `root`, honouring `.gitignore`, and collect task comments from text files.
+///
+/// Paths in the result are relative to `root`.
+pub fn scan_directory(root: &Path) -> Result<Vec<TodoComment>> {
+    let walker = ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| {
+            !(entry.file_type().is_some_and(|t| t.is_dir())
+                && SKIPPED_DIRS.iter().any(|dir| entry.file_name() == *dir))
+        })
+        .sort_by_file_path(Ord::cmp)
+        .build();
+
+    let mut comments = Vec::new();
+    for entry in walker {
+        let entry = entry.with_context(|| t!("scan.walk_failed"))?;
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        if content.contains('\0') {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        comments.extend(find_todo_comments(relative, &content));
+    }
+    Ok(comments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(line: usize, keyword: &str, text: &str, issue: Option<u64>) -> TodoComment {
+        TodoComment {
+            path: PathBuf::from("src/lib.rs"),
+            line,
+            keyword: keyword.to_string(),
+            text: text.to_string(),
+            issue_number: issue,
+        }
+    }
+
+    #[test]
+    fn test_find_comments_in_common_syntaxes() {
+        let content = r#"fn main() {
+    // TODO: handle errors
+    let x = 1; // FIXME: wrong value
+}
+/* TODO: block comment */
+/*
+ * FIXME: inside a block
+ */
+"#;
+
+        let comments = find_todo_comments(Path::new("src/lib.rs"), content);
+
+        assert_eq!(
+            comments,
+            vec![
+                comment(1, "TODO", "handle errors", None),
+                comment(2, "FIXME", "wrong value", None),
+                comment(4, "TODO", "block comment", None),
+                comment(6, "FIXME", "inside a block", None),
+            ]
+        );
+
+        let text = |path: &str, content: &str| -> Vec<String> {
+            find_todo_comments(Path::new(path), content)
+                .into_iter()
+                .map(|comment| comment.text)
+                .collect()
+        };
+        assert_eq!(text("run.py", "# TODO: python\n"), ["python"]);
+        assert_eq!(text("Makefile", "# TODO: make\n"), ["make"]);
+        assert_eq!(
+            text("page.html", "<!-- TODO: html comment -->\n"),
+            ["html comment"]
+        );
+        assert_eq!(text("query.sql", "-- TODO: sql comment\n"), ["sql comment"]);
+        assert!(text("data.bin", "// TODO: unknown type\n").is_empty());
+    }
+
+    #[test]
+    fn test_find_comments_with_issue_reference() {
+        let content =
+            "// TODO(#12): tracked\n// FIXME(alice, #34): assigned\n// TODO(alice): untracked\n";
+
+        let comments = find_todo_comments(Path::new("src/lib.rs"), content);
+
+        assert_eq!(
+            comments,
+            vec![
+                comment(0, "TODO", "tracked", Some(12)),
+                comment(1, "FIXME", "assigned", Some(34)),
+                comment(2, "TODO", "untracked", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_comments_ignores_non_comments() {
+        let content = r#"let s = "// TODO: inside a string";
+// TODO without colon
+// todo: lowercase
+// TODO:
+TODO: not a comment
+"#;
+
+        assert!(find_todo_comments(Path::new("src/lib.rs"), content).is_empty());
+    }
+
+    #[test]
+    fn test_find_comments_skips_string_literals() {
+        let content = r##"let a = "    // TODO: x";
+let b = "first line
+    // TODO: still in the string";
+let c = r#"quoted "// TODO: raw" text"#;
+let d = '"'; // TODO: after a char literal
+fn e<'a>(s: &'a str) {} // FIXME: after a lifetime
+"##;
+
+        assert_eq!(
+            find_todo_comments(Path::new("src/lib.rs"), content),
+            vec![
+                comment(4, "TODO", "after a char literal", None),
+                comment(5, "FIXME", "after a lifetime", None),
+            ]
+        );
+        assert!(
+            find_todo_comments(Path::new("app.js"), "const s = \"    // TODO: x\";\n").is_empty()
+        );
+    }
+
+    #[test]
+    fn test_find_comments_in_markdown() {
+        let content = "# TODO: heading\n\n* TODO: bullet\n\n<!-- TODO: comment -->\n";
+
+        let comments = find_todo_comments(Path::new("README.md"), content);
+
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, 4);
+        assert_eq!(comments[0].text, "comment");
+    }
+
+    #[test]
+    fn test_insert_issue_reference() {
+        let insert = |path: &str, content: &str, line: usize, issue_number: u64| {
+            insert_issue_reference(Path::new(path), content, line, issue_number)
+        };
+        assert_eq!(
+            insert(
+                "src/lib.rs",
+                "fn f() {}\n    // TODO: handle errors\n",
+                1,
+                12
+            ),
+            "fn f() {}\n    // TODO(#12): handle errors\n"
+        );
+        assert_eq!(
+            insert("run.py", "# FIXME(alice): fix\r\n", 0, 34),
+            "# FIXME(alice, #34): fix\r\n"
+        );
+        assert_eq!(
+            insert("src/lib.rs", "/* TODO(): empty */", 0, 5),
+            "/* TODO(#5): empty */"
+        );
+        assert_eq!(insert("src/lib.rs", "let x = 1;\n", 0, 1), "let x = 1;\n");
+        assert_eq!(
+            insert("src/lib.rs", "let s = \"// TODO: x\";\n", 0, 1),
+            "let s = \"// TODO: x\";\n"
+        );
+    }
+
+    #[test]
+    fn test_comment_todo_items_creates_and_closes() {
+        let comments = vec![
+            comment(1, "TODO", "new task", None),
+            comment(2, "FIXME", "tracked task", Some(12)),
+        ];
+
+        let items = comment_todo_items(&comments, &[12, 13]);
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].text, "new task");
+        assert!(!items[0].is_checked);
+        assert_eq!(items[0].issue_number, None);
+        assert_eq!(
+            items[0].body.as_deref(),
+            Some("TODO comment in `src/lib.rs:2`")
+        );
+        assert_eq!(items[1].issue_number, Some(12));
+        assert!(!items[1].is_checked);
+        assert_eq!(items[2].issue_number, Some(13));
+        assert!(items[2].is_checked);
+    }
+
+    #[test]
+    fn test_parse_tracked_issues() {
+        assert_eq!(parse_tracked_issues(b"[1, 2]").unwrap(), vec![1, 2]);
+        assert!(parse_tracked_issues(b"").unwrap().is_empty());
+        assert!(parse_tracked_issues(b"{").is_err());
+    }
+}
@@ -1,62 +1,331 @@
 use anyhow::{Context, Result};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::i18n::{self, t};
 
 /// Configuration keys enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConfigKey {
     Repositories,
+    DefaultRepository,
+    TodoFile,
+    Labels,
+    CloseReason,
+    ObsidianTasks,
+    CredentialHelper,
+    Language,
+}
+
+/// Kind of value a configuration key accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// Non-empty string
+    Text,
+    Bool,
+    /// `owner/repo`
+    RepositoryName,
+    /// List of `owner/repo` strings or `{"name", "remote", "account"}` objects
+    RepositoryList,
+    /// List of non-empty strings
+    TextList,
+    /// One of the given strings
+    OneOf(&'static [&'static str]),
 }
 
+/// `state_reason` values GitHub accepts when closing an issue
+pub const CLOSE_REASONS: &[&str] = &["completed", "not_planned"];
+
 impl ConfigKey {
     pub fn as_str(&self) -> &'static str {
         match self {
             ConfigKey::Repositories => "repositories",
+            ConfigKey::DefaultRepository => "default_repository",
+            ConfigKey::TodoFile => "todo_file",
+            ConfigKey::Labels => "labels",
+            ConfigKey::CloseReason => "close_reason",
+            ConfigKey::ObsidianTasks => "obsidian_tasks",
+            ConfigKey::CredentialHelper => "credential_helper",
+            ConfigKey::Language => "language",
+        }
+    }
+
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            ConfigKey::Repositories => ValueKind::RepositoryList,
+            ConfigKey::DefaultRepository => ValueKind::RepositoryName,
+            ConfigKey::TodoFile | ConfigKey::CredentialHelper => ValueKind::Text,
+            ConfigKey::Labels => ValueKind::TextList,
+            ConfigKey::CloseReason => ValueKind::OneOf(CLOSE_REASONS),
+            ConfigKey::ObsidianTasks => ValueKind::Bool,
+            ConfigKey::Language => ValueKind::OneOf(i18n::LANGUAGE_CODES),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            ConfigKey::Repositories => t!("config.key.repositories"),
+            ConfigKey::DefaultRepository => t!("config.key.default_repository"),
+            ConfigKey::TodoFile => t!("config.key.todo_file"),
+            ConfigKey::Labels => t!("config.key.labels"),
+            ConfigKey::CloseReason => t!("config.key.close_reason"),
+            ConfigKey::ObsidianTasks => t!("config.key.obsidian_tasks"),
+            ConfigKey::CredentialHelper => t!("config.key.credential_helper"),
+            ConfigKey::Language => t!("config.key.language"),
+        }
+    }
+
+    /// Look up a key by its name in the config file
+    pub fn from_name(name: &str) -> Option<ConfigKey> {
+        ConfigKey::all()
+            .iter()
+            .copied()
+            .find(|key| key.as_str() == name)
+    }
+
+    /// Environment variable overriding this key, e.g. `ATAT_TODO_FILE`
+    pub fn env_var(&self) -> String {
+        format!("ATAT_{}", self.as_str().to_uppercase())
+    }
+
+    /// Parse and validate a value given as text on the command line or in an environment variable.
+    ///
+    /// Lists are comma-separated and booleans accept `true`/`false`, `yes`/`no`, `on`/`off` or `1`/`0`.
+    pub fn parse_value(&self, text: &str) -> Result<Value> {
+        let value = match self.kind() {
+            ValueKind::RepositoryList | ValueKind::TextList => Value::Array(
+                text.split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| Value::String(item.to_string()))
+                    .collect(),
+            ),
+            ValueKind::Bool => match text.trim().to_lowercase().as_str() {
+                "true" | "1" | "yes" | "on" => Value::Bool(true),
+                "false" | "0" | "no" | "off" => Value::Bool(false),
+                _ => {
+                    return Err(anyhow::anyhow!(t!(
+                        "config.invalid_value",
+                        key = self.as_str(),
+                        expected = t!("config.expected.bool"),
+                        value = format!("{text:?}")
+                    )));
+                }
+            },
+            ValueKind::Text | ValueKind::RepositoryName | ValueKind::OneOf(_) => {
+                Value::String(text.trim().to_string())
+            }
+        };
+        self.validate(&value)?;
+        Ok(value)
+    }
+
+    /// Check that `value` has the type and form this key accepts.
+    pub fn validate(&self, value: &Value) -> Result<()> {
+        let invalid = |expected: String| {
+            Err(anyhow::anyhow!(t!(
+                "config.invalid_value",
+                key = self.as_str(),
+                expected = expected,
+                value = value
+            )))
+        };
+        let non_empty_str = |value: &Value| value.as_str().is_some_and(|text| !text.is_empty());
+
+        match self.kind() {
+            ValueKind::Text if non_empty_str(value) => Ok(()),
+            ValueKind::Text => invalid(t!("config.expected.text")),
+            ValueKind::Bool if value.is_boolean() => Ok(()),
+            ValueKind::Bool => invalid(t!("config.expected.bool")),
+            ValueKind::RepositoryName if value.as_str().is_some_and(is_repository_name) => Ok(()),
+            ValueKind::RepositoryName => invalid("<owner>/<repo>".to_string()),
+            ValueKind::TextList => match value.as_array() {
+                Some(items) if items.iter().all(non_empty_str) => Ok(()),
+                _ => invalid(t!("config.expected.text_list")),
+            },
+            ValueKind::RepositoryList => {
+                let valid = value.as_array().is_some_and(|entries| {
+                    entries.iter().all(|entry| {
+                        Repository::from_value(entry).is_some_and(|repository| {
+                            is_repository_name(&repository.name)
+                                && repository.remote.as_deref().is_none_or(is_remote_name)
+                                && repository.host.as_deref().is_none_or(is_host_name)
+                                && repository.account.as_deref().is_none_or(|account| {
+                                    crate::storage::validate_account_name(account).is_ok()
+                                })
+                        })
+                    })
+                });
+                if valid {
+                    Ok(())
+                } else {
+                    invalid(t!("config.expected.repository_list"))
+                }
+            }
+            ValueKind::OneOf(choices) if value.as_str().is_some_and(|v| choices.contains(&v)) => {
+                Ok(())
+            }
+            ValueKind::OneOf(choices) => {
+                invalid(t!("config.expected.one_of", choices = choices.join(", ")))
+            }
         }
     }
 
     /// Get all config keys
     pub fn all() -> &'static [ConfigKey] {
-        &[ConfigKey::Repositories]
+        &[
+            ConfigKey::Repositories,
+            ConfigKey::DefaultRepository,
+            ConfigKey::TodoFile,
+            ConfigKey::Labels,
+            ConfigKey::CloseReason,
+            ConfigKey::ObsidianTasks,
+            ConfigKey::CredentialHelper,
+            ConfigKey::Language,
+        ]
     }
 }
 
+/// Whether `name` can name a remote: non-empty, without `/`, `:` or whitespace, not starting with `-`
+pub fn is_remote_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && !name
+            .chars()
+            .any(|c| c == '/' || c == ':' || c.is_whitespace())
+}
+
+/// Whether `host` can be the host name of a GitHub Enterprise Server, optionally with a port
+pub fn is_host_name(host: &str) -> bool {
+    !host.is_empty()
+        && !host.starts_with(['-', '.'])
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':'))
+}
+
+/// Whether `name` has the `owner/repo` form
+pub fn is_repository_name(name: &str) -> bool {
+    matches!(
+        name.split('/').collect::<Vec<_>>().as_slice(),
+        [owner, repo] if !owner.is_empty() && !repo.is_empty()
+    )
+}
+
 /// Filename for the project-specific configuration within the .atat directory.
 pub const PROJECT_CONFIG_FILENAME: &str = "config.json";
 /// Directory name for project-specific configuration.
 pub const PROJECT_CONFIG_DIR: &str = ".atat";
+/// File within the .atat directory recording issues referenced by source comments.
+pub const COMMENT_ISSUES_FILENAME: &str = "comment_issues.json";
+/// Task file used when `todo_file` is not configured.
+pub const DEFAULT_TODO_FILE: &str = "TODO.md";
 
-/// Parses a JSON configuration file content into a map of configuration values.
+/// Version of the config file format written by this build.
+///
+/// Files without a `version` field are version 0.
+pub const CONFIG_VERSION: u64 = 1;
+/// Key holding the format version of a config file
+pub const VERSION_KEY: &str = "version";
+
+/// Migrations from each version to the next, indexed by the version they upgrade from.
+const MIGRATIONS: &[fn(Value) -> Result<Value>] = &[migrate_v0_to_v1];
+
+/// Version 0 allowed the bare repository list `["owner/repo"]` instead of an object.
+fn migrate_v0_to_v1(value: Value) -> Result<Value> {
+    match value {
+        Value::Array(_) => Ok(serde_json::json!({ "repositories": value })),
+        Value::Object(_) => Ok(value),
+        _ => Err(anyhow::anyhow!(t!("config.not_array_or_object"))),
+    }
+}
+
+/// Configuration values of a config file.
+#[derive(Debug, Default)]
+pub struct ConfigDocument {
+    pub values: HashMap<ConfigKey, Value>,
+    /// Version the file was written with, when older than [`CONFIG_VERSION`]
+    pub migrated_from: Option<u64>,
+}
+
+/// Upgrade a config document to [`CONFIG_VERSION`], returning the version it had.
+pub fn migrate_config(value: Value) -> Result<(Value, u64)> {
+    let version = match value.get(VERSION_KEY) {
+        None => 0,
+        Some(version) => version.as_u64().ok_or_else(|| {
+            anyhow::anyhow!(t!(
+                "config.invalid_value",
+                key = VERSION_KEY,
+                expected = t!("config.expected.number"),
+                value = version
+            ))
+        })?,
+    };
+    if version > CONFIG_VERSION {
+        return Err(anyhow::anyhow!(t!(
+            "config.version_too_new",
+            version = version,
+            supported = CONFIG_VERSION
+        )));
+    }
+
+    let mut value = value;
+    for migration in &MIGRATIONS[version as usize..] {
+        value = migration(value)?;
+    }
+    Ok((value, version))
+}
+
+/// Parses a JSON configuration file content, migrating it from older versions.
 ///
 /// Expects `content` to be a byte slice representing either:
-/// - A simple JSON array of strings (treated as just the repositories list)
-/// - A JSON object with configuration keys (e.g., {"repositories": [...], ...})
+/// - A JSON object with configuration keys (e.g., {"version": 1, "repositories": [...], ...})
+/// - A simple JSON array of strings (version 0, treated as just the repositories list)
 ///
-/// - Returns `Ok(HashMap<ConfigKey, Value>)` containing all parsed configuration values.
-/// - Returns `Ok(HashMap with only repositories)` if input is a direct array.
-/// - Returns an empty HashMap if the input `content` is empty or contains only whitespace.
-/// - Returns an `Err` if the JSON parsing fails (e.g., invalid format).
-pub fn parse_config(content: &[u8]) -> Result<HashMap<ConfigKey, Value>> {
+/// - Returns an empty document if the input `content` is empty or contains only whitespace.
+/// - Returns an `Err` if the JSON parsing fails, a key is unknown or a value has the wrong type.
+pub fn parse_config_document(content: &[u8]) -> Result<ConfigDocument> {
     if content.iter().all(|b| b.is_ascii_whitespace()) {
-        return Ok(HashMap::new());
+        return Ok(ConfigDocument::default());
     }
 
-    let value: Value = serde_json::from_slice(content).context("Failed to parse config JSON")?;
+    let value: Value =
+        serde_json::from_slice(content).with_context(|| t!("config.parse_failed"))?;
+    let (value, version) = migrate_config(value)?;
+    let Value::Object(map) = value else {
+        return Err(anyhow::anyhow!(t!("config.not_object")));
+    };
 
-    let mut config_map = HashMap::new();
-
-    if let Value::Object(map) = &value {
-        for key in ConfigKey::all() {
-            if let Some(val) = map.get(key.as_str()) {
-                config_map.insert(*key, val.clone());
-            }
+    let mut values = HashMap::new();
+    for (name, value) in map {
+        if name == VERSION_KEY {
+            continue;
         }
-        return Ok(config_map);
+        let key = ConfigKey::from_name(&name).ok_or_else(|| {
+            let valid: Vec<_> = ConfigKey::all().iter().map(ConfigKey::as_str).collect();
+            anyhow::anyhow!(t!(
+                "config.unknown_key",
+                key = name,
+                keys = valid.join(", ")
+            ))
+        })?;
+        key.validate(&value)?;
+        values.insert(key, value);
     }
 
-    Err(anyhow::anyhow!(
-        "Config must be either an array of strings or an object"
-    ))
+    Ok(ConfigDocument {
+        values,
+        migrated_from: (version < CONFIG_VERSION).then_some(version),
+    })
+}
+
+/// Parses a JSON configuration file content into a map of configuration values.
+///
+/// See [`parse_config_document`].
+pub fn parse_config(content: &[u8]) -> Result<HashMap<ConfigKey, Value>> {
+    Ok(parse_config_document(content)?.values)
 }
 
 /// Merges `updates` into `base_config` and returns a new configuration map.
@@ -83,6 +352,295 @@ pub fn update_config(
     new_config
 }
 
+/// Where a configuration value comes from, from the lowest precedence to the highest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    /// `~/.config/atat/config.json`
+    Global(PathBuf),
+    /// `.atat/config.json` of the project
+    Project(PathBuf),
+    /// An `ATAT_*` environment variable
+    Env(String),
+    /// `--config <key>=<value>`
+    Flag,
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigOrigin::Global(path) => write!(f, "global:{}", path.display()),
+            ConfigOrigin::Project(path) => write!(f, "project:{}", path.display()),
+            ConfigOrigin::Env(name) => write!(f, "env:{name}"),
+            ConfigOrigin::Flag => write!(f, "command line"),
+        }
+    }
+}
+
+/// Configuration assembled from layers, each overriding the ones added before it.
+#[derive(Debug, Default)]
+pub struct LayeredConfig {
+    layers: Vec<(ConfigOrigin, HashMap<ConfigKey, Value>)>,
+}
+
+impl LayeredConfig {
+    pub fn new() -> Self {
+        LayeredConfig::default()
+    }
+
+    /// Add a layer whose values override those of the earlier layers.
+    pub fn push_layer(&mut self, origin: ConfigOrigin, values: HashMap<ConfigKey, Value>) {
+        self.layers.push((origin, values));
+    }
+
+    /// The merged configuration
+    pub fn values(&self) -> HashMap<ConfigKey, Value> {
+        self.layers
+            .iter()
+            .fold(HashMap::new(), |merged, (_, values)| {
+                update_config(&merged, values)
+            })
+    }
+
+    /// The effective value of each set key with its origin, in `ConfigKey::all` order
+    pub fn entries(&self) -> Vec<(ConfigKey, &Value, &ConfigOrigin)> {
+        ConfigKey::all()
+            .iter()
+            .filter_map(|key| {
+                self.layers
+                    .iter()
+                    .rev()
+                    .find_map(|(origin, values)| values.get(key).map(|value| (*key, value, origin)))
+            })
+            .collect()
+    }
+}
+
+/// Values set through `ATAT_*` environment variables (see `ConfigKey::env_var`)
+pub fn env_overrides<E>(env: E) -> Result<HashMap<ConfigKey, Value>>
+where
+    E: Fn(&str) -> Option<String>,
+{
+    let mut values = HashMap::new();
+    for key in ConfigKey::all() {
+        let name = key.env_var();
+        if let Some(text) = env(&name) {
+            let value = key
+                .parse_value(&text)
+                .with_context(|| t!("config.invalid_env", name = name))?;
+            values.insert(*key, value);
+        }
+    }
+    Ok(values)
+}
+
+/// Values set with `--config <key>=<value>` flags; later flags win.
+pub fn flag_overrides(flags: &[String]) -> Result<HashMap<ConfigKey, Value>> {
+    let mut values = HashMap::new();
+    for flag in flags {
+        let (name, text) = flag.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!(t!("config.invalid_flag", flag = format!("{flag:?}")))
+        })?;
+        let key = ConfigKey::from_name(name.trim())
+            .ok_or_else(|| anyhow::anyhow!(t!("config.unknown_flag_key", key = name)))?;
+        values.insert(key, key.parse_value(text)?);
+    }
+    Ok(values)
+}
+
+/// Text of a value in `atat config list`: strings without quotes, anything else as JSON
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Returns the configured task file path, or `TODO.md` if none is set.
+///
+/// The file format is selected from the extension (e.g. `TODO.org` for org-mode).
+pub fn todo_file(config: &HashMap<ConfigKey, Value>) -> String {
+    config
+        .get(&ConfigKey::TodoFile)
+        .and_then(Value::as_str)
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or(DEFAULT_TODO_FILE)
+        .to_string()
+}
+
+/// Whether pull writes `✅` completion dates, if `obsidian_tasks` is set.
+///
+/// Unset, dates are written to files that already use Obsidian Tasks fields.
+pub fn obsidian_tasks(config: &HashMap<ConfigKey, Value>) -> Option<bool> {
+    config
+        .get(&ConfigKey::ObsidianTasks)
+        .and_then(Value::as_bool)
+}
+
+/// An entry of `repositories`: `"owner/repo"` or
+/// `{"name": "owner/repo", "remote": "upstream", "account": "work"}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repository {
+    pub name: String,
+    /// Login account used for this repository; the default account if `None`
+    pub account: Option<String>,
+    /// Name given with `atat remote add <remote> <repository>`
+    pub remote: Option<String>,
+    /// GitHub Enterprise Server host of the repository; github.com if `None`
+    pub host: Option<String>,
+}
+
+/// Remote used by default when `default_repository` is not set, as in git
+pub const DEFAULT_REMOTE: &str = "origin";
+
+impl Repository {
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let optional_str = |map: &serde_json::Map<String, Value>, key: &str| match map.get(key) {
+            None | Some(Value::Null) => Some(None),
+            Some(value) => Some(Some(value.as_str()?.to_string())),
+        };
+        match value {
+            Value::String(name) => Some(Repository {
+                name: name.clone(),
+                account: None,
+                remote: None,
+                host: None,
+            }),
+            Value::Object(map) => Some(Repository {
+                name: map.get("name")?.as_str()?.to_string(),
+                account: optional_str(map, "account")?,
+                remote: optional_str(map, "remote")?,
+                host: optional_str(map, "host")?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// JSON form written to the config: a plain string unless a remote name, account or host is set.
+    pub fn to_value(&self) -> Value {
+        if self.account.is_none() && self.remote.is_none() && self.host.is_none() {
+            return Value::String(self.name.clone());
+        }
+        let mut map = serde_json::Map::new();
+        map.insert("name".to_string(), Value::String(self.name.clone()));
+        if let Some(remote) = &self.remote {
+            map.insert("remote".to_string(), Value::String(remote.clone()));
+        }
+        if let Some(account) = &self.account {
+            map.insert("account".to_string(), Value::String(account.clone()));
+        }
+        if let Some(host) = &self.host {
+            map.insert("host".to_string(), Value::String(host.clone()));
+        }
+        Value::Object(map)
+    }
+
+    /// Host serving the repository
+    pub fn host(&self) -> &str {
+        self.host.as_deref().unwrap_or(crate::github::GITHUB_HOST)
+    }
+
+    /// REST API URL of the repository, e.g. `https://api.github.com/repos/owner/repo`
+    pub fn api_url(&self) -> String {
+        format!(
+            "{}/repos/{}",
+            crate::github::api_base_url(self.host()),
+            self.name
+        )
+    }
+
+    /// Whether `name` is this repository's `owner/repo` or remote name
+    pub fn matches(&self, name: &str) -> bool {
+        self.name == name || self.remote.as_deref() == Some(name)
+    }
+}
+
+/// Returns the configured repositories. Fails if an entry is neither a string nor a valid object.
+pub fn repositories(config: &HashMap<ConfigKey, Value>) -> Result<Vec<Repository>> {
+    let Some(value) = config.get(&ConfigKey::Repositories) else {
+        return Ok(Vec::new());
+    };
+    value
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!(t!("config.invalid_repository")))?
+        .iter()
+        .map(|entry| {
+            Repository::from_value(entry)
+                .ok_or_else(|| anyhow::anyhow!(t!("config.invalid_repository")))
+        })
+        .collect()
+}
+
+/// Returns the repository commands operate on: `default_repository` if set, otherwise the
+/// `origin` remote, otherwise the first configured one. A `default_repository` listed in
+/// `repositories` keeps its account.
+pub fn default_repository(config: &HashMap<ConfigKey, Value>) -> Result<Repository> {
+    let repositories = repositories(config)?;
+    if let Some(name) = config
+        .get(&ConfigKey::DefaultRepository)
+        .and_then(Value::as_str)
+    {
+        return Ok(repositories
+            .into_iter()
+            .find(|repository| repository.name == name)
+            .unwrap_or_else(|| Repository {
+                name: name.to_string(),
+                account: None,
+                remote: None,
+                host: None,
+            }));
+    }
+    let origin = repositories
+        .iter()
+        .position(|repository| repository.remote.as_deref() == Some(DEFAULT_REMOTE))
+        .unwrap_or(0);
+    repositories
+        .into_iter()
+        .nth(origin)
+        .ok_or_else(|| crate::error::Error::NoRepositoryConfigured.into())
+}
+
+/// Labels added to every issue created by push
+pub fn labels(config: &HashMap<ConfigKey, Value>) -> Vec<String> {
+    config
+        .get(&ConfigKey::Labels)
+        .and_then(Value::as_array)
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `state_reason` sent when push closes an issue, if configured
+pub fn close_reason(config: &HashMap<ConfigKey, Value>) -> Option<String> {
+    config
+        .get(&ConfigKey::CloseReason)
+        .and_then(Value::as_str)
+        .filter(|reason| CLOSE_REASONS.contains(reason))
+        .map(str::to_string)
+}
+
+/// Returns the configured language of messages, if any.
+pub fn language(config: &HashMap<ConfigKey, Value>) -> Option<String> {
+    config
+        .get(&ConfigKey::Language)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Returns the configured git credential helper used to store the token, if any.
+pub fn credential_helper(config: &HashMap<ConfigKey, Value>) -> Option<String> {
+    config
+        .get(&ConfigKey::CredentialHelper)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|helper| !helper.is_empty())
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,10 +693,85 @@ mod tests {
     }
 
     #[test]
-    fn unknown_key_skipped() {
+    fn unknown_key_fails() {
         let json = r#"{"unknown": "value"}"#.as_bytes();
+        assert!(
+            parse_config(json)
+                .unwrap_err()
+                .to_string()
+                .starts_with("Unknown config key: unknown. Valid keys: repositories, ")
+        );
+    }
+
+    #[test]
+    fn wrong_type_fails() {
+        let json = r#"{"repositories": "owner/repo"}"#.as_bytes();
+        assert_eq!(
+            parse_config(json).unwrap_err().to_string(),
+            r#"Invalid value for repositories: expected a list of <owner>/<repo>, got "owner/repo""#
+        );
+        assert!(parse_config(br#"{"obsidian_tasks": "yes"}"#).is_err());
+        assert!(parse_config(br#"{"version": "1"}"#).is_err());
+    }
+
+    #[test]
+    fn unversioned_config_is_migrated() {
+        let document = parse_config_document(br#"["owner/repo1"]"#).unwrap();
+        assert_eq!(document.migrated_from, Some(0));
+        assert_eq!(
+            document.values.get(&ConfigKey::Repositories),
+            Some(&json!(["owner/repo1"]))
+        );
+
+        let document = parse_config_document(br#"{"todo_file": "TODO.org"}"#).unwrap();
+        assert_eq!(document.migrated_from, Some(0));
+
+        let current = format!(r#"{{"version": {CONFIG_VERSION}, "todo_file": "TODO.org"}}"#);
+        let document = parse_config_document(current.as_bytes()).unwrap();
+        assert_eq!(document.migrated_from, None);
+        assert_eq!(todo_file(&document.values), "TODO.org");
+
+        assert!(parse_config(br#""owner/repo""#).is_err());
+    }
+
+    #[test]
+    fn newer_config_version_fails() {
+        let newer = format!(r#"{{"version": {}}}"#, CONFIG_VERSION + 1);
+        assert_eq!(
+            parse_config(newer.as_bytes()).unwrap_err().to_string(),
+            format!(
+                "Config version {} is newer than this atat supports ({CONFIG_VERSION}). Please upgrade atat",
+                CONFIG_VERSION + 1
+            )
+        );
+    }
+
+    #[test]
+    fn todo_file_key_works() {
+        let json = r#"{"repositories": [], "todo_file": "TODO.org"}"#.as_bytes();
         let config = parse_config(json).unwrap();
-        assert!(!config.contains_key(&ConfigKey::Repositories));
+        assert_eq!(todo_file(&config), "TODO.org");
+    }
+
+    #[test]
+    fn todo_file_defaults_to_todo_md() {
+        assert_eq!(todo_file(&HashMap::new()), "TODO.md");
+
+        let mut config = HashMap::new();
+        config.insert(ConfigKey::TodoFile, json!(""));
+        assert_eq!(todo_file(&config), "TODO.md");
+    }
+
+    #[test]
+    fn obsidian_tasks_key_works() {
+        let json = r#"{"obsidian_tasks": true}"#.as_bytes();
+        let config = parse_config(json).unwrap();
+        assert_eq!(obsidian_tasks(&config), Some(true));
+
+        let config = parse_config(br#"{"obsidian_tasks": false}"#).unwrap();
+        assert_eq!(obsidian_tasks(&config), Some(false));
+
+        assert_eq!(obsidian_tasks(&HashMap::new()), None);
     }
 
     #[test]
@@ -218,4 +851,295 @@ mod tests {
         );
         assert!(base_config.is_empty());
     }
+
+    #[test]
+    fn test_credential_helper() {
+        let config = parse_config(br#"{"credential_helper": "!pass-helper"}"#).unwrap();
+        assert_eq!(credential_helper(&config).as_deref(), Some("!pass-helper"));
+
+        let blank = parse_config(br#"{"credential_helper": " "}"#).unwrap();
+        assert_eq!(credential_helper(&blank), None);
+        assert_eq!(credential_helper(&HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_language() {
+        let config = parse_config(br#"{"language": "ja"}"#).unwrap();
+        assert_eq!(language(&config).as_deref(), Some("ja"));
+        assert_eq!(language(&HashMap::new()), None);
+
+        assert!(parse_config(br#"{"language": "fr"}"#).is_err());
+        assert!(ConfigKey::Language.parse_value("fr").is_err());
+    }
+
+    #[test]
+    fn test_repositories_with_accounts() {
+        let config = parse_config(
+            br#"{"repositories": ["owner/a", {"name": "corp/b", "account": "work"}]}"#,
+        )
+        .unwrap();
+
+        let repos = repositories(&config).unwrap();
+
+        assert_eq!(
+            repos,
+            vec![
+                Repository {
+                    name: "owner/a".to_string(),
+                    account: None,
+                    remote: None,
+                    host: None
+                },
+                Repository {
+                    name: "corp/b".to_string(),
+                    account: Some("work".to_string()),
+                    remote: None,
+                    host: None
+                },
+            ]
+        );
+        assert_eq!(repos[0].to_value(), serde_json::json!("owner/a"));
+        assert_eq!(
+            repos[1].to_value(),
+            serde_json::json!({"name": "corp/b", "account": "work"})
+        );
+        assert_eq!(default_repository(&config).unwrap(), repos[0]);
+    }
+
+    #[test]
+    fn test_named_remotes() {
+        let config = parse_config(
+            br#"{"repositories": ["owner/a", {"name": "owner/b", "remote": "origin"},
+                                   {"name": "up/b", "remote": "upstream", "account": "work"}]}"#,
+        )
+        .unwrap();
+        let repos = repositories(&config).unwrap();
+
+        assert_eq!(repos[2].remote.as_deref(), Some("upstream"));
+        assert!(repos[2].matches("upstream") && repos[2].matches("up/b"));
+        assert_eq!(
+            repos[2].to_value(),
+            serde_json::json!({"name": "up/b", "remote": "upstream", "account": "work"})
+        );
+        // The origin remote is the default, not the first entry
+        assert_eq!(default_repository(&config).unwrap().name, "owner/b");
+
+        assert!(
+            ConfigKey::Repositories
+                .validate(&serde_json::json!([{"name": "a/b", "remote": "bad/name"}]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_enterprise_host() {
+        let config = parse_config(
+            br#"{"repositories": ["owner/a", {"name": "team/b", "host": "ghe.example.com"}]}"#,
+        )
+        .unwrap();
+        let repos = repositories(&config).unwrap();
+
+        assert_eq!(repos[0].host(), "github.com");
+        assert_eq!(repos[0].api_url(), "https://api.github.com/repos/owner/a");
+        assert_eq!(repos[1].host(), "ghe.example.com");
+        assert_eq!(
+            repos[1].api_url(),
+            "https://ghe.example.com/api/v3/repos/team/b"
+        );
+        assert_eq!(
+            repos[1].to_value(),
+            serde_json::json!({"name": "team/b", "host": "ghe.example.com"})
+        );
+
+        assert!(
+            ConfigKey::Repositories
+                .validate(&serde_json::json!([{"name": "a/b", "host": "https://ghe/"}]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_default_repository_setting() {
+        let config = parse_config(
+            br#"{"repositories": ["owner/a", {"name": "corp/b", "account": "work"}],
+                 "default_repository": "corp/b"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            default_repository(&config).unwrap(),
+            Repository {
+                name: "corp/b".to_string(),
+                account: Some("work".to_string()),
+                remote: None,
+                host: None
+            }
+        );
+
+        let config = parse_config(br#"{"default_repository": "other/c"}"#).unwrap();
+        assert_eq!(default_repository(&config).unwrap().name, "other/c");
+    }
+
+    #[test]
+    fn test_labels_and_close_reason() {
+        let config =
+            parse_config(br#"{"labels": ["todo", "atat"], "close_reason": "not_planned"}"#)
+                .unwrap();
+        assert_eq!(labels(&config), vec!["todo", "atat"]);
+        assert_eq!(close_reason(&config).as_deref(), Some("not_planned"));
+
+        assert!(labels(&HashMap::new()).is_empty());
+        assert_eq!(close_reason(&HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_parse_value_validates() {
+        assert_eq!(
+            ConfigKey::Labels.parse_value("todo, atat").unwrap(),
+            json!(["todo", "atat"])
+        );
+        assert_eq!(
+            ConfigKey::CloseReason.parse_value("completed").unwrap(),
+            json!("completed")
+        );
+        assert_eq!(
+            ConfigKey::CloseReason
+                .parse_value("wontfix")
+                .unwrap_err()
+                .to_string(),
+            r#"Invalid value for close_reason: expected one of completed, not_planned, got "wontfix""#
+        );
+        assert!(ConfigKey::DefaultRepository.parse_value("owner").is_err());
+        assert!(ConfigKey::Repositories.parse_value("owner/a,b").is_err());
+        assert!(ConfigKey::TodoFile.parse_value(" ").is_err());
+    }
+
+    #[test]
+    fn test_validate_values() {
+        assert!(
+            ConfigKey::Repositories
+                .validate(&json!(["owner/a", {"name": "corp/b", "account": "work"}]))
+                .is_ok()
+        );
+        assert!(
+            ConfigKey::Repositories
+                .validate(&json!([{"name": "corp/b", "account": "../x"}]))
+                .is_err()
+        );
+        assert!(ConfigKey::ObsidianTasks.validate(&json!("true")).is_err());
+        assert!(ConfigKey::Labels.validate(&json!(["ok", ""])).is_err());
+    }
+
+    #[test]
+    fn test_repositories_errors() {
+        assert_eq!(
+            default_repository(&HashMap::new()).unwrap_err().to_string(),
+            "No repository configured"
+        );
+        let empty = parse_config(br#"{"repositories": []}"#).unwrap();
+        assert_eq!(
+            default_repository(&empty).unwrap_err().to_string(),
+            "No repository configured"
+        );
+        // Config files are validated when parsed; values set in code are checked here
+        assert!(parse_config(br#"{"repositories": [{"account": "work"}]}"#).is_err());
+        let invalid = HashMap::from([(ConfigKey::Repositories, json!([{"account": "work"}]))]);
+        assert_eq!(
+            repositories(&invalid).unwrap_err().to_string(),
+            "Invalid repository configuration"
+        );
+    }
+
+    fn layered() -> LayeredConfig {
+        let mut config = LayeredConfig::new();
+        config.push_layer(
+            ConfigOrigin::Global(PathBuf::from("/home/me/.config/atat/config.json")),
+            HashMap::from([
+                (ConfigKey::TodoFile, json!("TODO.org")),
+                (ConfigKey::ObsidianTasks, json!(true)),
+            ]),
+        );
+        config.push_layer(
+            ConfigOrigin::Project(PathBuf::from(".atat/config.json")),
+            HashMap::from([
+                (ConfigKey::Repositories, json!(["owner/repo"])),
+                (ConfigKey::TodoFile, json!("TODO.md")),
+            ]),
+        );
+        config
+    }
+
+    #[test]
+    fn layered_config_overrides_earlier_layers() {
+        let mut config = layered();
+        config.push_layer(
+            ConfigOrigin::Env("ATAT_OBSIDIAN_TASKS".to_string()),
+            HashMap::from([(ConfigKey::ObsidianTasks, json!(false))]),
+        );
+
+        let values = config.values();
+        assert_eq!(values[&ConfigKey::TodoFile], json!("TODO.md"));
+        assert_eq!(values[&ConfigKey::ObsidianTasks], json!(false));
+        assert_eq!(values[&ConfigKey::Repositories], json!(["owner/repo"]));
+
+        let entries: Vec<(ConfigKey, String)> = config
+            .entries()
+            .into_iter()
+            .map(|(key, _, origin)| (key, origin.to_string()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    ConfigKey::Repositories,
+                    "project:.atat/config.json".to_string()
+                ),
+                (ConfigKey::TodoFile, "project:.atat/config.json".to_string()),
+                (
+                    ConfigKey::ObsidianTasks,
+                    "env:ATAT_OBSIDIAN_TASKS".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn env_overrides_parse_values() {
+        let env = |name: &str| match name {
+            "ATAT_REPOSITORIES" => Some("owner/a, owner/b".to_string()),
+            "ATAT_OBSIDIAN_TASKS" => Some("yes".to_string()),
+            _ => None,
+        };
+
+        let values = env_overrides(env).unwrap();
+
+        assert_eq!(
+            values[&ConfigKey::Repositories],
+            json!(["owner/a", "owner/b"])
+        );
+        assert_eq!(values[&ConfigKey::ObsidianTasks], json!(true));
+        assert!(!values.contains_key(&ConfigKey::TodoFile));
+
+        let invalid = |name: &str| (name == "ATAT_OBSIDIAN_TASKS").then(|| "maybe".to_string());
+        assert!(env_overrides(invalid).is_err());
+    }
+
+    #[test]
+    fn flag_overrides_parse_key_value() {
+        let flags = vec![
+            "todo_file=TODO.org".to_string(),
+            "todo_file=NOTES.md".to_string(),
+        ];
+        let values = flag_overrides(&flags).unwrap();
+        assert_eq!(values[&ConfigKey::TodoFile], json!("NOTES.md"));
+
+        assert!(flag_overrides(&["todo_file".to_string()]).is_err());
+        assert!(flag_overrides(&["unknown=1".to_string()]).is_err());
+    }
+
+    #[test]
+    fn display_value_formats() {
+        assert_eq!(display_value(&json!("TODO.md")), "TODO.md");
+        assert_eq!(display_value(&json!(["a/b"])), r#"["a/b"]"#);
+        assert_eq!(display_value(&json!(true)), "true");
+    }
 }
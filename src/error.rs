@@ -0,0 +1,88 @@
+use crate::i18n::t;
+use std::fmt;
+
+/// Failures the user can fix, reported with a hint on how
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    AuthenticationRequired,
+    NoRepositoryConfigured,
+    /// Task file, as configured
+    TodoFileNotFound(String),
+    /// `owner/repo`
+    RepositoryNotAccessible(String),
+    /// Host, with the account if it is not the default one
+    NotLoggedIn(String),
+    /// GitHub Enterprise Server host without a token
+    EnterpriseTokenRequired(String),
+}
+
+impl Error {
+    /// What to do about the error
+    pub fn hint(&self) -> String {
+        match self {
+            Error::AuthenticationRequired => t!("error.authentication_required.hint"),
+            Error::NoRepositoryConfigured => t!("error.no_repository.hint"),
+            Error::TodoFileNotFound(todo_file) => {
+                t!("error.todo_file_not_found.hint", file = todo_file)
+            }
+            Error::RepositoryNotAccessible(_) => t!("error.repository_not_accessible.hint"),
+            Error::NotLoggedIn(_) => t!("error.not_logged_in.hint"),
+            Error::EnterpriseTokenRequired(host) => {
+                t!("error.enterprise_token_required.hint", host = host)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Error::AuthenticationRequired => t!("error.authentication_required"),
+            Error::NoRepositoryConfigured => t!("error.no_repository"),
+            Error::TodoFileNotFound(todo_file) => t!("error.todo_file_not_found", file = todo_file),
+            Error::RepositoryNotAccessible(repo) => {
+                t!("error.repository_not_accessible", repo = repo)
+            }
+            Error::NotLoggedIn(target) => t!("error.not_logged_in", target = target),
+            Error::EnterpriseTokenRequired(host) => {
+                t!("error.enterprise_token_required", host = host)
+            }
+        };
+        f.write_str(&message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Hint for the first known error in the chain of `error`
+pub fn hint(error: &anyhow::Error) -> Option<String> {
+    error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<Error>())
+        .map(Error::hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hint() {
+        let error = anyhow::Error::from(Error::AuthenticationRequired);
+        assert_eq!(error.to_string(), "Authentication required");
+        assert_eq!(
+            hint(&error).as_deref(),
+            Some("To get started, please run:  atat login")
+        );
+
+        let error = anyhow::Error::from(Error::TodoFileNotFound("TODO.md".to_string()))
+            .context("Failed to import issues");
+        assert_eq!(
+            format!("{error:#}"),
+            "Failed to import issues: TODO.md file not found"
+        );
+        assert!(hint(&error).unwrap().contains("atat init"));
+
+        assert_eq!(hint(&anyhow::anyhow!("Something else")), None);
+    }
+}
@@ -0,0 +1,212 @@
+use std::path::{Path, PathBuf};
+
+/// A GitHub repository identified from a git remote URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRepository {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl RemoteRepository {
+    /// `owner/repo` as written to `repositories`
+    pub fn name(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+
+    /// Host written to `repositories`: `None` for github.com, the server of GitHub Enterprise otherwise
+    pub fn enterprise_host(&self) -> Option<String> {
+        (self.host != crate::github::GITHUB_HOST).then(|| self.host.clone())
+    }
+}
+
+/// Returns the `url` of `[remote "<remote>"]` in the content of a git config file.
+pub fn remote_url(git_config: &str, remote: &str) -> Option<String> {
+    let section = format!("[remote \"{remote}\"]");
+    let mut in_section = false;
+    for line in git_config.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_section = line == section;
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=')
+            && key.trim().eq_ignore_ascii_case("url")
+        {
+            return Some(value.trim().trim_matches('"').to_string());
+        }
+    }
+    None
+}
+
+/// Names of the `[remote "<name>"]` sections in the content of a git config file.
+pub fn remote_names(git_config: &str) -> Vec<String> {
+    git_config
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix("[remote \"")?
+                .strip_suffix("\"]")
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// Parse an HTTPS or SSH remote URL into host, owner and repository.
+///
+/// Accepts `https://host/owner/repo(.git)`, `ssh://git@host[:port]/owner/repo(.git)`
+/// and the scp-like `git@host:owner/repo(.git)`.
+pub fn parse_remote_url(url: &str) -> Option<RemoteRepository> {
+    let url = url.trim();
+    let (host, path) = if let Some((scheme, rest)) = url.split_once("://") {
+        if !matches!(scheme, "https" | "http" | "ssh" | "git") {
+            return None;
+        }
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        let host = host.split(':').next()?;
+        (host, path)
+    } else {
+        // scp-like syntax: [user@]host:owner/repo
+        let (authority, path) = url.split_once(':')?;
+        if authority.contains('/') {
+            return None;
+        }
+        (authority.rsplit('@').next()?, path)
+    };
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    match path.split('/').collect::<Vec<_>>().as_slice() {
+        [owner, repo] if !host.is_empty() && !owner.is_empty() && !repo.is_empty() => {
+            Some(RemoteRepository {
+                host: host.to_lowercase(),
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Path of the git config file of the repository at `root`.
+///
+/// `.git` may be a file pointing to the real git directory (worktrees and submodules);
+/// a worktree shares the config of the main repository through `commondir`.
+pub fn git_config_path(root: &Path) -> Option<PathBuf> {
+    let dot_git = root.join(".git");
+    let git_dir = if dot_git.is_dir() {
+        dot_git
+    } else {
+        let content = std::fs::read_to_string(&dot_git).ok()?;
+        let git_dir = PathBuf::from(content.trim().strip_prefix("gitdir:")?.trim());
+        root.join(git_dir)
+    };
+
+    let common_dir = std::fs::read_to_string(git_dir.join("commondir"))
+        .map(|common_dir| git_dir.join(common_dir.trim()))
+        .unwrap_or(git_dir);
+    Some(common_dir.join("config"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(host: &str, owner: &str, repo: &str) -> Option<RemoteRepository> {
+        Some(RemoteRepository {
+            host: host.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_remote_url() {
+        let config = r#"[core]
+	bare = false
+[remote "upstream"]
+	url = https://github.com/upstream/project.git
+[remote "origin"]
+	url = git@github.com:me/project.git
+	fetch = +refs/heads/*:refs/remotes/origin/*
+[branch "main"]
+	remote = origin
+"#;
+
+        assert_eq!(
+            remote_url(config, "origin").as_deref(),
+            Some("git@github.com:me/project.git")
+        );
+        assert_eq!(
+            remote_url(config, "upstream").as_deref(),
+            Some("https://github.com/upstream/project.git")
+        );
+        assert_eq!(remote_url(config, "missing"), None);
+        assert_eq!(remote_names(config), vec!["upstream", "origin"]);
+    }
+
+    #[test]
+    fn test_parse_https_urls() {
+        assert_eq!(
+            parse_remote_url("https://github.com/owner/repo.git"),
+            remote("github.com", "owner", "repo")
+        );
+        assert_eq!(
+            parse_remote_url("https://user@GitHub.com/owner/repo/"),
+            remote("github.com", "owner", "repo")
+        );
+        assert_eq!(
+            parse_remote_url("https://ghe.example.com/team/tool"),
+            remote("ghe.example.com", "team", "tool")
+        );
+    }
+
+    #[test]
+    fn test_parse_ssh_urls() {
+        assert_eq!(
+            parse_remote_url("git@github.com:owner/repo.git"),
+            remote("github.com", "owner", "repo")
+        );
+        assert_eq!(
+            parse_remote_url("ssh://git@ghe.example.com:2222/team/tool.git"),
+            remote("ghe.example.com", "team", "tool")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_other_urls() {
+        assert_eq!(parse_remote_url("/srv/git/repo.git"), None);
+        assert_eq!(parse_remote_url("https://github.com/owner"), None);
+        assert_eq!(parse_remote_url("https://github.com/a/b/c"), None);
+        assert_eq!(parse_remote_url("file:///srv/git/repo.git"), None);
+    }
+
+    #[test]
+    fn test_git_config_path_follows_gitdir_file() {
+        let dir = std::env::temp_dir().join(format!("atat-git-remote-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let main = dir.join("main");
+        let worktree = dir.join("worktree");
+        std::fs::create_dir_all(main.join(".git/worktrees/wt")).unwrap();
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(main.join(".git/worktrees/wt/commondir"), "../..\n").unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", main.join(".git/worktrees/wt").display()),
+        )
+        .unwrap();
+
+        assert_eq!(git_config_path(&main), Some(main.join(".git/config")));
+        assert_eq!(
+            git_config_path(&worktree),
+            Some(main.join(".git/worktrees/wt/../../config"))
+        );
+        assert_eq!(git_config_path(&dir), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
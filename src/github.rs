@@ -1,4 +1,17 @@
+/// Host of github.com, where repositories are unless they name another host
+pub const GITHUB_HOST: &str = "github.com";
+
+/// Base URL of the REST API of `host`: api.github.com, or `/api/v3` of a GitHub Enterprise Server
+pub fn api_base_url(host: &str) -> String {
+    if host == GITHUB_HOST {
+        "https://api.github.com".to_string()
+    } else {
+        format!("https://{host}/api/v3")
+    }
+}
+
 pub mod issues;
+pub mod milestones;
 pub mod pull;
 pub mod push;
 pub(crate) mod title;
@@ -1,8 +1,11 @@
+use chrono::NaiveDate;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct GitHubIssue {
     pub number: u64,
     pub title: String,
     pub state: IssueState,
+    pub closed_at: Option<NaiveDate>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -0,0 +1,123 @@
+use chrono::NaiveDate;
+
+/// Title of the milestone created for a task due date
+pub fn milestone_title(due: NaiveDate) -> String {
+    due.format("%Y-%m-%d").to_string()
+}
+
+/// `due_on` value sent when creating a milestone for `due`
+pub fn milestone_due_on(due: NaiveDate) -> String {
+    format!("{}T00:00:00Z", milestone_title(due))
+}
+
+/// Find the open milestone whose due date (or title) matches `due`.
+///
+/// Only the date part of `due_on` is compared, since GitHub may shift the time of day.
+/// Closed milestones are skipped: they only share the date, and their work is done.
+pub fn find_milestone_for_due_date(
+    milestones_json: &[serde_json::Value],
+    due: NaiveDate,
+) -> Option<u64> {
+    let title = milestone_title(due);
+    milestones_json
+        .iter()
+        .filter(|milestone| milestone["state"].as_str() != Some("closed"))
+        .find(|milestone| {
+            milestone["due_on"].as_str().and_then(|d| d.get(..10)) == Some(title.as_str())
+                || milestone["title"].as_str() == Some(title.as_str())
+        })
+        .and_then(|milestone| milestone["number"].as_u64())
+}
+
+/// Find a closed milestone titled after `due`, which blocks creating one with that title
+pub fn find_closed_milestone_titled(
+    milestones_json: &[serde_json::Value],
+    due: NaiveDate,
+) -> Option<u64> {
+    let title = milestone_title(due);
+    milestones_json
+        .iter()
+        .find(|milestone| {
+            milestone["state"].as_str() == Some("closed")
+                && milestone["title"].as_str() == Some(title.as_str())
+        })
+        .and_then(|milestone| milestone["number"].as_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_milestone_title_and_due_on() {
+        assert_eq!(milestone_title(date(2026, 10, 20)), "2026-10-20");
+        assert_eq!(milestone_due_on(date(2026, 1, 2)), "2026-01-02T00:00:00Z");
+    }
+
+    #[test]
+    fn test_find_milestone_by_due_on() {
+        let milestones = vec![
+            serde_json::json!({"number": 1, "title": "v1.0", "due_on": "2026-10-01T07:00:00Z"}),
+            serde_json::json!({"number": 2, "title": "Sprint 5", "due_on": "2026-10-20T07:00:00Z"}),
+        ];
+
+        assert_eq!(
+            find_milestone_for_due_date(&milestones, date(2026, 10, 20)),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_find_milestone_by_title() {
+        let milestones =
+            vec![serde_json::json!({"number": 3, "title": "2026-10-20", "due_on": null})];
+
+        assert_eq!(
+            find_milestone_for_due_date(&milestones, date(2026, 10, 20)),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_find_milestone_skips_closed() {
+        let milestones = vec![
+            serde_json::json!({"number": 1, "title": "Sprint 4", "state": "closed", "due_on": "2026-10-20T07:00:00Z"}),
+            serde_json::json!({"number": 2, "title": "2026-10-20", "state": "closed", "due_on": null}),
+        ];
+
+        assert_eq!(
+            find_milestone_for_due_date(&milestones, date(2026, 10, 20)),
+            None
+        );
+        assert_eq!(
+            find_closed_milestone_titled(&milestones, date(2026, 10, 20)),
+            Some(2)
+        );
+
+        let mut milestones = milestones;
+        milestones.push(
+            serde_json::json!({"number": 3, "title": "Sprint 5", "state": "open", "due_on": "2026-10-20T07:00:00Z"}),
+        );
+        assert_eq!(
+            find_milestone_for_due_date(&milestones, date(2026, 10, 20)),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_find_milestone_none_matching() {
+        let milestones = vec![
+            serde_json::json!({"number": 1, "title": "v1.0", "due_on": "2026-10-01T07:00:00Z"}),
+        ];
+
+        assert_eq!(
+            find_milestone_for_due_date(&milestones, date(2026, 10, 20)),
+            None
+        );
+        assert_eq!(find_milestone_for_due_date(&[], date(2026, 10, 20)), None);
+    }
+}
@@ -23,6 +23,9 @@ pub fn parse_github_issues(issues_json: &[serde_json::Value]) -> Vec<GitHubIssue
                         number,
                         title: title.to_string(),
                         state,
+                        closed_at: issue["closed_at"]
+                            .as_str()
+                            .and_then(|closed_at| closing_date(closed_at, &chrono::Local)),
                     })
                 } else {
                     None
@@ -34,6 +37,14 @@ pub fn parse_github_issues(issues_json: &[serde_json::Value]) -> Vec<GitHubIssue
         .collect()
 }
 
+/// Date a `closed_at` timestamp falls on in `timezone`, so that a task closed
+/// late in the evening is not dated the next day in UTC
+fn closing_date<Tz: chrono::TimeZone>(closed_at: &str, timezone: &Tz) -> Option<chrono::NaiveDate> {
+    chrono::DateTime::parse_from_rfc3339(closed_at)
+        .ok()
+        .map(|closed_at| closed_at.with_timezone(timezone).date_naive())
+}
+
 pub fn fetch_github_issues<F>(repo: &str, token: &str, issue_fetcher: F) -> Result<Vec<GitHubIssue>>
 where
     F: Fn(&str, &str, u32, u32) -> Result<Vec<serde_json::Value>>,
@@ -118,8 +129,7 @@ fn synchronize_titles(
                     if is_stale_local_text {
                         TodoItem {
                             text: github_issue.title.clone(),
-                            is_checked: todo_item.is_checked,
-                            issue_number: todo_item.issue_number,
+                            ..todo_item.clone()
                         }
                     } else {
                         local_edits.push(github_issue.number);
@@ -153,9 +163,8 @@ pub fn synchronize_with_github_issues(
                 .map_or_else(
                     || todo_item.clone(),
                     |_| TodoItem {
-                        text: todo_item.text.clone(),
                         is_checked: true,
-                        issue_number: todo_item.issue_number,
+                        ..todo_item.clone()
                     },
                 )
         })
@@ -174,12 +183,46 @@ pub fn synchronize_with_github_issues(
             text: github_issue.title.clone(),
             is_checked: false,
             issue_number: Some(github_issue.number),
+            ..Default::default()
         })
         .collect();
 
     updated_items.into_iter().chain(new_items).collect()
 }
 
+/// Set the `✅` completion date on items that `synchronize_with_github_issues` checked.
+///
+/// `previous_items` are the items before synchronization; only items that were
+/// unchecked there and have no completion date get the issue's closing date.
+pub fn stamp_completion_dates(
+    previous_items: &[TodoItem],
+    updated_items: &[TodoItem],
+    github_issues: &[GitHubIssue],
+) -> Vec<TodoItem> {
+    updated_items
+        .iter()
+        .map(|todo_item| {
+            let newly_checked = todo_item.is_checked
+                && todo_item.metadata.done.is_none()
+                && previous_items.iter().any(|previous| {
+                    !previous.is_checked && previous.issue_number == todo_item.issue_number
+                });
+
+            let closed_at = todo_item
+                .issue_number
+                .filter(|_| newly_checked)
+                .and_then(|number| github_issues.iter().find(|issue| issue.number == number))
+                .and_then(|issue| issue.closed_at);
+
+            let mut stamped = todo_item.clone();
+            if closed_at.is_some() {
+                stamped.metadata.done = closed_at;
+            }
+            stamped
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,6 +255,131 @@ mod tests {
         assert_eq!(issues[1].state, IssueState::Closed);
     }
 
+    #[test]
+    fn test_parse_github_issues_closed_at() {
+        let issues_json = vec![
+            serde_json::json!({
+                "number": 1,
+                "title": "Closed issue",
+                "state": "closed",
+                "closed_at": "2026-10-10T12:34:56Z",
+                "pull_request": null
+            }),
+            serde_json::json!({
+                "number": 2,
+                "title": "Open issue",
+                "state": "open",
+                "closed_at": null,
+                "pull_request": null
+            }),
+        ];
+
+        let issues = parse_github_issues(&issues_json);
+
+        assert_eq!(
+            issues[0].closed_at,
+            closing_date("2026-10-10T12:34:56Z", &chrono::Local)
+        );
+        assert!(issues[0].closed_at.is_some());
+        assert_eq!(issues[1].closed_at, None);
+    }
+
+    #[test]
+    fn test_closing_date_in_local_time() {
+        let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d);
+        let tokyo = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+        let new_york = chrono::FixedOffset::west_opt(4 * 3600).unwrap();
+
+        assert_eq!(
+            closing_date("2026-10-10T20:00:00Z", &tokyo),
+            date(2026, 10, 11)
+        );
+        assert_eq!(
+            closing_date("2026-10-10T02:00:00Z", &new_york),
+            date(2026, 10, 9)
+        );
+        assert_eq!(
+            closing_date("2026-10-10T12:00:00Z", &chrono::Utc),
+            date(2026, 10, 10)
+        );
+        assert_eq!(closing_date("2026-10-10", &chrono::Utc), None);
+    }
+
+    #[test]
+    fn test_stamp_completion_dates_sets_done_on_newly_checked() {
+        let closed_on = chrono::NaiveDate::from_ymd_opt(2026, 10, 10).unwrap();
+        let previous_items = vec![
+            TodoItem {
+                text: "Newly closed".to_string(),
+                is_checked: false,
+                issue_number: Some(1),
+                ..Default::default()
+            },
+            TodoItem {
+                text: "Already checked".to_string(),
+                is_checked: true,
+                issue_number: Some(2),
+                ..Default::default()
+            },
+        ];
+        let updated_items: Vec<TodoItem> = previous_items
+            .iter()
+            .map(|item| TodoItem {
+                is_checked: true,
+                ..item.clone()
+            })
+            .collect();
+        let github_issues = vec![
+            GitHubIssue {
+                number: 1,
+                title: "Newly closed".to_string(),
+                state: IssueState::Closed,
+                closed_at: Some(closed_on),
+            },
+            GitHubIssue {
+                number: 2,
+                title: "Already checked".to_string(),
+                state: IssueState::Closed,
+                closed_at: Some(closed_on),
+            },
+        ];
+
+        let result = stamp_completion_dates(&previous_items, &updated_items, &github_issues);
+
+        assert_eq!(result[0].metadata.done, Some(closed_on));
+        assert_eq!(result[1].metadata.done, None);
+    }
+
+    #[test]
+    fn test_stamp_completion_dates_keeps_existing_done_date() {
+        let existing = chrono::NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let metadata = crate::task_metadata::TaskMetadata {
+            done: Some(existing),
+            ..Default::default()
+        };
+        let previous_items = vec![TodoItem {
+            text: "Task".to_string(),
+            is_checked: false,
+            issue_number: Some(1),
+            metadata: metadata.clone(),
+            ..Default::default()
+        }];
+        let updated_items = vec![TodoItem {
+            is_checked: true,
+            ..previous_items[0].clone()
+        }];
+        let github_issues = vec![GitHubIssue {
+            number: 1,
+            title: "Task".to_string(),
+            state: IssueState::Closed,
+            closed_at: chrono::NaiveDate::from_ymd_opt(2026, 10, 10),
+        }];
+
+        let result = stamp_completion_dates(&previous_items, &updated_items, &github_issues);
+
+        assert_eq!(result[0].metadata.done, Some(existing));
+    }
+
     #[test]
     fn test_parse_github_issues_filters_pull_requests() {
         let issues_json = vec![
@@ -433,11 +601,13 @@ mod tests {
                 text: "Fix bug".to_string(),
                 is_checked: false,
                 issue_number: Some(123),
+                ..Default::default()
             },
             TodoItem {
                 text: "Add feature".to_string(),
                 is_checked: false,
                 issue_number: Some(456),
+                ..Default::default()
             },
         ];
         let github_issues = vec![
@@ -445,11 +615,13 @@ mod tests {
                 number: 123,
                 title: "Fix bug".to_string(),
                 state: IssueState::Closed,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 456,
                 title: "Add feature".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
         ];
 
@@ -457,10 +629,10 @@ mod tests {
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].text, "Fix bug");
-        assert_eq!(result[0].is_checked, true);
+        assert!(result[0].is_checked);
         assert_eq!(result[0].issue_number, Some(123));
         assert_eq!(result[1].text, "Add feature");
-        assert_eq!(result[1].is_checked, false);
+        assert!(!result[1].is_checked);
         assert_eq!(result[1].issue_number, Some(456));
     }
 
@@ -470,17 +642,20 @@ mod tests {
             text: "Existing task".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![
             GitHubIssue {
                 number: 123,
                 title: "Existing task".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 456,
                 title: "New task".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
         ];
 
@@ -490,7 +665,7 @@ mod tests {
         assert_eq!(result[0].text, "Existing task");
         assert_eq!(result[0].issue_number, Some(123));
         assert_eq!(result[1].text, "New task");
-        assert_eq!(result[1].is_checked, false);
+        assert!(!result[1].is_checked);
         assert_eq!(result[1].issue_number, Some(456));
     }
 
@@ -500,18 +675,20 @@ mod tests {
             text: "Completed task".to_string(),
             is_checked: true,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Completed task".to_string(),
             state: IssueState::Closed,
+            closed_at: None,
         }];
 
         let result = synchronize_with_github_issues(&todo_items, &github_issues);
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].text, "Completed task");
-        assert_eq!(result[0].is_checked, true);
+        assert!(result[0].is_checked);
         assert_eq!(result[0].issue_number, Some(123));
     }
 
@@ -523,11 +700,13 @@ mod tests {
                 number: 123,
                 title: "Closed issue".to_string(),
                 state: IssueState::Closed,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 456,
                 title: "Open issue".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
         ];
 
@@ -535,7 +714,7 @@ mod tests {
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].text, "Open issue");
-        assert_eq!(result[0].is_checked, false);
+        assert!(!result[0].is_checked);
         assert_eq!(result[0].issue_number, Some(456));
     }
 
@@ -546,27 +725,30 @@ mod tests {
                 text: "Local task".to_string(),
                 is_checked: false,
                 issue_number: None,
+                ..Default::default()
             },
             TodoItem {
                 text: "Task with issue".to_string(),
                 is_checked: false,
                 issue_number: Some(123),
+                ..Default::default()
             },
         ];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Task with issue".to_string(),
             state: IssueState::Closed,
+            closed_at: None,
         }];
 
         let result = synchronize_with_github_issues(&todo_items, &github_issues);
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].text, "Local task");
-        assert_eq!(result[0].is_checked, false);
+        assert!(!result[0].is_checked);
         assert_eq!(result[0].issue_number, None);
         assert_eq!(result[1].text, "Task with issue");
-        assert_eq!(result[1].is_checked, true);
+        assert!(result[1].is_checked);
         assert_eq!(result[1].issue_number, Some(123));
     }
 
@@ -576,11 +758,13 @@ mod tests {
             text: "Same title task".to_string(),
             is_checked: false,
             issue_number: None,
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Same title task".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
 
         let result = synchronize_with_github_issues(&todo_items, &github_issues);
@@ -596,11 +780,13 @@ mod tests {
             text: "  Task with spaces  ".to_string(),
             is_checked: false,
             issue_number: None,
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Task with spaces".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
 
         let result = synchronize_with_github_issues(&todo_items, &github_issues);
@@ -616,18 +802,20 @@ mod tests {
             text: "Task without matching issue".to_string(),
             is_checked: false,
             issue_number: Some(999),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Different issue".to_string(),
             state: IssueState::Closed,
+            closed_at: None,
         }];
 
         let result = synchronize_with_github_issues(&todo_items, &github_issues);
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].text, "Task without matching issue");
-        assert_eq!(result[0].is_checked, false);
+        assert!(!result[0].is_checked);
         assert_eq!(result[0].issue_number, Some(999));
     }
 
@@ -648,16 +836,19 @@ mod tests {
                 text: "To be closed".to_string(),
                 is_checked: false,
                 issue_number: Some(100),
+                ..Default::default()
             },
             TodoItem {
                 text: "Already closed".to_string(),
                 is_checked: true,
                 issue_number: Some(200),
+                ..Default::default()
             },
             TodoItem {
                 text: "Local only task".to_string(),
                 is_checked: false,
                 issue_number: None,
+                ..Default::default()
             },
         ];
         let github_issues = vec![
@@ -665,21 +856,25 @@ mod tests {
                 number: 100,
                 title: "To be closed".to_string(),
                 state: IssueState::Closed,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 200,
                 title: "Already closed".to_string(),
                 state: IssueState::Closed,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 300,
                 title: "New open issue".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 400,
                 title: "Closed new issue".to_string(),
                 state: IssueState::Closed,
+                closed_at: None,
             },
         ];
 
@@ -687,14 +882,14 @@ mod tests {
 
         assert_eq!(result.len(), 4);
         assert_eq!(result[0].text, "To be closed");
-        assert_eq!(result[0].is_checked, true);
+        assert!(result[0].is_checked);
         assert_eq!(result[1].text, "Already closed");
-        assert_eq!(result[1].is_checked, true);
+        assert!(result[1].is_checked);
         assert_eq!(result[2].text, "Local only task");
-        assert_eq!(result[2].is_checked, false);
+        assert!(!result[2].is_checked);
         assert_eq!(result[2].issue_number, None);
         assert_eq!(result[3].text, "New open issue");
-        assert_eq!(result[3].is_checked, false);
+        assert!(!result[3].is_checked);
         assert_eq!(result[3].issue_number, Some(300));
     }
 
@@ -704,11 +899,13 @@ mod tests {
             text: "Old title".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "New title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let past_titles = HashMap::from([(123u64, vec!["Old title".to_string()])]);
 
@@ -727,11 +924,13 @@ mod tests {
             text: "Locally edited title".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Original title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let past_titles = HashMap::from([(123u64, vec![])]);
 
@@ -749,11 +948,13 @@ mod tests {
             text: "Old title".to_string(),
             is_checked: true,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "New title".to_string(),
             state: IssueState::Closed,
+            closed_at: None,
         }];
         let past_titles = HashMap::from([(123u64, vec!["Old title".to_string()])]);
 
@@ -771,17 +972,20 @@ mod tests {
                 text: "Same title".to_string(),
                 is_checked: false,
                 issue_number: Some(123),
+                ..Default::default()
             },
             TodoItem {
                 text: "Local task".to_string(),
                 is_checked: false,
                 issue_number: None,
+                ..Default::default()
             },
         ];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Same title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let past_titles = HashMap::new();
 
@@ -799,11 +1003,13 @@ mod tests {
             text: "  Old title  ".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "New title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let past_titles = HashMap::from([(123u64, vec!["Old title".to_string()])]);
 
@@ -819,16 +1025,19 @@ mod tests {
                 text: "Old title".to_string(),
                 is_checked: false,
                 issue_number: Some(123),
+                ..Default::default()
             },
             TodoItem {
                 text: "Locally edited title".to_string(),
                 is_checked: false,
                 issue_number: Some(456),
+                ..Default::default()
             },
             TodoItem {
                 text: "Same title".to_string(),
                 is_checked: false,
                 issue_number: Some(789),
+                ..Default::default()
             },
         ];
         let github_issues = vec![
@@ -836,16 +1045,19 @@ mod tests {
                 number: 123,
                 title: "New title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 456,
                 title: "Original title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 789,
                 title: "Same title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
         ];
         let events_fetcher = |issue_number: u64| async move {
@@ -874,16 +1086,19 @@ mod tests {
                         text: "New title".to_string(),
                         is_checked: false,
                         issue_number: Some(123),
+                        ..Default::default()
                     },
                     TodoItem {
                         text: "Locally edited title".to_string(),
                         is_checked: false,
                         issue_number: Some(456),
+                        ..Default::default()
                     },
                     TodoItem {
                         text: "Same title".to_string(),
                         is_checked: false,
                         issue_number: Some(789),
+                        ..Default::default()
                     },
                 ],
                 locally_edited_issues: vec![456],
@@ -897,11 +1112,13 @@ mod tests {
             text: "Same title".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Same title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let events_fetcher =
             |_: u64| async move { Err(anyhow::anyhow!("history should not be fetched")) };
@@ -921,11 +1138,13 @@ mod tests {
             text: "Old title".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "New title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let events_fetcher = |_: u64| async move { Err(anyhow::anyhow!("Network error")) };
 
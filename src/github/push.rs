@@ -1,6 +1,7 @@
 use crate::github::issues::{GitHubIssue, IssueState};
 use crate::todo::TodoItem;
 use anyhow::Result;
+use chrono::NaiveDate;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -81,6 +82,44 @@ fn calculate_title_updates(
     }
 }
 
+/// The issue push closes for this item: it is checked and its issue is still open.
+fn issue_to_close(todo: &TodoItem, github_issues: &[GitHubIssue]) -> Option<u64> {
+    let issue_num = todo.issue_number.filter(|_| todo.is_checked)?;
+    github_issues
+        .iter()
+        .find(|issue| issue.number == issue_num)
+        .filter(|issue| issue.state == IssueState::Open)
+        .map(|issue| issue.number)
+}
+
+/// Insert the next occurrence after each recurring item whose issue push will close.
+///
+/// The new items are unchecked and have no issue number, so
+/// `calculate_github_operations` creates their issues. An occurrence that is
+/// already in the list (same text and due date) is not added again.
+pub fn add_recurring_items(
+    todo_items: &[TodoItem],
+    github_issues: &[GitHubIssue],
+    today: NaiveDate,
+) -> Vec<TodoItem> {
+    let mut items = Vec::with_capacity(todo_items.len());
+    for todo in todo_items {
+        items.push(todo.clone());
+        let Some(next) = issue_to_close(todo, github_issues)
+            .and_then(|_| crate::recurrence::next_occurrence(todo, today))
+        else {
+            continue;
+        };
+        let exists = todo_items.iter().any(|item| {
+            !item.is_checked && item.text == next.text && item.metadata.due == next.metadata.due
+        });
+        if !exists {
+            items.push(next);
+        }
+    }
+    items
+}
+
 pub fn calculate_github_operations(
     todo_items: &[TodoItem],
     github_issues: &[GitHubIssue],
@@ -92,15 +131,8 @@ pub fn calculate_github_operations(
                 (false, None) => Some(GitHubOperation::CreateIssue {
                     title: todo.text.clone(),
                 }),
-                (true, Some(issue_num)) => {
-                    match github_issues.iter().find(|issue| issue.number == issue_num) {
-                        Some(github_issue) if github_issue.state == IssueState::Open => {
-                            Some(GitHubOperation::CloseIssue { number: issue_num })
-                        }
-                        _ => None,
-                    }
-                }
-                _ => None,
+                _ => issue_to_close(todo, github_issues)
+                    .map(|number| GitHubOperation::CloseIssue { number }),
             };
             operation.map(|op| (todo.clone(), op))
         })
@@ -142,6 +174,7 @@ mod tests {
             text: "New task".to_string(),
             is_checked: false,
             issue_number: None,
+            ..Default::default()
         }];
         let github_issues = vec![];
 
@@ -163,11 +196,13 @@ mod tests {
             text: "Completed task".to_string(),
             is_checked: true,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Completed task".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
 
         let operations = calculate_github_operations(&todo_items, &github_issues);
@@ -183,11 +218,13 @@ mod tests {
             text: "Already closed task".to_string(),
             is_checked: true,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Already closed task".to_string(),
             state: IssueState::Closed,
+            closed_at: None,
         }];
 
         let operations = calculate_github_operations(&todo_items, &github_issues);
@@ -200,11 +237,13 @@ mod tests {
             text: "Task with missing issue".to_string(),
             is_checked: true,
             issue_number: Some(999),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Different issue".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
 
         let operations = calculate_github_operations(&todo_items, &github_issues);
@@ -218,11 +257,13 @@ mod tests {
             text: "Unchecked with issue".to_string(),
             is_checked: false,
             issue_number: Some(456),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 456,
             title: "Existing issue".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
 
         let operations = calculate_github_operations(&todo_items, &github_issues);
@@ -235,6 +276,7 @@ mod tests {
             text: "Checked but no issue".to_string(),
             is_checked: true,
             issue_number: None,
+            ..Default::default()
         }];
         let github_issues = vec![];
 
@@ -249,6 +291,7 @@ mod tests {
             text: "New task".to_string(),
             is_checked: false,
             issue_number: None,
+            ..Default::default()
         };
         let github_operations = vec![(
             todo_item.clone(),
@@ -277,11 +320,13 @@ mod tests {
             text: "Locally edited title".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Original title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let past_titles = HashMap::from([(123u64, vec![])]);
 
@@ -308,11 +353,13 @@ mod tests {
             text: "Old title".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "New title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let past_titles = HashMap::from([(123u64, vec!["Old title".to_string()])]);
 
@@ -334,16 +381,19 @@ mod tests {
                 text: "Same title".to_string(),
                 is_checked: false,
                 issue_number: Some(123),
+                ..Default::default()
             },
             TodoItem {
                 text: "Edited closed title".to_string(),
                 is_checked: true,
                 issue_number: Some(456),
+                ..Default::default()
             },
             TodoItem {
                 text: "Local task".to_string(),
                 is_checked: false,
                 issue_number: None,
+                ..Default::default()
             },
         ];
         let github_issues = vec![
@@ -351,11 +401,13 @@ mod tests {
                 number: 123,
                 title: "Same title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 456,
                 title: "Closed title".to_string(),
                 state: IssueState::Closed,
+                closed_at: None,
             },
         ];
         let past_titles = HashMap::new();
@@ -377,11 +429,13 @@ mod tests {
             text: "  Edited title  ".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Original title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let past_titles = HashMap::new();
 
@@ -406,11 +460,13 @@ mod tests {
                 text: "Locally edited title".to_string(),
                 is_checked: false,
                 issue_number: Some(123),
+                ..Default::default()
             },
             TodoItem {
                 text: "Old title".to_string(),
                 is_checked: false,
                 issue_number: Some(456),
+                ..Default::default()
             },
         ];
         let github_issues = vec![
@@ -418,11 +474,13 @@ mod tests {
                 number: 123,
                 title: "Original title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 456,
                 title: "New title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
         ];
         let events_fetcher = |issue_number: u64| async move {
@@ -462,6 +520,7 @@ mod tests {
             text: "Edited title".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         };
         let github_operations = vec![(
             todo_item.clone(),
@@ -487,6 +546,7 @@ mod tests {
             text: "Completed task".to_string(),
             is_checked: true,
             issue_number: Some(123),
+            ..Default::default()
         };
         let github_operations = vec![(
             todo_item.clone(),
@@ -506,4 +566,69 @@ mod tests {
         assert_eq!(updates[0].0.text, "Completed task");
         assert_eq!(updates[0].1, None);
     }
+
+    fn recurring_item(is_checked: bool, issue_number: Option<u64>, due: &str) -> TodoItem {
+        TodoItem {
+            text: "Water plants".to_string(),
+            is_checked,
+            issue_number,
+            metadata: crate::task_metadata::TaskMetadata {
+                recurrence: Some("every week".to_string()),
+                due: Some(NaiveDate::parse_from_str(due, "%Y-%m-%d").unwrap()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn issue(number: u64, state: IssueState) -> GitHubIssue {
+        GitHubIssue {
+            number,
+            title: "Water plants".to_string(),
+            state,
+            closed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_add_recurring_items_after_closed_item() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let todo_items = vec![recurring_item(true, Some(5), "2026-10-17")];
+
+        let items = add_recurring_items(&todo_items, &[issue(5, IssueState::Open)], today);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0], todo_items[0]);
+        assert_eq!(items[1], recurring_item(false, None, "2026-10-24"));
+
+        let operations = calculate_github_operations(&items, &[issue(5, IssueState::Open)]);
+        assert_eq!(
+            operations
+                .iter()
+                .map(|(_, op)| op.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                GitHubOperation::CloseIssue { number: 5 },
+                GitHubOperation::CreateIssue {
+                    title: "Water plants".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_recurring_items_skips_closed_issue_and_existing_occurrence() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+
+        let already_closed = vec![recurring_item(true, Some(5), "2026-10-17")];
+        let items = add_recurring_items(&already_closed, &[issue(5, IssueState::Closed)], today);
+        assert_eq!(items, already_closed);
+
+        let already_added = vec![
+            recurring_item(true, Some(5), "2026-10-17"),
+            recurring_item(false, None, "2026-10-24"),
+        ];
+        let items = add_recurring_items(&already_added, &[issue(5, IssueState::Open)], today);
+        assert_eq!(items, already_added);
+    }
 }
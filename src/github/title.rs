@@ -109,11 +109,13 @@ mod tests {
                 text: "Old title".to_string(),
                 is_checked: false,
                 issue_number: Some(123),
+                ..Default::default()
             },
             TodoItem {
                 text: "Same title".to_string(),
                 is_checked: false,
                 issue_number: Some(456),
+                ..Default::default()
             },
         ];
         let github_issues = vec![
@@ -121,11 +123,13 @@ mod tests {
                 number: 123,
                 title: "New title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 456,
                 title: "Same title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
         ];
 
@@ -140,11 +144,13 @@ mod tests {
             text: "Old title".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "New title".to_string(),
             state: IssueState::Closed,
+            closed_at: None,
         }];
 
         let mismatches = find_title_mismatches(&todo_items, &github_issues);
@@ -158,11 +164,13 @@ mod tests {
             text: "Local task".to_string(),
             is_checked: false,
             issue_number: None,
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Unrelated issue".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
 
         let mismatches = find_title_mismatches(&todo_items, &github_issues);
@@ -176,11 +184,13 @@ mod tests {
             text: "  Same title  ".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "Same title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
 
         let mismatches = find_title_mismatches(&todo_items, &github_issues);
@@ -204,11 +214,13 @@ mod tests {
                 text: "Old title".to_string(),
                 is_checked: false,
                 issue_number: Some(123),
+                ..Default::default()
             },
             TodoItem {
                 text: "Same title".to_string(),
                 is_checked: false,
                 issue_number: Some(456),
+                ..Default::default()
             },
         ];
         let github_issues = vec![
@@ -216,11 +228,13 @@ mod tests {
                 number: 123,
                 title: "New title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
             GitHubIssue {
                 number: 456,
                 title: "Same title".to_string(),
                 state: IssueState::Open,
+                closed_at: None,
             },
         ];
         let events_fetcher = |issue_number: u64| async move {
@@ -251,11 +265,13 @@ mod tests {
             text: "Old title".to_string(),
             is_checked: false,
             issue_number: Some(123),
+            ..Default::default()
         }];
         let github_issues = vec![GitHubIssue {
             number: 123,
             title: "New title".to_string(),
             state: IssueState::Open,
+            closed_at: None,
         }];
         let events_fetcher = |_: u64| async move { Err(anyhow::anyhow!("Network error")) };
 
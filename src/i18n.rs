@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Language of the messages atat prints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    Japanese,
+}
+
+/// Values of the `language` setting
+pub const LANGUAGE_CODES: &[&str] = &["en", "ja"];
+
+/// Locale variables, in the order POSIX gives them precedence for messages
+const LOCALE_VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+impl Language {
+    pub fn from_code(code: &str) -> Option<Language> {
+        match code {
+            "en" => Some(Language::English),
+            "ja" => Some(Language::Japanese),
+            _ => None,
+        }
+    }
+
+    /// Language of a locale such as `ja_JP.UTF-8`; English unless it is Japanese
+    fn from_locale(locale: &str) -> Language {
+        if locale.starts_with("ja") {
+            Language::Japanese
+        } else {
+            Language::English
+        }
+    }
+
+    fn catalog(self) -> &'static HashMap<String, String> {
+        static ENGLISH: OnceLock<HashMap<String, String>> = OnceLock::new();
+        static JAPANESE: OnceLock<HashMap<String, String>> = OnceLock::new();
+        let (catalog, source) = match self {
+            Language::English => (&ENGLISH, include_str!("../locales/en.json")),
+            Language::Japanese => (&JAPANESE, include_str!("../locales/ja.json")),
+        };
+        catalog.get_or_init(|| serde_json::from_str(source).expect("invalid message catalog"))
+    }
+}
+
+/// Language from the `language` setting, else from the locale variables
+pub fn detect(setting: Option<&str>, var: impl Fn(&str) -> Option<String>) -> Language {
+    if let Some(language) = setting.and_then(Language::from_code) {
+        return language;
+    }
+    LOCALE_VARS
+        .iter()
+        .filter_map(|name| var(name))
+        .find(|value| !value.is_empty())
+        .map(|locale| Language::from_locale(&locale))
+        .unwrap_or_default()
+}
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+#[cfg(test)]
+thread_local! {
+    /// Language of the current test, which leaves tests on other threads in English
+    static TEST_LANGUAGE: std::cell::Cell<Option<Language>> = const { std::cell::Cell::new(None) };
+}
+
+/// Run `f` with messages in `language` on this thread
+#[cfg(test)]
+pub(crate) fn in_language<T>(language: Language, f: impl FnOnce() -> T) -> T {
+    TEST_LANGUAGE.set(Some(language));
+    let result = f();
+    TEST_LANGUAGE.set(None);
+    result
+}
+
+/// Select the language of messages for the rest of the process
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+/// Language of messages; English until `set_language` is called
+pub fn language() -> Language {
+    #[cfg(test)]
+    if let Some(language) = TEST_LANGUAGE.get() {
+        return language;
+    }
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::Japanese,
+        _ => Language::English,
+    }
+}
+
+/// Message `key` in the current language with `{name}` placeholders filled in.
+///
+/// Messages missing from a catalog fall back to English, then to the key itself.
+pub fn message(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    message_in(language(), key, args)
+}
+
+fn message_in(language: Language, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = language
+        .catalog()
+        .get(key)
+        .or_else(|| Language::English.catalog().get(key))
+        .map_or(key, String::as_str);
+    let mut text = template.to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    text
+}
+
+/// Localised message for a catalog key, with `name = value` arguments for its placeholders
+macro_rules! t {
+    ($key:literal) => {
+        $crate::i18n::message($key, &[])
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::message(
+            $key,
+            &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+],
+        )
+    };
+}
+pub(crate) use t;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// `{name}` placeholders of a message
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_match() {
+        let english = Language::English.catalog();
+        let japanese = Language::Japanese.catalog();
+        let english_keys: BTreeSet<_> = english.keys().collect();
+        let japanese_keys: BTreeSet<_> = japanese.keys().collect();
+        assert_eq!(english_keys, japanese_keys);
+
+        for (key, template) in english {
+            assert_eq!(
+                placeholders(template),
+                placeholders(&japanese[key]),
+                "placeholders of {key}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_used_keys_exist() {
+        fn sources(dir: &std::path::Path, found: &mut Vec<String>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    sources(&path, found);
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    found.push(std::fs::read_to_string(path).unwrap());
+                }
+            }
+        }
+        let mut files = Vec::new();
+        sources(
+            &std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut files,
+        );
+
+        let english = Language::English.catalog();
+        let mut used = 0;
+        for source in &files {
+            for (index, call) in source.match_indices("t!(\"") {
+                // Not the end of another macro such as `format!`
+                let before = source[..index].chars().next_back();
+                if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                let rest = &source[index + call.len()..];
+                let key = rest.split('"').next().unwrap();
+                assert!(english.contains_key(key), "{key} is missing from en.json");
+                used += 1;
+            }
+        }
+        assert!(used > 0);
+    }
+
+    #[test]
+    fn test_message() {
+        assert_eq!(
+            message_in(
+                Language::English,
+                "remote.added",
+                &[("repo", &"owner/repo")]
+            ),
+            "Repository owner/repo has been added"
+        );
+        assert_eq!(
+            message_in(
+                Language::Japanese,
+                "remote.added",
+                &[("repo", &"owner/repo")]
+            ),
+            "リポジトリ owner/repo を追加しました"
+        );
+        assert_eq!(
+            message_in(Language::Japanese, "no.such.key", &[]),
+            "no.such.key"
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(detect(None, env(&[])), Language::English);
+        assert_eq!(
+            detect(None, env(&[("LANG", "ja_JP.UTF-8")])),
+            Language::Japanese
+        );
+        assert_eq!(
+            detect(None, env(&[("LANG", "ja_JP.UTF-8"), ("LC_MESSAGES", "C")])),
+            Language::English
+        );
+        assert_eq!(
+            detect(None, env(&[("LC_ALL", ""), ("LANG", "ja_JP.UTF-8")])),
+            Language::Japanese
+        );
+        assert_eq!(
+            detect(Some("en"), env(&[("LANG", "ja_JP.UTF-8")])),
+            Language::English
+        );
+        assert_eq!(detect(Some("ja"), env(&[])), Language::Japanese);
+    }
+}
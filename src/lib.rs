@@ -1,11 +1,21 @@
 pub mod auth;
+pub mod auth_status;
 pub mod clean;
 pub mod cli;
+pub mod comment_scanner;
 pub mod config;
+pub mod error;
+pub mod git_remote;
 pub mod github;
+pub mod i18n;
+pub mod logging;
 pub mod markdown_parser;
+pub mod org_parser;
 pub mod output;
+pub mod recurrence;
 pub mod run;
 pub mod storage;
+pub mod task_metadata;
 pub mod todo;
+pub mod token_source;
 pub mod whoami;
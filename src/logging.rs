@@ -0,0 +1,239 @@
+use std::time::Instant;
+
+/// Environment variable holding a log filter, e.g. `ATAT_LOG=debug` or `ATAT_LOG=atat=trace`
+pub const LOG_VAR: &str = "ATAT_LOG";
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Start logging on stderr. `-v` logs one line per GitHub API call and `-vv` adds the headers
+/// and bodies, with credentials redacted. Later calls (e.g. from tests) are ignored.
+pub fn init(verbose: u8) {
+    let _ = env_logger::Builder::new()
+        .parse_filters(&filter(verbose, std::env::var(LOG_VAR).ok().as_deref()))
+        .format_target(false)
+        .try_init();
+}
+
+/// Filter for the verbosity given on the command line, falling back to `ATAT_LOG`
+fn filter(verbose: u8, env: Option<&str>) -> String {
+    match (verbose, env) {
+        (0, Some(env)) if !env.trim().is_empty() => env.to_string(),
+        (0, _) => "atat=warn".to_string(),
+        (1, _) => "atat=debug".to_string(),
+        _ => "atat=trace".to_string(),
+    }
+}
+
+/// Send a request, logging it and the response
+pub async fn send(request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+    let (client, request) = request.build_split();
+    let request = request?;
+    let method = request.method().clone();
+    let url = redact_url(request.url());
+
+    if log::log_enabled!(log::Level::Trace) {
+        for (name, value) in request.headers() {
+            log::trace!("> {name}: {}", redact_header(name, value));
+        }
+        if let Some(body) = request.body().and_then(reqwest::Body::as_bytes) {
+            log::trace!("> {}", redact_body(&String::from_utf8_lossy(body)));
+        }
+    }
+
+    let started = Instant::now();
+    let response = match client.execute(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::debug!("{method} {url} failed after {:?}: {e}", started.elapsed());
+            return Err(e);
+        }
+    };
+    log::debug!(
+        "{method} {url} -> {} in {}ms{}",
+        response.status(),
+        started.elapsed().as_millis(),
+        rate_limit(response.headers())
+    );
+
+    if !log::log_enabled!(log::Level::Trace) {
+        return Ok(response);
+    }
+    for (name, value) in response.headers() {
+        log::trace!("< {name}: {}", redact_header(name, value));
+    }
+
+    // The body can only be read once, so the response is rebuilt around it
+    let status = response.status();
+    let version = response.version();
+    let headers = response.headers().clone();
+    let body = response.bytes().await?;
+    log::trace!("< {}", redact_body(&String::from_utf8_lossy(&body)));
+
+    let mut rebuilt = http::Response::new(body);
+    *rebuilt.status_mut() = status;
+    *rebuilt.version_mut() = version;
+    *rebuilt.headers_mut() = headers;
+    Ok(reqwest::Response::from(rebuilt))
+}
+
+/// Rate limit GitHub reports in the `x-ratelimit-*` headers, e.g. ` (rate limit 4999/5000, resets 12:00:00)`
+fn rate_limit(headers: &reqwest::header::HeaderMap) -> String {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let (Some(remaining), Some(limit)) =
+        (header("x-ratelimit-remaining"), header("x-ratelimit-limit"))
+    else {
+        return String::new();
+    };
+    let reset = header("x-ratelimit-reset")
+        .and_then(|reset| reset.parse().ok())
+        .and_then(|reset| chrono::DateTime::from_timestamp(reset, 0))
+        .map(|reset| {
+            format!(
+                ", resets {}",
+                reset.with_timezone(&chrono::Local).format("%H:%M:%S")
+            )
+        })
+        .unwrap_or_default();
+    format!(" (rate limit {remaining}/{limit}{reset})")
+}
+
+/// Whether a field, query parameter or header holds a credential
+fn is_sensitive(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.contains("token")
+        || name.contains("secret")
+        || name.contains("password")
+        || name == "device_code"
+        || name.ends_with("authorization")
+        || name == "cookie"
+        || name == "set-cookie"
+}
+
+fn redact_header(
+    name: &reqwest::header::HeaderName,
+    value: &reqwest::header::HeaderValue,
+) -> String {
+    if is_sensitive(name.as_str()) {
+        REDACTED.to_string()
+    } else {
+        String::from_utf8_lossy(value.as_bytes()).into_owned()
+    }
+}
+
+fn redact_url(url: &reqwest::Url) -> String {
+    if url.query().is_none() {
+        return url.to_string();
+    }
+    let mut redacted = url.clone();
+    redacted
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(url.query_pairs().map(|(name, value)| {
+            let value = if is_sensitive(&name) {
+                REDACTED.into()
+            } else {
+                value
+            };
+            (name, value)
+        }));
+    redacted.to_string()
+}
+
+/// Body with credentials replaced, for JSON and form-encoded bodies
+fn redact_body(body: &str) -> String {
+    if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(body) {
+        redact_json(&mut value);
+        return value.to_string();
+    }
+    if body.contains('=') && !body.contains(char::is_whitespace) {
+        return body
+            .split('&')
+            .map(|pair| match pair.split_once('=') {
+                Some((name, _)) if is_sensitive(name) => format!("{name}={REDACTED}"),
+                _ => pair.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+    }
+    body.to_string()
+}
+
+fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (name, value) in object {
+                if is_sensitive(name) && !value.is_null() {
+                    *value = REDACTED.into();
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        assert_eq!(filter(0, None), "atat=warn");
+        assert_eq!(filter(0, Some("")), "atat=warn");
+        assert_eq!(filter(0, Some("atat=trace")), "atat=trace");
+        assert_eq!(filter(1, Some("off")), "atat=debug");
+        assert_eq!(filter(2, None), "atat=trace");
+        assert_eq!(filter(5, None), "atat=trace");
+    }
+
+    #[test]
+    fn test_redact_url() {
+        let url = reqwest::Url::parse(
+            "https://github.com/login/oauth/access_token?client_id=abc&refresh_token=ghr_x&client_secret=s",
+        )
+        .unwrap();
+        assert_eq!(
+            redact_url(&url),
+            "https://github.com/login/oauth/access_token?client_id=abc&refresh_token=%5BREDACTED%5D&client_secret=%5BREDACTED%5D"
+        );
+
+        let url = reqwest::Url::parse("https://api.github.com/user").unwrap();
+        assert_eq!(redact_url(&url), "https://api.github.com/user");
+    }
+
+    #[test]
+    fn test_redact_header() {
+        let name = reqwest::header::AUTHORIZATION;
+        let value = reqwest::header::HeaderValue::from_static("Bearer ghu_secret");
+        assert_eq!(redact_header(&name, &value), REDACTED);
+
+        let name = reqwest::header::PROXY_AUTHORIZATION;
+        let value = reqwest::header::HeaderValue::from_static("Basic dXNlcjpwYXNz");
+        assert_eq!(redact_header(&name, &value), REDACTED);
+
+        let name = reqwest::header::ACCEPT;
+        let value = reqwest::header::HeaderValue::from_static("application/json");
+        assert_eq!(redact_header(&name, &value), "application/json");
+    }
+
+    #[test]
+    fn test_redact_body() {
+        assert_eq!(
+            redact_body(
+                r#"{"access_token":"ghu_x","refresh_token":"ghr_y","token_type":"bearer","scope":""}"#
+            ),
+            r#"{"access_token":"[REDACTED]","refresh_token":"[REDACTED]","scope":"","token_type":"[REDACTED]"}"#
+        );
+        assert_eq!(
+            redact_body(r#"[{"title":"Write docs","user":{"login":"me"}}]"#),
+            r#"[{"title":"Write docs","user":{"login":"me"}}]"#
+        );
+        assert_eq!(
+            redact_body("client_id=abc&device_code=xyz&grant_type=device"),
+            "client_id=abc&device_code=[REDACTED]&grant_type=device"
+        );
+        assert_eq!(redact_body("Not Found"), "Not Found");
+    }
+}
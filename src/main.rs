@@ -1,5 +1,13 @@
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() {
+    // Answer completion requests from the scripts of `atat completions` and exit
+    clap_complete::CompleteEnv::with_factory(atat::cli::parser::command)
+        .var(atat::cli::completion::COMPLETE_VAR)
+        .complete();
+
     let args: Vec<String> = std::env::args().collect();
-    atat::run::run(args, None, None).await
+    // `run` prints the error with its hint
+    if atat::run::run(args, None, None).await.is_err() {
+        std::process::exit(1);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::task_metadata;
 use crate::todo::TodoItem;
 use anyhow::Result;
 use pulldown_cmark::{Event, Options, Parser};
@@ -23,7 +24,8 @@ pub fn parse_todo_markdown(content: &str) -> Result<Vec<TodoItem>> {
                 if pending_checked.is_some() && !text_buffer.is_empty() =>
             {
                 let is_checked = pending_checked.unwrap();
-                let text_str = text_buffer.trim();
+                let (text, metadata) = task_metadata::split_task_metadata(text_buffer.trim());
+                let text_str = text.as_str();
 
                 let (clean_text, issue_number) = text_str
                     .rfind(" (#")
@@ -42,13 +44,16 @@ pub fn parse_todo_markdown(content: &str) -> Result<Vec<TodoItem>> {
                     text: clean_text,
                     is_checked,
                     issue_number,
+                    metadata,
+                    ..Default::default()
                 });
 
                 (items, None, String::new())
             }
             Event::End(pulldown_cmark::TagEnd::Item) if !text_buffer.is_empty() => {
                 if let Some(is_checked) = pending_checked {
-                    let text_str = text_buffer.trim();
+                    let (text, metadata) = task_metadata::split_task_metadata(text_buffer.trim());
+                    let text_str = text.as_str();
 
                     let (clean_text, issue_number) = text_str
                         .rfind(" (#")
@@ -67,6 +72,8 @@ pub fn parse_todo_markdown(content: &str) -> Result<Vec<TodoItem>> {
                         text: clean_text,
                         is_checked,
                         issue_number,
+                        metadata,
+                        ..Default::default()
                     });
                 }
 
@@ -82,18 +89,188 @@ pub fn parse_todo_markdown(content: &str) -> Result<Vec<TodoItem>> {
 pub fn serialize_todo_markdown(items: &[TodoItem]) -> String {
     items
         .iter()
-        .map(|item| {
-            let checkbox = if item.is_checked { "[x]" } else { "[ ]" };
-            let text = if let Some(issue_number) = item.issue_number {
-                format!("{} (#{issue_number})", item.text)
+        .map(|item| format!("{}\n", render_item(item)))
+        .collect()
+}
+
+/// Write `items` back into `original`, keeping headings and other text.
+///
+/// Task lines are matched to items by issue number, or by text when the line
+/// has none. Lines whose item is gone are removed. Items without a line go
+/// below the line of the item before them, or after the last task (at the end
+/// of the file without one).
+pub fn update_todo_markdown(original: &str, items: &[TodoItem]) -> String {
+    if original.trim().is_empty() {
+        return serialize_todo_markdown(items);
+    }
+    let lines: Vec<&str> = original.lines().collect();
+    let mut used = vec![false; items.len()];
+    let mut after_item = vec![None; items.len()];
+    let mut output: Vec<String> = Vec::new();
+    let mut after_last_task = None;
+    let mut tasks = task_lines(original).into_iter().peekable();
+
+    for (index, line) in lines.iter().enumerate() {
+        let Some((_, column)) = tasks.next_if(|(task_index, _)| *task_index == index) else {
+            output.push(line.to_string());
+            continue;
+        };
+        let (prefix, task) = line.split_at(column);
+        let Some(parsed) = parse_todo_markdown(&format!("- {task}"))
+            .ok()
+            .and_then(|parsed| parsed.into_iter().next())
+        else {
+            output.push(line.to_string());
+            continue;
+        };
+
+        let matched = (0..items.len()).find(|&i| {
+            !used[i]
+                && match parsed.issue_number {
+                    Some(number) => items[i].issue_number == Some(number),
+                    None => items[i].text == parsed.text,
+                }
+        });
+        if let Some(i) = matched {
+            used[i] = true;
+            if render_task(&items[i]) == render_task(&parsed) {
+                output.push(line.to_string());
             } else {
-                item.text.clone()
-            };
-            format!("- {checkbox} {text}\n")
+                output.push(format!("{prefix}{}", render_task(&items[i])));
+            }
+            after_item[i] = Some(output.len());
+            after_last_task = Some(output.len());
+        }
+    }
+
+    let fallback = after_last_task.unwrap_or(output.len());
+    let mut new_lines: Vec<(usize, String)> = (0..items.len())
+        .filter(|&i| !used[i])
+        .map(|i| {
+            let at = after_item[..i].iter().rev().find_map(|at| *at);
+            (at.unwrap_or(fallback), render_item(&items[i]))
+        })
+        .collect();
+    new_lines.sort_by_key(|(at, _)| *at);
+    for (at, line) in new_lines.into_iter().rev() {
+        output.insert(at, line);
+    }
+
+    if output.is_empty() {
+        return String::new();
+    }
+    let mut content = output.join("\n");
+    content.push('\n');
+    content
+}
+
+/// Line index and checkbox column of each task list item in `content`
+fn task_lines(content: &str) -> Vec<(usize, usize)> {
+    Parser::new_ext(content, Options::ENABLE_TASKLISTS)
+        .into_offset_iter()
+        .filter(|(event, _)| matches!(event, Event::TaskListMarker(_)))
+        .map(|(_, range)| {
+            let line_start = content[..range.start].rfind('\n').map_or(0, |i| i + 1);
+            let index = content[..range.start].matches('\n').count();
+            (index, range.start - line_start)
         })
         .collect()
 }
 
+fn render_item(item: &TodoItem) -> String {
+    format!("- {}", render_task(item))
+}
+
+/// Checkbox, text, issue number and metadata of a task, without the list marker
+fn render_task(item: &TodoItem) -> String {
+    let checkbox = if item.is_checked { "[x]" } else { "[ ]" };
+    let text = if let Some(issue_number) = item.issue_number {
+        format!("{} (#{issue_number})", item.text)
+    } else {
+        item.text.clone()
+    };
+    let metadata = task_metadata::format_task_metadata(&item.metadata);
+    format!("{checkbox} {text}{metadata}")
+}
+
+/// Level and text of an ATX heading such as `## Bugs ##`
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    // A closing sequence of `#` is not part of the text
+    let title = rest.trim();
+    let unclosed = title.trim_end_matches('#');
+    if unclosed.is_empty() || unclosed.ends_with([' ', '\t']) {
+        Some((level, unclosed.trim_end()))
+    } else {
+        Some((level, title))
+    }
+}
+
+/// Add `item` to `content` without rewriting the rest of the file.
+///
+/// With a `section`, the item goes after the last line of the first heading
+/// with that text; a missing heading is added at the end of the file.
+pub fn insert_todo_markdown(content: &str, section: Option<&str>, item: &TodoItem) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let line = render_item(item);
+
+    let mut in_code_block = false;
+    let headings: Vec<(usize, usize, &str)> = content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+                in_code_block = !in_code_block;
+            }
+            if in_code_block {
+                return None;
+            }
+            heading(line).map(|(level, title)| (index, level, title))
+        })
+        .collect();
+
+    let found = section.and_then(|section| {
+        let position = headings
+            .iter()
+            .position(|(_, _, title)| *title == section)?;
+        let (start, level, _) = headings[position];
+        let end = headings[position + 1..]
+            .iter()
+            .find(|(_, next_level, _)| *next_level <= level)
+            .map_or(lines.len(), |(index, _, _)| *index);
+        let last = (start..end)
+            .rev()
+            .find(|&index| !lines[index].trim().is_empty())
+            .unwrap_or(start);
+        Some(last + 1)
+    });
+
+    match (found, section) {
+        (Some(index), _) => lines.insert(index, line),
+        (None, Some(section)) => {
+            if lines.last().is_some_and(|last| !last.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.push(format!("## {section}"));
+            lines.push(String::new());
+            lines.push(line);
+        }
+        (None, None) => lines.push(line),
+    }
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -284,21 +461,25 @@ code block
                 text: "Unchecked task".to_string(),
                 is_checked: false,
                 issue_number: None,
+                ..Default::default()
             },
             TodoItem {
                 text: "Checked task".to_string(),
                 is_checked: true,
                 issue_number: None,
+                ..Default::default()
             },
             TodoItem {
                 text: "Task with issue".to_string(),
                 is_checked: false,
                 issue_number: Some(123),
+                ..Default::default()
             },
             TodoItem {
                 text: "Checked task with issue".to_string(),
                 is_checked: true,
                 issue_number: Some(456),
+                ..Default::default()
             },
         ];
 
@@ -315,6 +496,44 @@ code block
         assert_eq!(actual, "");
     }
 
+    #[test]
+    fn test_obsidian_tasks_metadata() {
+        let content = r#"- [ ] Rotate credentials ⏫ 🔁 every week 📅 2026-10-20
+- [x] Ship release (#12) ✅ 2026-10-10
+- [ ] Task with emoji 🚀"#;
+
+        let items = parse_todo_markdown(content).unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].text, "Rotate credentials");
+        assert_eq!(
+            items[0].metadata.priority,
+            Some(task_metadata::Priority::High)
+        );
+        assert_eq!(items[0].metadata.recurrence.as_deref(), Some("every week"));
+        assert_eq!(
+            items[0].metadata.due.map(|d| d.to_string()).as_deref(),
+            Some("2026-10-20")
+        );
+        assert_eq!(items[1].text, "Ship release");
+        assert_eq!(items[1].issue_number, Some(12));
+        assert_eq!(
+            items[1].metadata.done.map(|d| d.to_string()).as_deref(),
+            Some("2026-10-10")
+        );
+        assert_eq!(items[2].text, "Task with emoji 🚀");
+        assert!(items[2].metadata.is_empty());
+    }
+
+    #[test]
+    fn test_serialize_obsidian_tasks_roundtrip() {
+        let original_content = "- [ ] Rotate credentials (#3) ⏫ 🔁 every week 📅 2026-10-20\n- [x] Done task ✅ 2026-10-10\n";
+        let parsed_items = parse_todo_markdown(original_content).unwrap();
+        let serialized = serialize_todo_markdown(&parsed_items);
+
+        assert_eq!(serialized, original_content);
+    }
+
     #[test]
     fn test_serialize_roundtrip() {
         let original_content = "- [ ] Task 1\n- [x] Task 2 (#123)\n- [ ] Task 3\n";
@@ -323,4 +542,87 @@ code block
 
         assert_eq!(serialized, original_content);
     }
+
+    #[test]
+    fn test_update_keeps_other_lines() {
+        let original = "# Tasks\n\n## Bugs\n\n* [ ] Crash on start (#1)\n  - [ ] Nested\n\nSome notes.\n\n```\n- [ ] Not a task\n```\n";
+        let mut items = parse_todo_markdown(original).unwrap();
+        items[0].is_checked = true;
+        items[1].issue_number = Some(2);
+
+        assert_eq!(
+            update_todo_markdown(original, &items),
+            "# Tasks\n\n## Bugs\n\n* [x] Crash on start (#1)\n  - [ ] Nested (#2)\n\nSome notes.\n\n```\n- [ ] Not a task\n```\n"
+        );
+        assert_eq!(
+            update_todo_markdown(original, &parse_todo_markdown(original).unwrap()),
+            original
+        );
+    }
+
+    #[test]
+    fn test_update_removes_and_adds_items() {
+        let original =
+            "## Bugs\n- [x] Old (#1)\n- [ ] Weekly (#2) 🔁 every week\n\n## Notes\nText\n";
+        let mut items = parse_todo_markdown(original).unwrap();
+        items.remove(0);
+        items.push(TodoItem {
+            text: "Next".to_string(),
+            ..Default::default()
+        });
+        items.insert(
+            1,
+            TodoItem {
+                text: "Weekly".to_string(),
+                issue_number: Some(3),
+                metadata: items[0].metadata.clone(),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            update_todo_markdown(original, &items),
+            "## Bugs\n- [ ] Weekly (#2) 🔁 every week\n- [ ] Weekly (#3) 🔁 every week\n- [ ] Next\n\n## Notes\nText\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_todo_markdown() {
+        let content = "# TODO\n\n## Bugs\n\n- [ ] Crash (#1)\n\n## Docs ##\n\n- [ ] Intro\n";
+        let mut item = parse_todo_markdown("- [ ] Fix login").unwrap().remove(0);
+
+        assert_eq!(
+            insert_todo_markdown(content, Some("Bugs"), &item),
+            "# TODO\n\n## Bugs\n\n- [ ] Crash (#1)\n- [ ] Fix login\n\n## Docs ##\n\n- [ ] Intro\n"
+        );
+        assert_eq!(
+            insert_todo_markdown(content, Some("Docs"), &item),
+            format!("{content}- [ ] Fix login\n")
+        );
+
+        item.issue_number = Some(7);
+        assert_eq!(
+            insert_todo_markdown(content, Some("Ideas"), &item),
+            format!("{content}\n## Ideas\n\n- [ ] Fix login (#7)\n")
+        );
+        assert_eq!(
+            insert_todo_markdown("- [ ] Task", None, &item),
+            "- [ ] Task\n- [ ] Fix login (#7)\n"
+        );
+        assert_eq!(
+            insert_todo_markdown("", None, &item),
+            "- [ ] Fix login (#7)\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_ignores_headings_in_code_blocks() {
+        let content = "```sh\n# Bugs\n```\n# Bugs\n";
+        let item = parse_todo_markdown("- [ ] Fix login").unwrap().remove(0);
+
+        assert_eq!(
+            insert_todo_markdown(content, Some("Bugs"), &item),
+            "```sh\n# Bugs\n```\n# Bugs\n- [ ] Fix login\n"
+        );
+    }
 }
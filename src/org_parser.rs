@@ -0,0 +1,758 @@
+use crate::todo::TodoItem;
+use anyhow::Result;
+
+/// Property holding the GitHub issue number of a headline
+const ISSUE_PROPERTY: &str = "ISSUE";
+/// In-buffer settings that declare TODO keyword sequences
+const KEYWORD_SETTINGS: &[&str] = &["#+TODO:", "#+SEQ_TODO:", "#+TYP_TODO:"];
+const PLANNING_KEYWORDS: &[&str] = &["SCHEDULED:", "DEADLINE:", "CLOSED:"];
+
+/// TODO keywords recognised in a file, split into open and finished states
+#[derive(Debug, Clone, PartialEq)]
+struct TodoKeywords {
+    active: Vec<String>,
+    done: Vec<String>,
+}
+
+impl TodoKeywords {
+    /// Collect keywords from `#+TODO:` lines, falling back to org-mode's `TODO | DONE`.
+    fn from_lines(lines: &[&str]) -> Self {
+        let declared = lines
+            .iter()
+            .filter_map(|line| {
+                let line = line.trim_start();
+                KEYWORD_SETTINGS.iter().find_map(|setting| {
+                    line.get(..setting.len())
+                        .filter(|prefix| prefix.eq_ignore_ascii_case(setting))
+                        .map(|_| &line[setting.len()..])
+                })
+            })
+            .map(parse_keyword_sequence)
+            .fold(None, |acc: Option<TodoKeywords>, keywords| match acc {
+                None => Some(keywords),
+                Some(mut acc) => {
+                    acc.active.extend(keywords.active);
+                    acc.done.extend(keywords.done);
+                    Some(acc)
+                }
+            });
+
+        declared.unwrap_or_else(|| TodoKeywords {
+            active: vec!["TODO".to_string()],
+            done: vec!["DONE".to_string()],
+        })
+    }
+
+    fn is_done(&self, keyword: &str) -> Option<bool> {
+        if self.active.iter().any(|k| k == keyword) {
+            Some(false)
+        } else if self.done.iter().any(|k| k == keyword) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    fn keyword_for(&self, is_checked: bool) -> &str {
+        if is_checked {
+            &self.done[0]
+        } else {
+            &self.active[0]
+        }
+    }
+}
+
+/// Parse `TODO NEXT | DONE CANCELLED(c)`. Without `|` the last keyword is the done state.
+fn parse_keyword_sequence(sequence: &str) -> TodoKeywords {
+    let words: Vec<String> = sequence
+        .split_whitespace()
+        .map(|word| match word.find('(') {
+            Some(pos) if word.ends_with(')') => word[..pos].to_string(),
+            _ => word.to_string(),
+        })
+        .collect();
+
+    let (active, done) = match words.iter().position(|word| word == "|") {
+        Some(pos) => (words[..pos].to_vec(), words[pos + 1..].to_vec()),
+        None if words.len() > 1 => (
+            words[..words.len() - 1].to_vec(),
+            words[words.len() - 1..].to_vec(),
+        ),
+        None => (words, Vec::new()),
+    };
+
+    TodoKeywords {
+        active: if active.is_empty() {
+            vec!["TODO".to_string()]
+        } else {
+            active
+        },
+        done: if done.is_empty() {
+            vec!["DONE".to_string()]
+        } else {
+            done
+        },
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Headline {
+    level: usize,
+    keyword: String,
+    priority: Option<String>,
+    title: String,
+    tags: Vec<String>,
+}
+
+/// Number of leading stars if `line` is an org headline
+fn headline_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|&c| c == '*').count();
+    (level > 0 && line[level..].starts_with([' ', '\t'])).then_some(level)
+}
+
+fn parse_tags(word: &str) -> Option<Vec<String>> {
+    let inner = word.strip_prefix(':')?.strip_suffix(':')?;
+    let valid = !inner.is_empty()
+        && inner
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%' | ':'));
+    valid.then(|| {
+        inner
+            .split(':')
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+}
+
+fn parse_headline(line: &str, keywords: &TodoKeywords) -> Option<Headline> {
+    let level = headline_level(line)?;
+    let rest = line[level..].trim();
+
+    let (keyword, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    keywords.is_done(keyword)?;
+    let mut rest = rest.trim();
+
+    let priority = rest
+        .strip_prefix("[#")
+        .and_then(|after| after.split_once(']'))
+        .map(|(cookie, after)| {
+            rest = after.trim_start();
+            cookie.to_string()
+        });
+
+    let (title, tags) = match rest.rsplit_once(char::is_whitespace) {
+        Some((title, last)) => match parse_tags(last) {
+            Some(tags) => (title.trim_end(), tags),
+            None => (rest, Vec::new()),
+        },
+        None => match parse_tags(rest) {
+            Some(tags) => ("", tags),
+            None => (rest, Vec::new()),
+        },
+    };
+
+    Some(Headline {
+        level,
+        keyword: keyword.to_string(),
+        priority,
+        title: title.to_string(),
+        tags,
+    })
+}
+
+fn render_headline(headline: &Headline) -> String {
+    let mut line = format!("{} {}", "*".repeat(headline.level), headline.keyword);
+    if let Some(priority) = &headline.priority {
+        line.push_str(&format!(" [#{priority}]"));
+    }
+    if !headline.title.is_empty() {
+        line.push(' ');
+        line.push_str(&headline.title);
+    }
+    if !headline.tags.is_empty() {
+        line.push_str(&format!(" :{}:", headline.tags.join(":")));
+    }
+    line
+}
+
+/// A TODO headline together with the lines up to the next headline
+#[derive(Debug, Clone, PartialEq)]
+struct OrgEntry {
+    start: usize,
+    end: usize,
+    headline: Headline,
+    is_done: bool,
+    issue_number: Option<u64>,
+    drawer: Option<PropertyDrawer>,
+}
+
+/// Line positions of a property drawer, relative to the whole file
+#[derive(Debug, Clone, PartialEq)]
+struct PropertyDrawer {
+    issue_line: Option<usize>,
+    end_line: usize,
+}
+
+fn is_planning_line(line: &str) -> bool {
+    let line = line.trim_start();
+    PLANNING_KEYWORDS.iter().any(|k| line.starts_with(k))
+}
+
+fn property_value<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let rest = line.trim().strip_prefix(':')?;
+    let (key, value) = rest.split_once(':')?;
+    key.eq_ignore_ascii_case(name).then_some(value.trim())
+}
+
+fn parse_issue_number(value: &str) -> Option<u64> {
+    value.trim_start_matches('#').parse().ok()
+}
+
+/// Find the property drawer that follows a headline (and its optional planning line).
+fn find_property_drawer(lines: &[&str], start: usize, end: usize) -> Option<PropertyDrawer> {
+    let mut index = start + 1;
+    if index < end && is_planning_line(lines[index]) {
+        index += 1;
+    }
+    if index >= end || !lines[index].trim().eq_ignore_ascii_case(":PROPERTIES:") {
+        return None;
+    }
+
+    let mut issue_line = None;
+    for (offset, line) in lines[index + 1..end].iter().enumerate() {
+        let line_index = index + 1 + offset;
+        if line.trim().eq_ignore_ascii_case(":END:") {
+            return Some(PropertyDrawer {
+                issue_line,
+                end_line: line_index,
+            });
+        }
+        if issue_line.is_none() && property_value(line, ISSUE_PROPERTY).is_some() {
+            issue_line = Some(line_index);
+        }
+    }
+    None
+}
+
+fn parse_entries(lines: &[&str], keywords: &TodoKeywords) -> Vec<OrgEntry> {
+    let headline_starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| headline_level(line).is_some())
+        .map(|(index, _)| index)
+        .collect();
+
+    headline_starts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &start)| {
+            let end = headline_starts.get(i + 1).copied().unwrap_or(lines.len());
+            let headline = parse_headline(lines[start], keywords)?;
+            let is_done = keywords.is_done(&headline.keyword)?;
+            let drawer = find_property_drawer(lines, start, end);
+            let issue_number = drawer
+                .as_ref()
+                .and_then(|drawer| drawer.issue_line)
+                .and_then(|line| property_value(lines[line], ISSUE_PROPERTY))
+                .and_then(parse_issue_number);
+
+            Some(OrgEntry {
+                start,
+                end,
+                headline,
+                is_done,
+                issue_number,
+                drawer,
+            })
+        })
+        .collect()
+}
+
+pub fn parse_todo_org(content: &str) -> Result<Vec<TodoItem>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let keywords = TodoKeywords::from_lines(&lines);
+
+    Ok(parse_entries(&lines, &keywords)
+        .into_iter()
+        .map(|entry| TodoItem {
+            text: entry.headline.title,
+            is_checked: entry.is_done,
+            issue_number: entry.issue_number,
+            labels: entry.headline.tags,
+            ..Default::default()
+        })
+        .collect())
+}
+
+impl OrgEntry {
+    /// Whether `item` is the (possibly updated) item parsed from this entry
+    fn corresponds_to(&self, item: &TodoItem) -> bool {
+        match self.issue_number {
+            Some(number) => item.issue_number == Some(number),
+            None => item.text == self.headline.title,
+        }
+    }
+
+    /// Body lines the user wrote under the headline, kept when the task itself is removed.
+    ///
+    /// The headline, planning line and property drawer go with the task.
+    fn notes<'a>(&self, lines: &'a [&'a str]) -> &'a [&'a str] {
+        let body_start = match &self.drawer {
+            Some(drawer) => drawer.end_line + 1,
+            None if self.start + 1 < self.end && is_planning_line(lines[self.start + 1]) => {
+                self.start + 2
+            }
+            None => self.start + 1,
+        };
+        let body = &lines[body_start..self.end];
+        if body.iter().all(|line| line.trim().is_empty()) {
+            &[]
+        } else {
+            body
+        }
+    }
+
+    fn render(&self, lines: &[&str], item: &TodoItem, keywords: &TodoKeywords) -> Vec<String> {
+        let mut rendered: Vec<String> = lines[self.start..self.end]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        if item.is_checked != self.is_done
+            || item.text != self.headline.title
+            || item.labels != self.headline.tags
+        {
+            let keyword = if item.is_checked == self.is_done {
+                self.headline.keyword.clone()
+            } else {
+                keywords.keyword_for(item.is_checked).to_string()
+            };
+            rendered[0] = render_headline(&Headline {
+                keyword,
+                title: item.text.clone(),
+                tags: item.labels.clone(),
+                ..self.headline.clone()
+            });
+        }
+
+        if let Some(number) = item
+            .issue_number
+            .filter(|_| item.issue_number != self.issue_number)
+        {
+            match &self.drawer {
+                Some(drawer) => {
+                    let end_line = lines[drawer.end_line];
+                    let indent = &end_line[..end_line.len() - end_line.trim_start().len()];
+                    let property = format!("{indent}:{ISSUE_PROPERTY}: {number}");
+                    match drawer.issue_line {
+                        Some(line) => rendered[line - self.start] = property,
+                        None => rendered.insert(drawer.end_line - self.start, property),
+                    }
+                }
+                None => {
+                    let at = if rendered.len() > 1 && is_planning_line(&rendered[1]) {
+                        2
+                    } else {
+                        1
+                    };
+                    rendered.splice(at..at, property_drawer(number));
+                }
+            }
+        }
+
+        rendered
+    }
+}
+
+fn property_drawer(issue_number: u64) -> Vec<String> {
+    vec![
+        ":PROPERTIES:".to_string(),
+        format!(":{ISSUE_PROPERTY}: {issue_number}"),
+        ":END:".to_string(),
+    ]
+}
+
+/// Headline and property drawer of an item that is not in the file yet
+fn render_new_item(item: &TodoItem, level: usize, keywords: &TodoKeywords) -> Vec<String> {
+    let mut lines = vec![render_headline(&Headline {
+        level,
+        keyword: keywords.keyword_for(item.is_checked).to_string(),
+        priority: None,
+        title: item.text.clone(),
+        tags: item.labels.clone(),
+    })];
+    if let Some(number) = item.issue_number {
+        lines.extend(property_drawer(number));
+    }
+    lines
+}
+
+/// Level and title of any headline, with or without a TODO keyword
+fn section_title(line: &str) -> Option<(usize, &str)> {
+    let level = headline_level(line)?;
+    let rest = line[level..].trim();
+    let title = match rest.rsplit_once(char::is_whitespace) {
+        Some((title, last)) if parse_tags(last).is_some() => title.trim_end(),
+        _ => rest,
+    };
+    Some((level, title))
+}
+
+/// Add `item` to `original` without touching the rest of the file.
+///
+/// With a `section`, the item becomes the last child of the first headline
+/// with that title; a missing headline is added at the end of the file.
+pub fn insert_todo_org(original: &str, section: Option<&str>, item: &TodoItem) -> String {
+    let lines: Vec<&str> = original.lines().collect();
+    let keywords = TodoKeywords::from_lines(&lines);
+    let mut output: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+
+    let found = section.and_then(|section| {
+        let (start, level) = lines.iter().enumerate().find_map(|(index, line)| {
+            section_title(line)
+                .filter(|(_, title)| *title == section)
+                .map(|(level, _)| (index, level))
+        })?;
+        let end = lines[start + 1..]
+            .iter()
+            .position(|line| headline_level(line).is_some_and(|next| next <= level))
+            .map_or(lines.len(), |offset| start + 1 + offset);
+        let last = (start..end)
+            .rev()
+            .find(|&index| !lines[index].trim().is_empty())
+            .unwrap_or(start);
+        Some((last + 1, level + 1))
+    });
+
+    match (found, section) {
+        (Some((index, level)), _) => {
+            output.splice(index..index, render_new_item(item, level, &keywords));
+        }
+        (None, Some(section)) => {
+            output.push(format!("* {section}"));
+            output.extend(render_new_item(item, 2, &keywords));
+        }
+        (None, None) => output.extend(render_new_item(item, 1, &keywords)),
+    }
+
+    let mut content = output.join("\n");
+    content.push('\n');
+    content
+}
+
+/// Write `items` back into `original`, leaving all other content untouched.
+///
+/// Entries are matched to items by `:ISSUE:` or, without one, by title. An
+/// entry whose item is missing loses its headline, planning line and property
+/// drawer but keeps its notes, and items matching no entry are appended as new
+/// top-level headlines.
+pub fn serialize_todo_org(original: &str, items: &[TodoItem]) -> String {
+    let lines: Vec<&str> = original.lines().collect();
+    let keywords = TodoKeywords::from_lines(&lines);
+    let entries = parse_entries(&lines, &keywords);
+
+    let mut output: Vec<String> = Vec::new();
+    let mut used = vec![false; items.len()];
+    let mut next_line = 0;
+
+    for entry in &entries {
+        output.extend(lines[next_line..entry.start].iter().map(|l| l.to_string()));
+        next_line = entry.end;
+
+        let matched = (0..items.len()).find(|&i| !used[i] && entry.corresponds_to(&items[i]));
+        match matched {
+            Some(i) => {
+                used[i] = true;
+                output.extend(entry.render(&lines, &items[i], &keywords));
+            }
+            None => output.extend(entry.notes(&lines).iter().map(|l| l.to_string())),
+        }
+    }
+    output.extend(lines[next_line..].iter().map(|l| l.to_string()));
+
+    for (item, _) in items.iter().zip(&used).filter(|(_, used)| !**used) {
+        output.extend(render_new_item(item, 1, &keywords));
+    }
+
+    if output.is_empty() {
+        return String::new();
+    }
+    let mut content = output.join("\n");
+    if original.is_empty() || original.ends_with('\n') {
+        content.push('\n');
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, is_checked: bool, issue_number: Option<u64>) -> TodoItem {
+        TodoItem {
+            text: text.to_string(),
+            is_checked,
+            issue_number,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_todo_and_done_headlines() {
+        let content = "* TODO Task 1\n* DONE Task 2\n** TODO Sub task\n";
+
+        let items = parse_todo_org(content).unwrap();
+
+        assert_eq!(
+            items,
+            vec![
+                item("Task 1", false, None),
+                item("Task 2", true, None),
+                item("Sub task", false, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ignores_headlines_without_keyword() {
+        let content = "#+TITLE: Tasks\n* Notes\nSome text\n* TODO Real task\n- [ ] not a task\n";
+
+        let items = parse_todo_org(content).unwrap();
+
+        assert_eq!(items, vec![item("Real task", false, None)]);
+    }
+
+    #[test]
+    fn test_parse_tags_and_priority() {
+        let content = "* TODO [#A] Fix login bug   :bug:auth:\n";
+
+        let items = parse_todo_org(content).unwrap();
+
+        assert_eq!(items[0].text, "Fix login bug");
+        assert_eq!(items[0].labels, vec!["bug", "auth"]);
+    }
+
+    #[test]
+    fn test_parse_issue_property() {
+        let content = r#"* TODO Task with issue
+SCHEDULED: <2026-10-20 Tue>
+:PROPERTIES:
+:CUSTOM_ID: task
+:ISSUE:    #123
+:END:
+* DONE Another task
+  :PROPERTIES:
+  :issue: 456
+  :END:
+* TODO Task (#789)
+"#;
+
+        let items = parse_todo_org(content).unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].issue_number, Some(123));
+        assert_eq!(items[1].issue_number, Some(456));
+        assert!(items[1].is_checked);
+        assert_eq!(items[2].text, "Task (#789)");
+        assert_eq!(items[2].issue_number, None);
+    }
+
+    #[test]
+    fn test_parse_custom_keywords() {
+        let content = "#+TODO: TODO(t) NEXT | DONE(d) CANCELLED\n* NEXT Next task\n* CANCELLED Dropped task\n* WAITING Not a keyword\n";
+
+        let items = parse_todo_org(content).unwrap();
+
+        assert_eq!(
+            items,
+            vec![
+                item("Next task", false, None),
+                item("Dropped task", true, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_keyword_sequence_without_separator() {
+        assert_eq!(
+            parse_keyword_sequence(" TODO FEEDBACK VERIFY DONE"),
+            TodoKeywords {
+                active: vec![
+                    "TODO".to_string(),
+                    "FEEDBACK".to_string(),
+                    "VERIFY".to_string()
+                ],
+                done: vec!["DONE".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_serialize_unchanged_items_preserves_content() {
+        let content = r#"#+TITLE: Tasks
+
+* Project
+Some notes.
+** TODO [#B] Task 1        :work:
+   SCHEDULED: <2026-10-20 Tue>
+** DONE Task 2
+:PROPERTIES:
+:ISSUE: 12
+:END:
+"#;
+        let items = parse_todo_org(content).unwrap();
+
+        assert_eq!(serialize_todo_org(content, &items), content);
+    }
+
+    #[test]
+    fn test_serialize_adds_property_drawer() {
+        let content = "* TODO Task 1\nBody text\n";
+        let items = vec![item("Task 1", false, Some(42))];
+
+        let actual = serialize_todo_org(content, &items);
+
+        assert_eq!(
+            actual,
+            "* TODO Task 1\n:PROPERTIES:\n:ISSUE: 42\n:END:\nBody text\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_adds_property_drawer_after_planning_line() {
+        let content = "* TODO Task 1\nDEADLINE: <2026-10-20 Tue>\n";
+        let items = vec![item("Task 1", false, Some(42))];
+
+        let actual = serialize_todo_org(content, &items);
+
+        assert_eq!(
+            actual,
+            "* TODO Task 1\nDEADLINE: <2026-10-20 Tue>\n:PROPERTIES:\n:ISSUE: 42\n:END:\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_adds_issue_to_existing_drawer() {
+        let content = "* TODO Task 1\n  :PROPERTIES:\n  :CUSTOM_ID: t1\n  :END:\n";
+        let items = vec![item("Task 1", false, Some(42))];
+
+        let actual = serialize_todo_org(content, &items);
+
+        assert_eq!(
+            actual,
+            "* TODO Task 1\n  :PROPERTIES:\n  :CUSTOM_ID: t1\n  :ISSUE: 42\n  :END:\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_updates_keyword_and_title() {
+        let content = "#+TODO: TODO NEXT | DONE\n** NEXT [#A] Old title :ops:\n:PROPERTIES:\n:ISSUE: 7\n:END:\n";
+        let items = vec![TodoItem {
+            text: "New title".to_string(),
+            is_checked: true,
+            issue_number: Some(7),
+            labels: vec!["ops".to_string()],
+            ..Default::default()
+        }];
+
+        let actual = serialize_todo_org(content, &items);
+
+        assert_eq!(
+            actual,
+            "#+TODO: TODO NEXT | DONE\n** DONE [#A] New title :ops:\n:PROPERTIES:\n:ISSUE: 7\n:END:\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_removes_missing_entries() {
+        let content =
+            "* Section\n** DONE Finished\n:PROPERTIES:\n:ISSUE: 1\n:END:\n** TODO Remaining\n";
+        let items = vec![item("Remaining", false, None)];
+
+        let actual = serialize_todo_org(content, &items);
+
+        assert_eq!(actual, "* Section\n** TODO Remaining\n");
+    }
+
+    #[test]
+    fn test_serialize_keeps_notes_of_removed_entries() {
+        let content = "* Section\n** DONE Finished\nSCHEDULED: <2026-10-01 Thu>\n:PROPERTIES:\n:ISSUE: 1\n:END:\nMeeting notes\n:LOGBOOK:\n- Note taken\n:END:\n** TODO Remaining\n";
+        let items = vec![item("Remaining", false, None)];
+
+        let actual = serialize_todo_org(content, &items);
+
+        assert_eq!(
+            actual,
+            "* Section\nMeeting notes\n:LOGBOOK:\n- Note taken\n:END:\n** TODO Remaining\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_matches_entries_by_issue_and_text() {
+        let content = "* TODO First\n:PROPERTIES:\n:ISSUE: 1\n:END:\nFirst notes\n* TODO Second\nSecond notes\n";
+        let items = vec![
+            item("Second", false, None),
+            item("Inserted", false, Some(3)),
+            item("First", true, Some(1)),
+        ];
+
+        let actual = serialize_todo_org(content, &items);
+
+        assert_eq!(
+            actual,
+            "* DONE First\n:PROPERTIES:\n:ISSUE: 1\n:END:\nFirst notes\n* TODO Second\nSecond notes\n* TODO Inserted\n:PROPERTIES:\n:ISSUE: 3\n:END:\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_appends_new_items() {
+        let content = "* TODO Existing\n";
+        let items = vec![
+            item("Existing", false, None),
+            TodoItem {
+                text: "From GitHub".to_string(),
+                is_checked: false,
+                issue_number: Some(5),
+                labels: vec!["bug".to_string()],
+                ..Default::default()
+            },
+        ];
+
+        let actual = serialize_todo_org(content, &items);
+
+        assert_eq!(
+            actual,
+            "* TODO Existing\n* TODO From GitHub :bug:\n:PROPERTIES:\n:ISSUE: 5\n:END:\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_empty() {
+        assert_eq!(serialize_todo_org("", &[]), "");
+    }
+
+    #[test]
+    fn test_insert_todo_org() {
+        let content = "#+TITLE: Tasks\n* Bugs :work:\n** TODO Crash\n:PROPERTIES:\n:ISSUE: 1\n:END:\n\n* Notes\nText\n";
+        let mut new_item = item("Fix login", false, Some(7));
+        new_item.labels = vec!["bug".to_string()];
+
+        assert_eq!(
+            insert_todo_org(content, Some("Bugs"), &new_item),
+            "#+TITLE: Tasks\n* Bugs :work:\n** TODO Crash\n:PROPERTIES:\n:ISSUE: 1\n:END:\n** TODO Fix login :bug:\n:PROPERTIES:\n:ISSUE: 7\n:END:\n\n* Notes\nText\n"
+        );
+        assert_eq!(
+            insert_todo_org("* Notes\n", Some("Ideas"), &item("Fix login", false, None)),
+            "* Notes\n* Ideas\n** TODO Fix login\n"
+        );
+        assert_eq!(
+            insert_todo_org(
+                "#+TODO: NEXT | FINISHED\n",
+                None,
+                &item("Fix login", false, None)
+            ),
+            "#+TODO: NEXT | FINISHED\n* NEXT Fix login\n"
+        );
+    }
+}
@@ -1,8 +1,15 @@
-use std::io::{self, Write};
+use crate::i18n::t;
+use serde::Serialize;
+use std::io::{self, IsTerminal, Write};
 
 pub fn println(message: &str, writer: &mut Option<&mut dyn Write>) -> io::Result<()> {
-    if let Err(e) = writeln!(io::stdout(), "{message}") {
-        eprintln!("標準出力への書き込みに失敗しました: {e}");
+    write_line(message, message, writer)
+}
+
+/// Print `styled` on stdout and `message` to the extra writer
+fn write_line(message: &str, styled: &str, writer: &mut Option<&mut dyn Write>) -> io::Result<()> {
+    if let Err(e) = writeln!(io::stdout(), "{styled}") {
+        eprintln!("{}", t!("output.stdout_failed", error = e));
     }
 
     if let Some(w) = writer {
@@ -11,3 +18,369 @@ pub fn println(message: &str, writer: &mut Option<&mut dyn Write>) -> io::Result
 
     Ok(())
 }
+
+/// Symbol at the start of a status line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    Success,
+    Failure,
+    Warning,
+    Info,
+}
+
+impl Mark {
+    const ALL: [Mark; 4] = [Mark::Success, Mark::Failure, Mark::Warning, Mark::Info];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Mark::Success => "✓",
+            Mark::Failure => "X",
+            Mark::Warning => "!",
+            Mark::Info => "ℹ",
+        }
+    }
+
+    fn style(self) -> anstyle::Style {
+        let color = match self {
+            Mark::Success => anstyle::AnsiColor::Green,
+            Mark::Failure => anstyle::AnsiColor::Red,
+            Mark::Warning => anstyle::AnsiColor::Yellow,
+            Mark::Info => anstyle::AnsiColor::Cyan,
+        };
+        color.on_default().bold()
+    }
+}
+
+/// Status line, e.g. `✓ Logged out of github.com`
+pub fn status(mark: Mark, message: &str) -> String {
+    format!("{} {message}", mark.symbol())
+}
+
+/// Whether colour is allowed: not with `--no-color` or a non-empty `NO_COLOR`
+pub fn color_enabled(no_color: bool) -> bool {
+    !no_color && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+/// Text with the mark starting each (possibly indented) status line coloured
+fn paint(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let content = line.trim_start();
+            let indent = &line[..line.len() - content.len()];
+            Mark::ALL
+                .iter()
+                .find_map(|mark| {
+                    let rest = content.strip_prefix(mark.symbol())?;
+                    rest.starts_with(' ').then(|| {
+                        let style = mark.style();
+                        format!("{indent}{style}{}{style:#}{rest}", mark.symbol())
+                    })
+                })
+                .unwrap_or_else(|| line.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format of command output, selected with `--output`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Messages for people
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// A result or warning of a command, printed as one JSON object per line with `--output json`.
+///
+/// The `type` field names the variant. This is a public interface documented in the README:
+/// fields may be added, but existing types and fields keep their names and meaning.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    IssueCreated {
+        repository: String,
+        number: u64,
+        title: String,
+    },
+    IssueClosed {
+        repository: String,
+        number: u64,
+    },
+    IssueRenamed {
+        repository: String,
+        number: u64,
+        title: String,
+    },
+    /// Change `push --dry-run` would make; `action` is `create`, `close` or `rename`
+    PlannedOperation {
+        repository: String,
+        action: &'static str,
+        number: Option<u64>,
+        title: String,
+    },
+    /// Title that differs between the task file and GitHub; `changed` is `github` or `local`
+    StaleTitle {
+        repository: String,
+        number: u64,
+        changed: &'static str,
+    },
+    /// Checked item removed by `clean`, or that would be with `--dry-run`
+    ItemRemoved {
+        number: u64,
+        text: String,
+        dry_run: bool,
+    },
+    /// Comment found by `scan --dry-run`; `line` starts at 1
+    Comment {
+        path: String,
+        line: usize,
+        keyword: String,
+        text: String,
+    },
+    Remote {
+        name: Option<String>,
+        repository: String,
+        account: Option<String>,
+        host: String,
+    },
+    RemoteAdded {
+        name: Option<String>,
+        repository: String,
+        account: Option<String>,
+        host: String,
+    },
+    RemoteRemoved {
+        name: Option<String>,
+        repository: String,
+    },
+    /// User a token belongs to, from `whoami`
+    User {
+        login: String,
+        account: Option<String>,
+        token_source: String,
+    },
+    /// Effective setting; `origin` is where it comes from, as in `config list --show-origin`
+    Setting {
+        key: String,
+        value: serde_json::Value,
+        origin: String,
+    },
+    /// Check of `auth status`; `status` is `ok`, `warning`, `error` or `info`
+    Check {
+        account: Option<String>,
+        repository: Option<String>,
+        status: &'static str,
+        message: String,
+        fix: Option<String>,
+    },
+    /// Code to enter on GitHub to finish `login`; `expires_in` is in seconds
+    LoginCode {
+        verification_uri: String,
+        user_code: String,
+        expires_in: u64,
+    },
+    LoggedIn {
+        login: String,
+        account: String,
+        permissions: Vec<String>,
+    },
+    LoggedOut {
+        hostname: String,
+        account: String,
+        revoked: bool,
+    },
+    FileCreated {
+        path: String,
+    },
+    Warning {
+        message: String,
+    },
+    /// Failure; the last event of a failed command has no `account`, and may have a `hint`
+    Error {
+        account: Option<String>,
+        message: String,
+        hint: Option<String>,
+    },
+}
+
+/// Where commands print, in the format selected with `--output`.
+///
+/// Text goes to the extra writer as is, and to a terminal with its status marks coloured.
+pub struct Output<'a> {
+    format: OutputFormat,
+    color: bool,
+    writer: Option<&'a mut dyn Write>,
+}
+
+impl<'a> Output<'a> {
+    pub fn new(format: OutputFormat, color: bool, writer: Option<&'a mut dyn Write>) -> Self {
+        Output {
+            format,
+            color,
+            writer,
+        }
+    }
+
+    /// Whether results are printed as JSON
+    pub fn is_json(&self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    /// Message for people, left out of JSON output
+    pub fn text(&mut self, message: &str) -> io::Result<()> {
+        match self.format {
+            OutputFormat::Text => self.print(message),
+            OutputFormat::Json => Ok(()),
+        }
+    }
+
+    /// Result of a command: `text` (if any) as text, `event` as JSON
+    pub fn event(&mut self, text: Option<&str>, event: &Event) -> io::Result<()> {
+        match (self.format, text) {
+            (OutputFormat::Text, Some(text)) => self.print(text),
+            (OutputFormat::Text, None) => Ok(()),
+            (OutputFormat::Json, _) => println(&to_json(event)?, &mut self.writer),
+        }
+    }
+
+    /// Problem printed on stderr as text, and with the results as JSON
+    pub fn diagnostic(&mut self, text: &str, event: &Event) -> io::Result<()> {
+        match self.format {
+            OutputFormat::Text => {
+                self.eprint(text);
+                Ok(())
+            }
+            OutputFormat::Json => println(&to_json(event)?, &mut self.writer),
+        }
+    }
+
+    /// Error a command failed with, and the hint for it if it is a known one
+    pub fn error(&mut self, error: &anyhow::Error) -> io::Result<()> {
+        let message = format!("{error:#}");
+        let hint = crate::error::hint(error);
+        let mut text = status(Mark::Failure, &message);
+        if let Some(hint) = &hint {
+            text = format!("{text}\n{}", status(Mark::Info, hint));
+        }
+        self.diagnostic(
+            &text,
+            &Event::Error {
+                account: None,
+                message,
+                hint,
+            },
+        )
+    }
+
+    /// Output that is the same in every format, such as a completion script
+    pub fn document(&mut self, content: &str) -> io::Result<()> {
+        println(content, &mut self.writer)
+    }
+
+    fn print(&mut self, text: &str) -> io::Result<()> {
+        if self.color && io::stdout().is_terminal() {
+            write_line(text, &paint(text), &mut self.writer)
+        } else {
+            println(text, &mut self.writer)
+        }
+    }
+
+    fn eprint(&self, text: &str) {
+        if self.color && io::stderr().is_terminal() {
+            eprintln!("{}", paint(text));
+        } else {
+            eprintln!("{text}");
+        }
+    }
+}
+
+fn to_json(event: &Event) -> io::Result<String> {
+    serde_json::to_string(event).map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_json() {
+        let created = Event::IssueCreated {
+            repository: "owner/repo".to_string(),
+            number: 12,
+            title: "Write docs".to_string(),
+        };
+        assert_eq!(
+            to_json(&created).unwrap(),
+            r#"{"type":"issue_created","repository":"owner/repo","number":12,"title":"Write docs"}"#
+        );
+
+        let remote = Event::Remote {
+            name: None,
+            repository: "owner/repo".to_string(),
+            account: Some("work".to_string()),
+            host: "github.com".to_string(),
+        };
+        assert_eq!(
+            to_json(&remote).unwrap(),
+            r#"{"type":"remote","name":null,"repository":"owner/repo","account":"work","host":"github.com"}"#
+        );
+    }
+
+    #[test]
+    fn test_output_formats() {
+        let warning = Event::Warning {
+            message: "careful".to_string(),
+        };
+
+        let mut buffer = Vec::new();
+        let mut output = Output::new(OutputFormat::Text, true, Some(&mut buffer));
+        output.text("hello").unwrap();
+        output.event(Some("Warning: careful"), &warning).unwrap();
+        output.event(None, &warning).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "hello\nWarning: careful\n"
+        );
+
+        let mut buffer = Vec::new();
+        let mut output = Output::new(OutputFormat::Json, true, Some(&mut buffer));
+        output.text("hello").unwrap();
+        output.event(Some("Warning: careful"), &warning).unwrap();
+        output.document("script").unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "{\"type\":\"warning\",\"message\":\"careful\"}\nscript\n"
+        );
+    }
+
+    #[test]
+    fn test_status_and_paint() {
+        assert_eq!(status(Mark::Success, "Done"), "✓ Done");
+        assert_eq!(status(Mark::Failure, "Failed"), "X Failed");
+
+        let green = Mark::Success.style();
+        let red = Mark::Failure.style();
+        assert_eq!(paint("✓ Done"), format!("{green}✓{green:#} Done"));
+        assert_eq!(
+            paint("owner/repo\n  X Missing\n    Fix: run it"),
+            format!("owner/repo\n  {red}X{red:#} Missing\n    Fix: run it")
+        );
+        assert_eq!(paint("Xylophone"), "Xylophone");
+        assert_eq!(paint("origin\tX/repo"), "origin\tX/repo");
+    }
+
+    #[test]
+    fn test_error_event() {
+        let mut buffer = Vec::new();
+        let mut output = Output::new(OutputFormat::Json, false, Some(&mut buffer));
+        output
+            .error(&crate::error::Error::AuthenticationRequired.into())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "{\"type\":\"error\",\"account\":null,\"message\":\"Authentication required\",\"hint\":\"To get started, please run:  atat login\"}\n"
+        );
+    }
+}
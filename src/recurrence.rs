@@ -0,0 +1,244 @@
+use crate::todo::TodoItem;
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+/// Unit of a recurrence interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Day,
+    Weekday,
+    Week,
+    Month,
+    Year,
+}
+
+/// A parsed `🔁` recurrence rule such as `every 2 weeks when done`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recurrence {
+    pub interval: u32,
+    pub unit: Unit,
+    /// Count from the completion date instead of the previous dates
+    pub when_done: bool,
+}
+
+impl Recurrence {
+    /// Parse `every [N] day|week|month|year[s]`, `every weekday`, with an optional `when done`.
+    pub fn parse(rule: &str) -> Option<Self> {
+        let rule = rule.trim().to_lowercase();
+        let (rule, when_done) = match rule.strip_suffix("when done") {
+            Some(rest) => (rest.trim_end().to_string(), true),
+            None => (rule, false),
+        };
+
+        let words: Vec<&str> = rule.split_whitespace().collect();
+        let (interval, unit_word) = match words.as_slice() {
+            ["every", unit] => (1, *unit),
+            ["every", interval, unit] => (interval.parse().ok().filter(|&n| n > 0)?, *unit),
+            _ => return None,
+        };
+
+        let unit = match unit_word.strip_suffix('s').unwrap_or(unit_word) {
+            "day" => Unit::Day,
+            "weekday" if interval == 1 => Unit::Weekday,
+            "week" => Unit::Week,
+            "month" => Unit::Month,
+            "year" => Unit::Year,
+            _ => return None,
+        };
+
+        Some(Recurrence {
+            interval,
+            unit,
+            when_done,
+        })
+    }
+
+    /// The next date after `date` for this rule
+    pub fn advance(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self.unit {
+            Unit::Day => date.checked_add_days(Days::new(self.interval.into())),
+            Unit::Week => date.checked_add_days(Days::new(u64::from(self.interval) * 7)),
+            Unit::Month => date.checked_add_months(Months::new(self.interval)),
+            Unit::Year => date.checked_add_months(Months::new(self.interval * 12)),
+            Unit::Weekday => {
+                let days = match date.weekday() {
+                    Weekday::Fri => 3,
+                    Weekday::Sat => 2,
+                    _ => 1,
+                };
+                date.checked_add_days(Days::new(days))
+            }
+        }
+    }
+}
+
+/// Build the next occurrence of a completed recurring item.
+///
+/// The new item is unchecked and has no issue. Its dates are moved by the
+/// recurrence interval (from the done date for `when done` rules); an item
+/// without dates gets a due date counted from `today`. Returns `None` if the item has no valid recurrence rule.
+pub fn next_occurrence(item: &TodoItem, today: NaiveDate) -> Option<TodoItem> {
+    let recurrence = Recurrence::parse(item.metadata.recurrence.as_deref()?)?;
+    let metadata = &item.metadata;
+
+    // The date the rule advances; the other dates keep their offset from it
+    let primary = metadata.due.or(metadata.scheduled).or(metadata.start);
+    let base = if recurrence.when_done {
+        metadata.done.unwrap_or(today)
+    } else {
+        primary.unwrap_or(today)
+    };
+    let next_primary = recurrence.advance(base)?;
+    let shift = next_primary.signed_duration_since(primary.unwrap_or(base));
+
+    let mut next = item.clone();
+    next.is_checked = false;
+    next.issue_number = None;
+    next.body = None;
+    next.metadata.done = None;
+    next.metadata.created = metadata.created.map(|_| today);
+    if primary.is_some() {
+        next.metadata.due = metadata.due.map(|date| date + shift);
+        next.metadata.scheduled = metadata.scheduled.map(|date| date + shift);
+        next.metadata.start = metadata.start.map(|date| date + shift);
+    } else {
+        next.metadata.due = Some(next_primary);
+    }
+    Some(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task_metadata::TaskMetadata;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn recurring(rule: &str, metadata: TaskMetadata) -> TodoItem {
+        TodoItem {
+            text: "Rotate staging credentials".to_string(),
+            is_checked: true,
+            issue_number: Some(10),
+            labels: vec!["ops".to_string()],
+            metadata: TaskMetadata {
+                recurrence: Some(rule.to_string()),
+                ..metadata
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(
+            Recurrence::parse("every week"),
+            Some(Recurrence {
+                interval: 1,
+                unit: Unit::Week,
+                when_done: false
+            })
+        );
+        assert_eq!(
+            Recurrence::parse("Every 3 Days when done"),
+            Some(Recurrence {
+                interval: 3,
+                unit: Unit::Day,
+                when_done: true
+            })
+        );
+        assert_eq!(
+            Recurrence::parse("every weekday").map(|r| r.unit),
+            Some(Unit::Weekday)
+        );
+        assert_eq!(
+            Recurrence::parse("every 2 years").map(|r| r.unit),
+            Some(Unit::Year)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unsupported_rules() {
+        assert_eq!(Recurrence::parse("every week on Monday"), None);
+        assert_eq!(Recurrence::parse("every 0 days"), None);
+        assert_eq!(Recurrence::parse("weekly"), None);
+        assert_eq!(Recurrence::parse(""), None);
+    }
+
+    #[test]
+    fn test_advance() {
+        let week = Recurrence::parse("every week").unwrap();
+        assert_eq!(week.advance(date(2026, 10, 20)), Some(date(2026, 10, 27)));
+
+        let month = Recurrence::parse("every month").unwrap();
+        assert_eq!(month.advance(date(2026, 1, 31)), Some(date(2026, 2, 28)));
+
+        let weekday = Recurrence::parse("every weekday").unwrap();
+        assert_eq!(
+            weekday.advance(date(2026, 10, 23)),
+            Some(date(2026, 10, 26))
+        );
+        assert_eq!(
+            weekday.advance(date(2026, 10, 20)),
+            Some(date(2026, 10, 21))
+        );
+    }
+
+    #[test]
+    fn test_next_occurrence_moves_dates() {
+        let item = recurring(
+            "every week",
+            TaskMetadata {
+                scheduled: Some(date(2026, 10, 18)),
+                due: Some(date(2026, 10, 20)),
+                done: Some(date(2026, 10, 19)),
+                ..TaskMetadata::default()
+            },
+        );
+
+        let next = next_occurrence(&item, date(2026, 10, 19)).unwrap();
+
+        assert_eq!(next.text, "Rotate staging credentials");
+        assert!(!next.is_checked);
+        assert_eq!(next.issue_number, None);
+        assert_eq!(next.labels, vec!["ops"]);
+        assert_eq!(next.metadata.due, Some(date(2026, 10, 27)));
+        assert_eq!(next.metadata.scheduled, Some(date(2026, 10, 25)));
+        assert_eq!(next.metadata.done, None);
+        assert_eq!(next.metadata.recurrence.as_deref(), Some("every week"));
+    }
+
+    #[test]
+    fn test_next_occurrence_when_done_counts_from_completion() {
+        let item = recurring(
+            "every 2 days when done",
+            TaskMetadata {
+                due: Some(date(2026, 10, 1)),
+                done: Some(date(2026, 10, 10)),
+                ..TaskMetadata::default()
+            },
+        );
+
+        let next = next_occurrence(&item, date(2026, 10, 18)).unwrap();
+
+        assert_eq!(next.metadata.due, Some(date(2026, 10, 12)));
+    }
+
+    #[test]
+    fn test_next_occurrence_without_dates_uses_today() {
+        let item = recurring("every month", TaskMetadata::default());
+
+        let next = next_occurrence(&item, date(2026, 10, 18)).unwrap();
+
+        assert_eq!(next.metadata.due, Some(date(2026, 11, 18)));
+    }
+
+    #[test]
+    fn test_next_occurrence_requires_valid_rule() {
+        let mut item = recurring("every full moon", TaskMetadata::default());
+        assert_eq!(next_occurrence(&item, date(2026, 10, 18)), None);
+
+        item.metadata.recurrence = None;
+        assert_eq!(next_occurrence(&item, date(2026, 10, 18)), None);
+    }
+}
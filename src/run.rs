@@ -1,249 +1,884 @@
 use anyhow::anyhow;
 
 use crate::auth;
+use crate::auth_status;
 use crate::cli;
+use crate::comment_scanner;
 use crate::config;
+use crate::error;
+use crate::git_remote;
 use crate::github;
-use crate::markdown_parser;
+use crate::i18n::{self, t};
+use crate::logging;
 use crate::output;
 use crate::storage;
+use crate::todo;
+use crate::token_source;
 use crate::whoami;
 
 mod endpoints {
     pub const DEVICE_CODE: &str = "https://github.com/login/device/code";
     pub const ACCESS_TOKEN: &str = "https://github.com/login/oauth/access_token";
-    pub const USER: &str = "https://api.github.com/user";
-    pub const REPO_DETAILS: &str = "https://api.github.com/repos";
-    pub const ISSUES: &str = "https://api.github.com/repos";
+    pub const USER_INSTALLATIONS: &str = "https://api.github.com/user/installations";
+    pub const APPLICATIONS: &str = "https://api.github.com/applications";
+    pub const APP_AUTHORIZATIONS: &str = "https://github.com/settings/apps/authorizations";
 }
 
 const CLIENT_ID: &str = std::env!("CLIENT_ID");
-const DEFAULT_POLL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+const CLIENT_SECRET: Option<&str> = std::option_env!("CLIENT_SECRET");
+/// Slug of the GitHub App, used to link to its installation page
+const APP_SLUG: Option<&str> = std::option_env!("APP_SLUG");
 
 pub async fn run(
     args: Vec<String>,
     mut stdout_additional: Option<&mut dyn std::io::Write>,
     poll_timeout: Option<std::time::Duration>,
 ) -> anyhow::Result<()> {
-    match cli::parser::parse_args(&args) {
+    // Usage errors are reported before the config, which may set `language`, is read
+    i18n::set_language(i18n::detect(None, |name| std::env::var(name).ok()));
+    let (global_options, command) = match cli::parser::parse_args(&args) {
+        Ok(parsed) => parsed,
+        // clap reports `--help` and `--version` as errors to print on stdout
+        Err(e) if !e.use_stderr() => {
+            output::println(e.render().to_string().trim_end(), &mut stdout_additional)?;
+            return Ok(());
+        }
+        Err(e) => {
+            let error = anyhow!("{}", cli::parser::usage_error_message(&e));
+            output::Output::new(
+                output::OutputFormat::Text,
+                output::color_enabled(false),
+                stdout_additional,
+            )
+            .error(&error)?;
+            return Err(error);
+        }
+    };
+
+    logging::init(global_options.verbose);
+    if let Ok(layered) = load_config(&global_options) {
+        let setting = config::language(&layered.values());
+        i18n::set_language(i18n::detect(setting.as_deref(), |name| {
+            std::env::var(name).ok()
+        }));
+    }
+    let mut out = output::Output::new(
+        global_options.output,
+        output::color_enabled(global_options.no_color),
+        stdout_additional,
+    );
+    let result = run_command(command, &global_options, &mut out, poll_timeout).await;
+    if let Err(e) = &result {
+        out.error(e)?;
+    }
+    result
+}
+
+async fn run_command(
+    command: cli::parser::Command,
+    global_options: &cli::parser::GlobalOptions,
+    out: &mut output::Output<'_>,
+    poll_timeout: Option<std::time::Duration>,
+) -> anyhow::Result<()> {
+    match command {
         cli::parser::Command::Whoami => {
-            let storage = storage::FileTokenStorage::new();
-            match storage::TokenStorage::load(&storage)? {
-                Some(token) => {
-                    let client = reqwest::Client::builder()
-                        .timeout(std::time::Duration::from_secs(30))
-                        .build()?;
-                    let response = client
-                        .get(endpoints::USER)
-                        .bearer_auth(token)
-                        .header("Accept", "application/json")
-                        .header("User-Agent", "atat-cli")
-                        .send()
-                        .await?;
-                    if response.status().is_success() {
-                        let text = response.text().await?;
-                        match whoami::extract_login_from_user_response(&text) {
-                            Ok(login) => output::println(&login, &mut stdout_additional)?,
-                            Err(err) => eprintln!("{err}"),
-                        }
-                    } else if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-                        eprintln!("Token invalid or expired. Please run `login` again.");
-                    } else {
-                        eprintln!("API request error: {}", response.status());
+            let client = reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()?;
+
+            // The token commands use without a named account, then every logged-in account
+            let resolved = resolve_token(global_options, None).await.ok().flatten();
+            let source = resolved
+                .as_ref()
+                .map_or(token_source::TokenSource::Stored, |resolved| {
+                    resolved.source
+                });
+            let mut tokens: Vec<(anyhow::Result<String>, Option<String>)> = resolved
+                .filter(|resolved| resolved.source != token_source::TokenSource::Stored)
+                .map(|resolved| (Ok(resolved.token), None))
+                .into_iter()
+                .collect();
+            let accounts = match &global_options.account {
+                Some(account) => vec![account.clone()],
+                None => all_accounts()?,
+            };
+            for account in accounts {
+                match load_stored_token(global_options, &account).await {
+                    Ok(Some(token)) => tokens.push((Ok(token), Some(account))),
+                    Ok(None) => {}
+                    Err(e) => tokens.push((Err(e), Some(account))),
+                }
+            }
+
+            if tokens.is_empty() {
+                let message = t!("whoami.no_token");
+                out.diagnostic(
+                    &message,
+                    &output::Event::Error {
+                        account: None,
+                        message: message.clone(),
+                        hint: None,
+                    },
+                )?;
+                return Ok(());
+            }
+            for (token, account) in &tokens {
+                let login = match token {
+                    Ok(token) => fetch_login(&client, cli::parser::DEFAULT_HOSTNAME, token).await,
+                    Err(e) => Err(anyhow!("{e}")),
+                };
+                match login {
+                    Ok(login) => {
+                        let text = match account {
+                            Some(account) => {
+                                format!("{login}{}", t!("account.suffix", account = account))
+                            }
+                            None => login.clone(),
+                        };
+                        out.event(
+                            Some(&text),
+                            &output::Event::User {
+                                login,
+                                account: account.clone(),
+                                token_source: source.to_string(),
+                            },
+                        )?;
                     }
+                    Err(e) => {
+                        let text = match account {
+                            Some(account) => format!("{account}: {e}"),
+                            None => e.to_string(),
+                        };
+                        out.diagnostic(
+                            &text,
+                            &output::Event::Error {
+                                account: account.clone(),
+                                message: e.to_string(),
+                                hint: None,
+                            },
+                        )?;
+                    }
+                }
+            }
+            out.text(&t!("whoami.token_source", source = source))?;
+        }
+        cli::parser::Command::AuthStatus => {
+            let config_map = load_config(global_options)?.values();
+            let repositories = config::repositories(&config_map)?;
+
+            // Repositories grouped by the host and account whose token reaches them
+            let mut accounts: Vec<(&str, String, Vec<&config::Repository>)> = Vec::new();
+            for repository in &repositories {
+                let host = repository.host();
+                let account = selected_account(global_options, repository.account.as_deref())?;
+                match accounts
+                    .iter_mut()
+                    .find(|(group_host, name, _)| *group_host == host && *name == account)
+                {
+                    Some((_, _, repos)) => repos.push(repository),
+                    None => accounts.push((host, account, vec![repository])),
+                }
+            }
+            if accounts.is_empty() {
+                accounts.push((
+                    cli::parser::DEFAULT_HOSTNAME,
+                    selected_account(global_options, None)?,
+                    Vec::new(),
+                ));
+            }
+
+            let client = reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()?;
+            let now = chrono::Utc::now();
+            let mut failed = false;
+
+            for (host, account, repos) in &accounts {
+                // `atat login` and accounts are for github.com only
+                if *host != cli::parser::DEFAULT_HOSTNAME {
+                    out.text(host)?;
+                    let resolved = token_source::resolve_enterprise_token(
+                        global_options.token.as_deref(),
+                        |name| std::env::var(name).ok(),
+                        || gh_auth_token(host),
+                    );
+                    let login = match &resolved {
+                        Some(resolved) => fetch_login(&client, host, &resolved.token).await.ok(),
+                        None => None,
+                    };
+                    let checks = match &resolved {
+                        Some(resolved) => auth_status::check_token(
+                            account,
+                            Some(resolved.source),
+                            login.as_deref(),
+                            None,
+                            now,
+                        ),
+                        None => vec![auth_status::Check {
+                            status: auth_status::Status::Error,
+                            message: t!("auth_status.not_logged_in"),
+                            fix: Some(t!("auth_status.fix_enterprise", host = host)),
+                        }],
+                    };
+                    failed |= print_checks(&checks, None, None, out)?;
+                    let (Some(resolved), Some(_)) = (&resolved, &login) else {
+                        continue;
+                    };
+                    for repository in repos {
+                        out.text(&repository.name)?;
+                        let check =
+                            match fetch_repo_access(&client, repository, &resolved.token).await {
+                                Ok(access) => {
+                                    auth_status::check_repository(&repository.name, None, access)
+                                }
+                                Err(e) => auth_status::check_repository_failed(
+                                    &repository.name,
+                                    &format!("{e:#}"),
+                                ),
+                            };
+                        failed |= print_checks(&[check], None, Some(&repository.name), out)?;
+                    }
+                    continue;
+                }
+
+                out.text(&format!(
+                    "{}{}",
+                    host,
+                    t!("account.suffix", account = account)
+                ))?;
+
+                let resolved = match resolve_token(global_options, Some(account)).await {
+                    Ok(resolved) => resolved,
+                    Err(e) => {
+                        let check = auth_status::Check {
+                            status: auth_status::Status::Error,
+                            message: e.to_string(),
+                            fix: Some(t!(
+                                "auth_status.fix_run",
+                                command = auth_status::login_command(account)
+                            )),
+                        };
+                        failed |= print_checks(&[check], Some(account), None, out)?;
+                        continue;
+                    }
+                };
+                let login = match &resolved {
+                    Some(resolved) => fetch_login(&client, host, &resolved.token).await.ok(),
+                    None => None,
+                };
+                let credentials = match &resolved {
+                    Some(resolved) if resolved.source == token_source::TokenSource::Stored => {
+                        stored_credentials(global_options, account).ok().flatten()
+                    }
+                    _ => None,
+                };
+                failed |= print_checks(
+                    &auth_status::check_token(
+                        account,
+                        resolved.as_ref().map(|resolved| resolved.source),
+                        login.as_deref(),
+                        credentials.as_ref(),
+                        now,
+                    ),
+                    Some(account),
+                    None,
+                    out,
+                )?;
+                let (Some(resolved), Some(_)) = (&resolved, &login) else {
+                    continue;
+                };
+
+                // Only GitHub App user tokens can list installations
+                let installations = fetch_installations(&client, &resolved.token).await.ok();
+                for repository in repos {
+                    out.text(&repository.name)?;
+                    let mut checks = auth_status::check_installation(
+                        &repository.name,
+                        installations.as_deref(),
+                        APP_SLUG,
+                    );
+                    let installation = installations.as_deref().and_then(|installations| {
+                        auth_status::installation_for(&repository.name, installations)
+                    });
+                    // A repository that cannot be looked up fails its check, not the report
+                    checks.push(
+                        match fetch_repo_access(&client, repository, &resolved.token).await {
+                            Ok(access) => auth_status::check_repository(
+                                &repository.name,
+                                installation,
+                                access,
+                            ),
+                            Err(e) => auth_status::check_repository_failed(
+                                &repository.name,
+                                &format!("{e:#}"),
+                            ),
+                        },
+                    );
+                    failed |= print_checks(&checks, Some(account), Some(&repository.name), out)?;
+                }
+            }
+
+            if repositories.is_empty() {
+                let check = auth_status::Check {
+                    status: auth_status::Status::Error,
+                    message: error::Error::NoRepositoryConfigured.to_string(),
+                    fix: Some(t!(
+                        "auth_status.fix_run",
+                        command = "atat remote add <owner>/<repo>"
+                    )),
+                };
+                failed |= print_checks(&[check], None, None, out)?;
+            }
+            if failed {
+                return Err(anyhow!(t!("auth_status.failed")));
+            }
+        }
+        cli::parser::Command::ConfigList { show_origin } => {
+            let layered = load_config(global_options)?;
+            for (key, value, origin) in layered.entries() {
+                let entry = format!("{}={}", key.as_str(), config::display_value(value));
+                let line = if show_origin {
+                    format!("{origin}\t{entry}")
+                } else {
+                    entry
+                };
+                out.event(
+                    Some(&line),
+                    &output::Event::Setting {
+                        key: key.as_str().to_string(),
+                        value: value.clone(),
+                        origin: origin.to_string(),
+                    },
+                )?;
+            }
+        }
+        cli::parser::Command::ConfigGet { key } => {
+            let key = config_key(&key)?;
+            let layered = load_config(global_options)?;
+            let (_, value, origin) = layered
+                .entries()
+                .into_iter()
+                .find(|(entry_key, _, _)| *entry_key == key)
+                .ok_or_else(|| anyhow!(t!("config.not_set", key = key.as_str())))?;
+            out.event(
+                Some(&config::display_value(value)),
+                &output::Event::Setting {
+                    key: key.as_str().to_string(),
+                    value: value.clone(),
+                    origin: origin.to_string(),
+                },
+            )?;
+        }
+        cli::parser::Command::ConfigSet { key, value, global } => {
+            let key = config_key(&key)?;
+            let value = key.parse_value(&value)?;
+            let config_storage = config_storage(global)?;
+            let config_map = storage::ConfigStorage::load_config(config_storage.as_ref())?;
+            let new_config = config::update_config(
+                &config_map,
+                &std::collections::HashMap::from([(key, value)]),
+            );
+            storage::ConfigStorage::save_config(config_storage.as_ref(), &new_config)?;
+        }
+        cli::parser::Command::ConfigUnset { key, global } => {
+            let key = config_key(&key)?;
+            let config_storage = config_storage(global)?;
+            let mut config_map = storage::ConfigStorage::load_config(config_storage.as_ref())?;
+            if config_map.remove(&key).is_none() {
+                return Err(anyhow!(t!("config.not_set", key = key.as_str())));
+            }
+            storage::ConfigStorage::save_config(config_storage.as_ref(), &config_map)?;
+        }
+        cli::parser::Command::Init { import } => {
+            let root = storage::project_root()?;
+            let remote = git_remote_repository(&root, config::DEFAULT_REMOTE)
+                .map_err(|e| anyhow!(t!("init.no_remote", error = e)))?;
+            let name = remote.name();
+
+            let config_storage = storage::LocalConfigStorage::new()
+                .map_err(|e| anyhow!(t!("storage.init_failed", error = e)))?;
+            let mut config_map = storage::ConfigStorage::load_config(&config_storage)
+                .map_err(|e| anyhow!(t!("config.load_project_failed", error = e)))?;
+            if config::repositories(&config_map)?
+                .iter()
+                .any(|repository| repository.name == name)
+            {
+                out.text(&output::status(
+                    output::Mark::Info,
+                    &t!("init.already_configured", repo = name),
+                ))?;
+            } else {
+                if let Some(account) = &global_options.account {
+                    storage::validate_account_name(account)?;
                 }
-                None => eprintln!("No token found. Please run `login` first."),
+                let repository = config::Repository {
+                    name: name.clone(),
+                    account: global_options.account.clone(),
+                    remote: Some(config::DEFAULT_REMOTE.to_string()),
+                    host: remote.enterprise_host(),
+                };
+                let entry = repository.to_value();
+                let repositories = config_map
+                    .entry(config::ConfigKey::Repositories)
+                    .or_insert_with(|| serde_json::json!([]));
+                repositories
+                    .as_array_mut()
+                    .ok_or_else(|| anyhow!(t!("config.invalid_repository")))?
+                    .push(entry);
+                storage::ConfigStorage::save_config(&config_storage, &config_map)
+                    .map_err(|e| anyhow!(t!("config.save_project_failed", error = e)))?;
+                out.event(
+                    Some(&output::status(
+                        output::Mark::Success,
+                        &t!(
+                            "init.added",
+                            repo = name,
+                            path = format!(
+                                "{}/{}",
+                                config::PROJECT_CONFIG_DIR,
+                                config::PROJECT_CONFIG_FILENAME
+                            )
+                        ),
+                    )),
+                    &remote_added_event(repository),
+                )?;
+            }
+
+            let todo_file = config::todo_file(&load_config(global_options)?.values());
+            let todo_path = root.join(&todo_file);
+            if !todo_path.exists() {
+                let todo_format = todo::TodoFormat::from_path(&todo_path);
+                std::fs::write(&todo_path, todo_format.starter_content())
+                    .map_err(|e| anyhow!(t!("file.write_failed", path = todo_file, error = e)))?;
+                out.event(
+                    Some(&output::status(
+                        output::Mark::Success,
+                        &t!("init.created", path = todo_file),
+                    )),
+                    &output::Event::FileCreated {
+                        path: todo_file.clone(),
+                    },
+                )?;
+            }
+
+            let import = match import {
+                Some(import) => import,
+                None => confirm(
+                    &t!("init.import_question", repo = name, path = todo_file),
+                    out,
+                )?,
+            };
+            if import {
+                pull(global_options, out)
+                    .await
+                    .map_err(|e| anyhow!(t!("init.import_failed", error = e)))?;
+                out.text(&output::status(
+                    output::Mark::Success,
+                    &t!("init.imported", repo = name),
+                ))?;
+            } else {
+                out.text(&output::status(
+                    output::Mark::Info,
+                    &t!("init.import_later"),
+                ))?;
             }
         }
-        cli::parser::Command::Login => {
+        cli::parser::Command::Login { no_browser } => {
+            let account = selected_account(global_options, None)?;
+
             let client = anyhow::Context::context(
                 reqwest::Client::builder()
                     .timeout(std::time::Duration::from_secs(30))
                     .build(),
-                "Failed to create HTTP client",
+                t!("login.client_failed"),
             )?;
 
             let device_code_res = anyhow::Context::context(
                 request_device_code(&client, CLIENT_ID).await,
-                "Failed to get device code",
+                t!("login.device_code_failed"),
             )?;
 
-            output::println(
-                &format!("Please visit: {}", device_code_res.verification_uri),
-                &mut stdout_additional,
-            )?;
-            output::println(
-                &format!("and enter code: {}", device_code_res.user_code),
-                &mut stdout_additional,
+            out.event(
+                Some(&t!(
+                    "login.code",
+                    uri = device_code_res.verification_uri,
+                    code = device_code_res.user_code,
+                    minutes = device_code_res.expires_in.div_ceil(60)
+                )),
+                &output::Event::LoginCode {
+                    verification_uri: device_code_res.verification_uri.clone(),
+                    user_code: device_code_res.user_code.clone(),
+                    expires_in: device_code_res.expires_in,
+                },
             )?;
+            if !no_browser && std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+                out.text(&t!(
+                    "login.press_enter",
+                    uri = device_code_res.verification_uri
+                ))?;
+                offer_browser(device_code_res.verification_uri.clone());
+            }
 
-            let timeout = poll_timeout.unwrap_or(DEFAULT_POLL_TIMEOUT);
+            let timeout =
+                poll_timeout.unwrap_or(std::time::Duration::from_secs(device_code_res.expires_in));
 
-            let access_token = anyhow::Context::context(
-                poll_for_token(&client, &device_code_res, timeout).await,
-                "Failed to poll for access token",
+            let credentials = tokio::select! {
+                credentials = poll_for_token(&client, &device_code_res, timeout) => {
+                    anyhow::Context::context(credentials, t!("login.poll_failed"))?
+                }
+                _ = tokio::signal::ctrl_c() => return Err(anyhow!(t!("login.cancelled"))),
+            };
+
+            let login = anyhow::Context::context(
+                fetch_login(
+                    &client,
+                    cli::parser::DEFAULT_HOSTNAME,
+                    &credentials.access_token,
+                )
+                .await,
+                t!("login.confirm_failed"),
             )?;
 
-            let storage = storage::FileTokenStorage::new();
+            let storage = token_storage(global_options, &account)?;
             anyhow::Context::context(
-                storage::TokenStorage::save(&storage, &access_token),
-                "Failed to save token",
+                storage::TokenStorage::save(storage.as_ref(), &credentials.to_json()),
+                t!("token.save_failed"),
             )?;
-            output::println("✓ Authentication complete", &mut stdout_additional)?;
-        }
-        cli::parser::Command::RemoteList => {
-            let config_storage = anyhow::Context::context(
-                storage::LocalConfigStorage::new(),
-                "Failed to read project configuration",
+            storage::AccountRegistry::new()?.add(&account)?;
+
+            // The granted permissions are informational; login succeeded without them
+            let permissions = fetch_installations(&client, &credentials.access_token)
+                .await
+                .map(|installations| auth::describe_installation_permissions(&installations))
+                .unwrap_or_default();
+            if permissions.is_empty() {
+                out.text(&output::status(
+                    output::Mark::Success,
+                    &t!("login.complete"),
+                ))?;
+            } else {
+                out.text(&output::status(
+                    output::Mark::Success,
+                    &t!("login.complete_with_permissions"),
+                ))?;
+                for permission in &permissions {
+                    out.text(&format!("  - {permission}"))?;
+                }
+            }
+            out.event(
+                Some(&output::status(
+                    output::Mark::Success,
+                    &t!("login.logged_in", login = login),
+                )),
+                &output::Event::LoggedIn {
+                    login,
+                    account,
+                    permissions,
+                },
             )?;
-
-            match storage::ConfigStorage::load_config(&config_storage) {
-                Ok(config_map) => {
-                    if let Some(serde_json::Value::Array(repos)) =
-                        config_map.get(&config::ConfigKey::Repositories)
-                    {
-                        for repo_val in repos {
-                            if let serde_json::Value::String(repo_str) = repo_val {
-                                output::println(repo_str, &mut stdout_additional)?;
-                            }
-                        }
-                    }
+        }
+        cli::parser::Command::Logout { hostname, revoke } => {
+            if hostname != cli::parser::DEFAULT_HOSTNAME {
+                return Err(error::Error::NotLoggedIn(hostname).into());
+            }
+            // Checked first, so that the token is not deleted without being revoked
+            let client_secret = match (revoke, CLIENT_SECRET) {
+                (false, _) => None,
+                (true, Some(client_secret)) => Some(client_secret),
+                (true, None) => {
+                    return Err(anyhow!(t!(
+                        "logout.revoke_unsupported",
+                        url = endpoints::APP_AUTHORIZATIONS
+                    )));
                 }
-                Err(e) => {
-                    return Err(anyhow::anyhow!("Error loading project config: {e}"));
+            };
+
+            let account = selected_account(global_options, None)?;
+            let account_suffix = if account == storage::DEFAULT_ACCOUNT {
+                String::new()
+            } else {
+                t!("account.suffix", account = account)
+            };
+
+            let storage = token_storage(global_options, &account)?;
+            // A token that cannot be read (e.g. insecure permissions) is still deleted
+            let loaded = storage::TokenStorage::load(storage.as_ref());
+            if let Ok(None) = loaded {
+                return Err(
+                    error::Error::NotLoggedIn(format!("{hostname}{account_suffix}")).into(),
+                );
+            }
+            if let Some(client_secret) = client_secret {
+                let content = loaded?.unwrap_or_default();
+                let credentials = auth::Credentials::parse(&content).map_err(|e| anyhow!(e))?;
+                revoke_authorization(client_secret, &credentials.access_token).await?;
+            }
+            anyhow::Context::context(
+                storage::TokenStorage::delete(storage.as_ref()),
+                t!("logout.delete_failed"),
+            )?;
+            storage::AccountRegistry::new()?.remove(&account)?;
+            let host = format!("{hostname}{account_suffix}");
+            let message = if revoke {
+                t!("logout.revoked", host = host)
+            } else {
+                t!("logout.logged_out", host = host)
+            };
+            out.event(
+                Some(&output::status(output::Mark::Success, &message)),
+                &output::Event::LoggedOut {
+                    hostname: hostname.clone(),
+                    account,
+                    revoked: revoke,
+                },
+            )?;
+        }
+        cli::parser::Command::RemoteList => {
+            let config_map = load_config(global_options)?.values();
+            for repository in config::repositories(&config_map)? {
+                let location = match &repository.host {
+                    Some(host) => format!("{host}/{}", repository.name),
+                    None => repository.name.clone(),
+                };
+                let mut line = match &repository.remote {
+                    Some(remote) => format!("{remote}\t{location}"),
+                    None => location,
+                };
+                if let Some(account) = &repository.account {
+                    line.push_str(&t!("account.suffix", account = account));
                 }
+                out.event(
+                    Some(&line),
+                    &output::Event::Remote {
+                        host: repository.host().to_string(),
+                        name: repository.remote,
+                        repository: repository.name,
+                        account: repository.account,
+                    },
+                )?;
             }
         }
-        cli::parser::Command::RemoteAdd { repo } => {
+        cli::parser::Command::RemoteAdd { name, target } => {
+            let (repo, host, name) = match target {
+                cli::parser::RemoteTarget::Repository { name: repo, host } => (repo, host, name),
+                cli::parser::RemoteTarget::GitRemote(git_remote_name) => {
+                    let remote = storage::project_root()
+                        .and_then(|root| git_remote_repository(&root, &git_remote_name))
+                        .map_err(|_| anyhow!(t!("remote.invalid_format")))?;
+                    (
+                        remote.name(),
+                        remote.enterprise_host(),
+                        name.or(Some(git_remote_name)),
+                    )
+                }
+            };
+
             let config_storage = match storage::LocalConfigStorage::new() {
                 Ok(storage) => storage,
                 Err(e) => {
-                    return Err(anyhow::anyhow!("Error initializing config storage: {e}"));
+                    return Err(anyhow!(t!("storage.init_failed", error = e)));
                 }
             };
 
-            let mut config_map =
-                storage::ConfigStorage::load_config(&config_storage).unwrap_or_default();
+            let mut config_map = storage::ConfigStorage::load_config(&config_storage)
+                .map_err(|e| anyhow!(t!("config.load_project_failed", error = e)))?;
+
+            if let Some(account) = &global_options.account {
+                storage::validate_account_name(account)?;
+            }
+            if let Some(name) = &name
+                && config::repositories(&config_map)?
+                    .iter()
+                    .any(|r| r.remote.as_deref() == Some(name.as_str()) && r.name != repo)
+            {
+                return Err(anyhow!(t!("remote.exists", name = name)));
+            }
 
             let repo_list_val = config_map
                 .entry(config::ConfigKey::Repositories)
                 .or_insert_with(|| serde_json::json!([]));
 
             if let Some(repos_array) = repo_list_val.as_array_mut() {
-                let new_repo_val = serde_json::json!(repo.clone());
-                if !repos_array.contains(&new_repo_val) {
+                let existing = repos_array.iter().position(|value| {
+                    config::Repository::from_value(value).is_some_and(|r| r.name == repo)
+                });
+                if let Some(pos) = existing {
+                    // `--account` or a name on an existing repository changes it
+                    let mut repository = config::Repository::from_value(&repos_array[pos])
+                        .ok_or_else(|| anyhow!(t!("config.invalid_repository")))?;
+                    repository.account = global_options.account.clone().or(repository.account);
+                    repository.remote = name.or(repository.remote);
+                    repository.host = host.or(repository.host);
+                    let updated = repository.to_value();
+                    let message = if repos_array[pos] != updated {
+                        repos_array[pos] = updated;
+                        storage::ConfigStorage::save_config(&config_storage, &config_map)
+                            .map_err(|e| anyhow!(t!("config.save_project_failed", error = e)))?;
+                        output::status(output::Mark::Success, &t!("remote.updated", repo = repo))
+                    } else {
+                        output::status(
+                            output::Mark::Info,
+                            &t!("remote.already_configured", repo = repo),
+                        )
+                    };
+                    out.event(Some(&message), &remote_added_event(repository))?;
+                } else {
+                    let new_repo = config::Repository {
+                        name: repo.clone(),
+                        account: global_options.account.clone(),
+                        remote: name,
+                        host,
+                    };
+                    let new_repo_val = new_repo.to_value();
+
                     let client = reqwest::Client::builder()
                         .timeout(std::time::Duration::from_secs(30))
                         .build()?;
 
-                    let token_storage = storage::FileTokenStorage::new();
-                    let token = storage::TokenStorage::load(&token_storage).unwrap_or(None);
+                    let token = resolve_repository_token(global_options, &new_repo)
+                        .await
+                        .unwrap_or(None)
+                        .map(|resolved| resolved.token);
 
-                    match check_repo_exists(&client, &repo, token.as_deref()).await {
+                    match check_repo_exists(&client, &new_repo, token.as_deref()).await {
                         Ok(true) => {
                             repos_array.push(new_repo_val);
                             storage::ConfigStorage::save_config(&config_storage, &config_map)
-                                .map_err(|e| anyhow::anyhow!("Error saving project config: {e}"))?;
+                                .map_err(|e| {
+                                    anyhow!(t!("config.save_project_failed", error = e))
+                                })?;
+                            out.event(
+                                Some(&output::status(
+                                    output::Mark::Success,
+                                    &t!("remote.added", repo = repo),
+                                )),
+                                &remote_added_event(new_repo),
+                            )?;
                         }
                         Ok(false) => {
-                            return Err(anyhow!("Repository {repo} not found or not accessible."));
+                            return Err(error::Error::RepositoryNotAccessible(repo).into());
                         }
                         Err(e) => {
-                            return Err(anyhow!("Failed to check repository {repo}: {e}"));
+                            return Err(anyhow!(t!("remote.check_failed", repo = repo, error = e)));
                         }
                     }
                 }
             } else {
-                return Err(anyhow::anyhow!(
-                    "'repositories' key in config is not an array. Cannot add repository."
-                ));
+                return Err(anyhow!(t!("remote.not_an_array")));
             }
         }
         cli::parser::Command::RemoteRemove { repo } => {
             let config_storage = match storage::LocalConfigStorage::new() {
                 Ok(storage) => storage,
                 Err(e) => {
-                    return Err(anyhow::anyhow!("Error initializing config storage: {e}"));
+                    return Err(anyhow!(t!("storage.init_failed", error = e)));
                 }
             };
 
-            let config_map =
-                storage::ConfigStorage::load_config(&config_storage).unwrap_or_default();
+            let config_map = storage::ConfigStorage::load_config(&config_storage)
+                .map_err(|e| anyhow!(t!("config.load_project_failed", error = e)))?;
 
-            let new_config = if let Some(serde_json::Value::Array(repos)) =
+            let mut removed = Vec::new();
+            if let Some(serde_json::Value::Array(repos)) =
                 config_map.get(&config::ConfigKey::Repositories)
             {
-                let repo_json = serde_json::json!(repo.clone());
-                let filtered_repos: Vec<serde_json::Value> =
-                    repos.iter().filter(|&r| r != &repo_json).cloned().collect();
+                let filtered_repos: Vec<serde_json::Value> = repos
+                    .iter()
+                    .filter(|value| {
+                        config::Repository::from_value(value).is_none_or(|r| !r.matches(&repo))
+                    })
+                    .cloned()
+                    .collect();
+                removed = repos
+                    .iter()
+                    .filter_map(config::Repository::from_value)
+                    .filter(|r| r.matches(&repo))
+                    .collect();
 
-                if filtered_repos.is_empty() {
-                    std::collections::HashMap::new()
-                } else {
+                if filtered_repos.len() != repos.len() {
                     let mut updates = std::collections::HashMap::new();
                     updates.insert(
                         config::ConfigKey::Repositories,
                         serde_json::json!(filtered_repos),
                     );
-                    config::update_config(&config_map, &updates)
+                    let new_config = config::update_config(&config_map, &updates);
+                    storage::ConfigStorage::save_config(&config_storage, &new_config)
+                        .map_err(|e| anyhow!(t!("config.save_project_failed", error = e)))?;
                 }
-            } else {
-                config_map
-            };
+            }
 
-            storage::ConfigStorage::save_config(&config_storage, &new_config)
-                .map_err(|e| anyhow::anyhow!("Error saving project config: {e}"))?;
+            if removed.is_empty() {
+                out.text(&output::status(
+                    output::Mark::Info,
+                    &t!("remote.not_configured", repo = repo),
+                ))?;
+            }
+            for repository in removed {
+                out.event(
+                    Some(&output::status(
+                        output::Mark::Success,
+                        &t!("remote.removed", repo = repository.name),
+                    )),
+                    &output::Event::RemoteRemoved {
+                        name: repository.remote,
+                        repository: repository.name,
+                    },
+                )?;
+            }
         }
-        cli::parser::Command::Push => {
-            let token_storage = storage::FileTokenStorage::new();
-            let token = match storage::TokenStorage::load(&token_storage)? {
-                Some(token) => token,
-                None => return Err(anyhow!("Authentication required")),
-            };
-
-            let config_storage = storage::LocalConfigStorage::new()
-                .map_err(|e| anyhow!("Failed to read project configuration: {e}"))?;
-
-            let config_map = storage::ConfigStorage::load_config(&config_storage)
-                .map_err(|e| anyhow!("Error loading project config: {e}"))?;
+        cli::parser::Command::Push { dry_run } => {
+            let config_map = load_config(global_options)?.values();
 
-            let repos = config_map
-                .get(&config::ConfigKey::Repositories)
-                .and_then(|v| v.as_array())
-                .ok_or_else(|| anyhow!("No repository configured"))?;
+            let repository = config::default_repository(&config_map)?;
+            let repo = repository.name.as_str();
 
-            if repos.is_empty() {
-                return Err(anyhow!("No repository configured"));
-            }
-
-            let repo = repos[0]
-                .as_str()
-                .ok_or_else(|| anyhow!("Invalid repository configuration"))?;
+            let token = match resolve_repository_token(global_options, &repository).await? {
+                Some(resolved) => resolved.token,
+                None => return Err(error::Error::AuthenticationRequired.into()),
+            };
 
-            let todo_content = std::fs::read_to_string("TODO.md")
-                .map_err(|_| anyhow!("TODO.md file not found"))?;
+            let todo_file = config::todo_file(&config_map);
+            let todo_path = storage::project_root()?.join(&todo_file);
+            let todo_format = todo::TodoFormat::from_path(&todo_path);
+            let todo_content = std::fs::read_to_string(&todo_path)
+                .map_err(|_| error::Error::TodoFileNotFound(todo_file.clone()))?;
 
-            let todo_items = markdown_parser::parse_todo_markdown(&todo_content)?;
+            let todo_items = todo_format.parse(&todo_content)?;
 
             let client = reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(30))
                 .build()?;
 
-            let github_issues = fetch_github_issues_async(&client, repo, &token).await?;
+            let github_issues = fetch_github_issues_async(&client, &repository, &token).await?;
+
+            let todo_items = github::push::add_recurring_items(
+                &todo_items,
+                &github_issues,
+                chrono::Local::now().date_naive(),
+            );
 
             let title_updates = github::push::calculate_title_updates_with_history(
                 &todo_items,
                 &github_issues,
-                |issue_number| fetch_issue_events_async(&client, repo, issue_number, &token),
+                |issue_number| fetch_issue_events_async(&client, &repository, issue_number, &token),
             )
             .await?;
 
             for issue_number in title_updates.stale_issues {
-                output::println(
-                    &format!(
-                        "Warning: issue #{issue_number} was renamed on GitHub; run `atat pull` to update TODO.md"
-                    ),
-                    &mut stdout_additional,
+                out.event(
+                    Some(&output::status(
+                        output::Mark::Warning,
+                        &t!(
+                            "push.renamed_on_github",
+                            number = issue_number,
+                            path = todo_file
+                        ),
+                    )),
+                    &output::Event::StaleTitle {
+                        repository: repo.to_string(),
+                        number: issue_number,
+                        changed: "github",
+                    },
                 )?;
             }
 
@@ -251,55 +886,45 @@ pub async fn run(
                 github::push::calculate_github_operations(&todo_items, &github_issues),
             );
 
-            let mut results = Vec::new();
-
-            for (todo_item, operation) in operations {
-                let result = match &operation {
-                    github::push::GitHubOperation::CreateIssue { title } => {
-                        create_github_issue(&client, repo, title, &token)
-                            .await
-                            .map(|issue_number| (todo_item.clone(), Some(issue_number)))
-                    }
-                    github::push::GitHubOperation::CloseIssue { number } => {
-                        close_github_issue(&client, repo, *number, &token)
-                            .await
-                            .map(|_| (todo_item.clone(), None))
-                    }
-                    github::push::GitHubOperation::RenameIssue { number, title } => {
-                        rename_github_issue(&client, repo, *number, title, &token)
-                            .await
-                            .map(|_| (todo_item.clone(), None))
-                    }
-                };
-
-                match (&result, &operation) {
-                    (Ok((_, Some(issue_number))), _) => {
-                        output::println(
-                            &format!("Created issue #{issue_number}: {}", todo_item.text),
-                            &mut stdout_additional,
-                        )?;
-                    }
-                    (Ok((_, None)), github::push::GitHubOperation::CloseIssue { number }) => {
-                        output::println(
-                            &format!("Closed issue #{number}"),
-                            &mut stdout_additional,
-                        )?;
-                    }
-                    (
-                        Ok((_, None)),
-                        github::push::GitHubOperation::RenameIssue { number, title },
-                    ) => {
-                        output::println(
-                            &format!("Renamed issue #{number}: {title}"),
-                            &mut stdout_additional,
-                        )?;
-                    }
-                    _ => {}
+            if dry_run {
+                for (todo_item, operation) in operations {
+                    let (line, action, number, title) = match operation {
+                        github::push::GitHubOperation::CreateIssue { title } => (
+                            t!("push.would_create", title = title),
+                            "create",
+                            None,
+                            title,
+                        ),
+                        github::push::GitHubOperation::CloseIssue { number } => (
+                            t!("push.would_close", number = number, title = todo_item.text),
+                            "close",
+                            Some(number),
+                            todo_item.text,
+                        ),
+                        github::push::GitHubOperation::RenameIssue { number, title } => (
+                            t!("push.would_rename", number = number, title = title),
+                            "rename",
+                            Some(number),
+                            title,
+                        ),
+                    };
+                    out.event(
+                        Some(&line),
+                        &output::Event::PlannedOperation {
+                            repository: repo.to_string(),
+                            action,
+                            number,
+                            title,
+                        },
+                    )?;
                 }
-
-                results.push(result);
+                return Ok(());
             }
 
+            let results =
+                apply_github_operations(&client, &repository, &token, &config_map, operations, out)
+                    .await?;
+
             let (successes, failures): (Vec<_>, Vec<_>) =
                 results.into_iter().partition(Result::is_ok);
 
@@ -316,57 +941,55 @@ pub async fn run(
                 }
             }
 
-            let updated_content = markdown_parser::serialize_todo_markdown(&updated_todo_items);
-            std::fs::write("TODO.md", updated_content)
-                .map_err(|e| anyhow!("Failed to write TODO.md: {e}"))?;
+            let updated_content = todo_format.serialize(&todo_content, &updated_todo_items);
+            std::fs::write(&todo_path, updated_content)
+                .map_err(|e| anyhow!(t!("file.write_failed", path = todo_file, error = e)))?;
 
             if let Some(Err(error)) = failures.into_iter().next() {
                 return Err(error);
             }
         }
-        cli::parser::Command::Clean { dry_run } => {
-            let token_storage = storage::FileTokenStorage::new();
-            let token = match storage::TokenStorage::load(&token_storage)? {
-                Some(token) => token,
-                None => return Err(anyhow!("Authentication required")),
-            };
-
-            let config_storage = storage::LocalConfigStorage::new()
-                .map_err(|e| anyhow!("Failed to read project configuration: {e}"))?;
+        cli::parser::Command::Clean { dry_run, issues } => {
+            let config_map = load_config(global_options)?.values();
 
-            let config_map = storage::ConfigStorage::load_config(&config_storage)
-                .map_err(|e| anyhow!("Error loading project config: {e}"))?;
-
-            let repos = config_map
-                .get(&config::ConfigKey::Repositories)
-                .and_then(|v| v.as_array())
-                .ok_or_else(|| anyhow!("No repository configured"))?;
-
-            if repos.is_empty() {
-                return Err(anyhow!("No repository configured"));
-            }
+            let repository = config::default_repository(&config_map)?;
 
-            let repo = repos[0]
-                .as_str()
-                .ok_or_else(|| anyhow!("Invalid repository configuration"))?;
+            let token = match resolve_repository_token(global_options, &repository).await? {
+                Some(resolved) => resolved.token,
+                None => return Err(error::Error::AuthenticationRequired.into()),
+            };
 
-            let todo_content = std::fs::read_to_string("TODO.md")
-                .map_err(|_| anyhow!("TODO.md file not found"))?;
+            let todo_file = config::todo_file(&config_map);
+            let todo_path = storage::project_root()?.join(&todo_file);
+            let todo_format = todo::TodoFormat::from_path(&todo_path);
+            let todo_content = std::fs::read_to_string(&todo_path)
+                .map_err(|_| error::Error::TodoFileNotFound(todo_file.clone()))?;
 
-            let todo_items = markdown_parser::parse_todo_markdown(&todo_content)?;
+            let todo_items = todo_format.parse(&todo_content)?;
 
             let candidates: Vec<crate::clean::CleanCandidate> = todo_items
                 .iter()
                 .filter_map(|item| crate::clean::CleanCandidate::try_from(item).ok())
+                .filter(|candidate| issues.is_empty() || issues.contains(&candidate.issue_number))
                 .collect();
 
             let client = reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(30))
                 .build()?;
 
-            let github_issues = fetch_github_issues_async(&client, repo, &token).await?;
+            let github_issues = fetch_github_issues_async(&client, &repository, &token).await?;
 
             let removable = crate::clean::find_removable_items(&candidates, &github_issues);
+            for item in &removable {
+                out.event(
+                    None,
+                    &output::Event::ItemRemoved {
+                        number: item.issue_number,
+                        text: item.text.clone(),
+                        dry_run,
+                    },
+                )?;
+            }
 
             if !dry_run {
                 let updated_items: Vec<_> = todo_items
@@ -381,99 +1004,1131 @@ pub async fn run(
                     .cloned()
                     .collect();
 
-                let updated_content = markdown_parser::serialize_todo_markdown(&updated_items);
-                std::fs::write("TODO.md", updated_content)
-                    .map_err(|e| anyhow!("Failed to write TODO.md: {e}"))?;
+                let updated_content = todo_format.serialize(&todo_content, &updated_items);
+                std::fs::write(&todo_path, updated_content)
+                    .map_err(|e| anyhow!(t!("file.write_failed", path = todo_file, error = e)))?;
             }
         }
-        cli::parser::Command::Pull => {
-            let token_storage = storage::FileTokenStorage::new();
-            let token = match storage::TokenStorage::load(&token_storage)? {
-                Some(token) => token,
-                None => return Err(anyhow!("Authentication required")),
+        cli::parser::Command::Scan { dry_run } => {
+            let root = storage::project_root()?;
+            let comments = comment_scanner::scan_directory(&root)?;
+
+            if dry_run {
+                for comment in &comments {
+                    out.event(
+                        Some(&format!(
+                            "{}:{}: {}: {}",
+                            comment.path.display(),
+                            comment.line + 1,
+                            comment.keyword,
+                            comment.text
+                        )),
+                        &output::Event::Comment {
+                            path: comment.path.display().to_string(),
+                            line: comment.line + 1,
+                            keyword: comment.keyword.to_string(),
+                            text: comment.text.clone(),
+                        },
+                    )?;
+                }
+                return Ok(());
+            }
+
+            let config_map = load_config(global_options)?.values();
+
+            let repository = config::default_repository(&config_map)?;
+
+            let token = match resolve_repository_token(global_options, &repository).await? {
+                Some(resolved) => resolved.token,
+                None => return Err(error::Error::AuthenticationRequired.into()),
             };
 
-            let config_storage = storage::LocalConfigStorage::new()
-                .map_err(|e| anyhow!("Failed to read project configuration: {e}"))?;
+            let tracked_path = root
+                .join(config::PROJECT_CONFIG_DIR)
+                .join(config::COMMENT_ISSUES_FILENAME);
+            let tracked_issues = comment_scanner::parse_tracked_issues(
+                &std::fs::read(&tracked_path).unwrap_or_default(),
+            )?;
 
-            let config_map = storage::ConfigStorage::load_config(&config_storage)
-                .map_err(|e| anyhow!("Error loading project config: {e}"))?;
+            let todo_items = comment_scanner::comment_todo_items(&comments, &tracked_issues);
+
+            let client = reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()?;
 
-            let repos = config_map
-                .get(&config::ConfigKey::Repositories)
-                .and_then(|v| v.as_array())
-                .ok_or_else(|| anyhow!("No repository configured"))?;
+            let github_issues = fetch_github_issues_async(&client, &repository, &token).await?;
+
+            let operations = github::push::calculate_github_operations(&todo_items, &github_issues);
+
+            let results =
+                apply_github_operations(&client, &repository, &token, &config_map, operations, out)
+                    .await?;
+
+            let (successes, failures): (Vec<_>, Vec<_>) =
+                results.into_iter().partition(Result::is_ok);
+
+            let mut referenced_comments = comments.clone();
+            for success in successes {
+                if let Ok((todo_item, Some(issue_number))) = success
+                    && let Some(comment) = referenced_comments
+                        .iter_mut()
+                        .find(|c| c.issue_number.is_none() && c.text == todo_item.text)
+                {
+                    comment.issue_number = Some(issue_number);
+                    let path = root.join(&comment.path);
+                    let content = std::fs::read_to_string(&path).map_err(|e| {
+                        anyhow!(t!(
+                            "file.read_failed",
+                            path = comment.path.display(),
+                            error = e
+                        ))
+                    })?;
+                    let updated_content = comment_scanner::insert_issue_reference(
+                        &comment.path,
+                        &content,
+                        comment.line,
+                        issue_number,
+                    );
+                    std::fs::write(&path, updated_content).map_err(|e| {
+                        anyhow!(t!(
+                            "file.write_failed",
+                            path = comment.path.display(),
+                            error = e
+                        ))
+                    })?;
+                }
+            }
 
-            if repos.is_empty() {
-                return Err(anyhow!("No repository configured"));
+            let mut tracked: Vec<u64> = referenced_comments
+                .iter()
+                .filter_map(|comment| comment.issue_number)
+                .collect();
+            if !failures.is_empty() {
+                tracked.extend(tracked_issues);
             }
+            tracked.sort_unstable();
+            tracked.dedup();
+
+            std::fs::create_dir_all(root.join(config::PROJECT_CONFIG_DIR))
+                .map_err(|e| anyhow!(t!("config.create_dir_failed", error = e)))?;
+            std::fs::write(&tracked_path, serde_json::to_string(&tracked)?).map_err(|e| {
+                anyhow!(t!(
+                    "file.write_failed",
+                    path = tracked_path.display(),
+                    error = e
+                ))
+            })?;
 
-            let repo = repos[0]
-                .as_str()
-                .ok_or_else(|| anyhow!("Invalid repository configuration"))?;
+            if let Some(Err(error)) = failures.into_iter().next() {
+                return Err(error);
+            }
+        }
+        cli::parser::Command::Pull => pull(global_options, out).await?,
+        cli::parser::Command::Add {
+            text,
+            section,
+            labels,
+            assignees,
+            body,
+        } => {
+            let config_map = load_config(global_options)?.values();
+
+            let repository = config::default_repository(&config_map)?;
+
+            let token = match resolve_repository_token(global_options, &repository).await? {
+                Some(resolved) => resolved.token,
+                None => return Err(error::Error::AuthenticationRequired.into()),
+            };
 
-            let todo_content = std::fs::read_to_string("TODO.md")
-                .map_err(|_| anyhow!("TODO.md file not found"))?;
+            let todo_file = config::todo_file(&config_map);
+            let todo_path = storage::project_root()?.join(&todo_file);
+            let todo_format = todo::TodoFormat::from_path(&todo_path);
+            let todo_content = std::fs::read_to_string(&todo_path)
+                .map_err(|_| error::Error::TodoFileNotFound(todo_file.clone()))?;
 
-            let todo_items = markdown_parser::parse_todo_markdown(&todo_content)?;
+            let mut todo_item = todo_format.new_item(&text);
+            if todo_item.text.is_empty() {
+                return Err(anyhow!(t!("add.empty_text")));
+            }
+            todo_item.labels = labels;
+            todo_item.assignees = assignees;
+            todo_item.body = body;
+
+            // The task is kept even if its issue cannot be created, for `atat push` to retry
+            let section = section.as_deref();
+            std::fs::write(
+                &todo_path,
+                todo_format.insert(&todo_content, section, &todo_item),
+            )
+            .map_err(|e| anyhow!(t!("file.write_failed", path = todo_file, error = e)))?;
 
             let client = reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(30))
                 .build()?;
-
-            let github_issues = fetch_github_issues_async(&client, repo, &token).await?;
-
-            let title_synchronization = github::pull::synchronize_titles_with_history(
-                &todo_items,
-                &github_issues,
-                |issue_number| fetch_issue_events_async(&client, repo, issue_number, &token),
+            let operation = github::push::GitHubOperation::CreateIssue {
+                title: todo_item.text.clone(),
+            };
+            let results = apply_github_operations(
+                &client,
+                &repository,
+                &token,
+                &config_map,
+                [(todo_item.clone(), operation)],
+                out,
             )
             .await?;
+            todo_item.issue_number = match results.into_iter().next() {
+                Some(Ok((_, Some(issue_number)))) => Some(issue_number),
+                Some(Err(e)) => {
+                    return Err(anyhow!(t!(
+                        "add.create_failed",
+                        path = todo_file,
+                        error = e
+                    )));
+                }
+                _ => return Err(anyhow!(t!("add.no_issue_number", path = todo_file))),
+            };
 
-            for issue_number in title_synchronization.locally_edited_issues {
-                output::println(
-                    &format!(
-                        "Warning: TODO.md text for issue #{issue_number} was changed locally; run `atat push` to update the issue title"
-                    ),
-                    &mut stdout_additional,
+            std::fs::write(
+                &todo_path,
+                todo_format.insert(&todo_content, section, &todo_item),
+            )
+            .map_err(|e| anyhow!(t!("file.write_failed", path = todo_file, error = e)))?;
+        }
+        cli::parser::Command::Completions { shell } => {
+            let shells = clap_complete::env::Shells::builtins();
+            let completer = shells
+                .completer(&shell.to_string())
+                .ok_or_else(|| anyhow!(t!("completions.unsupported", shell = shell)))?;
+            let mut script = Vec::new();
+            completer.write_registration(
+                cli::completion::COMPLETE_VAR,
+                "atat",
+                "atat",
+                "atat",
+                &mut script,
+            )?;
+            out.document(String::from_utf8_lossy(&script).trim())?;
+        }
+        cli::parser::Command::Man { output_dir } => match output_dir {
+            Some(output_dir) => {
+                std::fs::create_dir_all(&output_dir).map_err(|e| {
+                    anyhow!(t!(
+                        "file.create_dir_failed",
+                        path = output_dir.display(),
+                        error = e
+                    ))
+                })?;
+                clap_mangen::generate_to(cli::parser::command(), &output_dir)
+                    .map_err(|e| anyhow!(t!("man.write_failed", error = e)))?;
+            }
+            None => {
+                let mut page = Vec::new();
+                clap_mangen::Man::new(cli::parser::command()).render(&mut page)?;
+                out.document(String::from_utf8_lossy(&page).trim_end())?;
+            }
+        },
+        cli::parser::Command::Help => {
+            out.document(cli::parser::help().trim_end())?;
+        }
+    }
+    Ok(())
+}
+
+/// GitHub repository of the git remote `remote` of the git repository containing `root`
+fn git_remote_repository(
+    root: &std::path::Path,
+    remote: &str,
+) -> anyhow::Result<git_remote::RemoteRepository> {
+    // The project root may be a subdirectory of the git repository
+    let git_config_path = root
+        .ancestors()
+        .find_map(git_remote::git_config_path)
+        .ok_or_else(|| anyhow!(t!("git.not_a_repository")))?;
+    let git_config = std::fs::read_to_string(&git_config_path).map_err(|e| {
+        anyhow!(t!(
+            "file.read_failed",
+            path = git_config_path.display(),
+            error = e
+        ))
+    })?;
+    let url = git_remote::remote_url(&git_config, remote)
+        .ok_or_else(|| anyhow!(t!("git.no_remote", remote = remote)))?;
+    git_remote::parse_remote_url(&url)
+        .ok_or_else(|| anyhow!(t!("git.no_repository_in_url", remote = remote, url = url)))
+}
+
+fn remote_added_event(repository: config::Repository) -> output::Event {
+    output::Event::RemoteAdded {
+        host: repository.host().to_string(),
+        name: repository.remote,
+        repository: repository.name,
+        account: repository.account,
+    }
+}
+
+/// Ask a yes/no question on the terminal, defaulting to yes.
+///
+/// The question goes to stderr so that it stays out of the results. Without a terminal,
+/// or with JSON output that a program reads, nobody is asked and the answer is no.
+fn confirm(question: &str, out: &output::Output<'_>) -> anyhow::Result<bool> {
+    if out.is_json() || !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        return Ok(false);
+    }
+    eprint!("{question}");
+    std::io::Write::flush(&mut std::io::stderr())?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_lowercase().as_str(),
+        "" | "y" | "yes"
+    ))
+}
+
+/// Update the task file from the GitHub issues of the default repository.
+async fn pull(
+    global_options: &cli::parser::GlobalOptions,
+    out: &mut output::Output<'_>,
+) -> anyhow::Result<()> {
+    let config_map = load_config(global_options)?.values();
+
+    let repository = config::default_repository(&config_map)?;
+    let repo = repository.name.as_str();
+
+    let token = match resolve_repository_token(global_options, &repository).await? {
+        Some(resolved) => resolved.token,
+        None => return Err(error::Error::AuthenticationRequired.into()),
+    };
+
+    let todo_file = config::todo_file(&config_map);
+    let todo_path = storage::project_root()?.join(&todo_file);
+    let todo_format = todo::TodoFormat::from_path(&todo_path);
+    let todo_content = std::fs::read_to_string(&todo_path)
+        .map_err(|_| error::Error::TodoFileNotFound(todo_file.clone()))?;
+
+    let todo_items = todo_format.parse(&todo_content)?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+
+    let github_issues = fetch_github_issues_async(&client, &repository, &token).await?;
+
+    let title_synchronization = github::pull::synchronize_titles_with_history(
+        &todo_items,
+        &github_issues,
+        |issue_number| fetch_issue_events_async(&client, &repository, issue_number, &token),
+    )
+    .await?;
+
+    for issue_number in title_synchronization.locally_edited_issues {
+        out.event(
+            Some(&output::status(
+                output::Mark::Warning,
+                &t!(
+                    "pull.changed_locally",
+                    path = todo_file,
+                    number = issue_number
+                ),
+            )),
+            &output::Event::StaleTitle {
+                repository: repo.to_string(),
+                number: issue_number,
+                changed: "local",
+            },
+        )?;
+    }
+
+    let mut updated_todo_items =
+        github::pull::synchronize_with_github_issues(&title_synchronization.items, &github_issues);
+
+    let uses_tasks_fields = || todo_items.iter().any(|item| !item.metadata.is_empty());
+    if config::obsidian_tasks(&config_map).unwrap_or_else(uses_tasks_fields) {
+        updated_todo_items =
+            github::pull::stamp_completion_dates(&todo_items, &updated_todo_items, &github_issues);
+    }
+
+    let updated_content = todo_format.serialize(&todo_content, &updated_todo_items);
+    std::fs::write(&todo_path, updated_content)
+        .map_err(|e| anyhow!(t!("file.write_failed", path = todo_file, error = e)))?;
+    Ok(())
+}
+
+/// Account to use: `--account`, then the repository's account, then the default account.
+fn selected_account(
+    global_options: &cli::parser::GlobalOptions,
+    repository_account: Option<&str>,
+) -> anyhow::Result<String> {
+    let account = global_options
+        .account
+        .as_deref()
+        .or(repository_account)
+        .unwrap_or(storage::DEFAULT_ACCOUNT);
+    storage::validate_account_name(account)?;
+    Ok(account.to_string())
+}
+
+/// The default account followed by the named accounts
+fn all_accounts() -> anyhow::Result<Vec<String>> {
+    let mut accounts = vec![storage::DEFAULT_ACCOUNT.to_string()];
+    accounts.extend(storage::AccountRegistry::new()?.list()?);
+    Ok(accounts)
+}
+
+/// Resolve the token from the `--token` flag, the environment, the `gh` CLI or `atat login`.
+///
+/// The stored token is that of the selected account (see `selected_account`). A named
+/// account that is not logged in is an error rather than falling back to another token.
+async fn resolve_token(
+    global_options: &cli::parser::GlobalOptions,
+    repository_account: Option<&str>,
+) -> anyhow::Result<Option<token_source::ResolvedToken>> {
+    let account = selected_account(global_options, repository_account)?;
+    let resolved = token_source::resolve_token(
+        global_options.token.as_deref(),
+        &account,
+        |name| std::env::var(name).ok(),
+        || gh_auth_token(cli::parser::DEFAULT_HOSTNAME),
+        || load_stored_token(global_options, &account),
+    )
+    .await?;
+    if let Some(resolved) = &resolved
+        && resolved.source == token_source::TokenSource::Stored
+    {
+        STORED_TOKENS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(StoredToken {
+                issued: resolved.token.clone(),
+                current: resolved.token.clone(),
+                storage: token_storage(global_options, &account)?,
+            });
+    }
+    if resolved.is_none() && account != storage::DEFAULT_ACCOUNT {
+        return Err(error::Error::NotLoggedIn(format!(
+            "{}{}",
+            cli::parser::DEFAULT_HOSTNAME,
+            t!("account.suffix", account = account)
+        ))
+        .into());
+    }
+    Ok(resolved)
+}
+
+/// Resolve the token for `repository`: as `resolve_token` does on github.com, and from
+/// the flag, the enterprise variables or the `gh` CLI on a GitHub Enterprise Server.
+async fn resolve_repository_token(
+    global_options: &cli::parser::GlobalOptions,
+    repository: &config::Repository,
+) -> anyhow::Result<Option<token_source::ResolvedToken>> {
+    let host = repository.host();
+    if host == cli::parser::DEFAULT_HOSTNAME {
+        return resolve_token(global_options, repository.account.as_deref()).await;
+    }
+    token_source::resolve_enterprise_token(
+        global_options.token.as_deref(),
+        |name| std::env::var(name).ok(),
+        || gh_auth_token(host),
+    )
+    .map(Some)
+    .ok_or_else(|| error::Error::EnterpriseTokenRequired(host.to_string()).into())
+}
+
+/// Configuration from the global and project config files, `ATAT_*` variables and
+/// `--config` flags, each overriding the ones before it
+fn load_config(
+    global_options: &cli::parser::GlobalOptions,
+) -> anyhow::Result<config::LayeredConfig> {
+    let mut layered = config::LayeredConfig::new();
+
+    // Without XDG_CONFIG_HOME or HOME there is no global config
+    if let Ok(global_storage) = storage::GlobalConfigStorage::new() {
+        let values = storage::ConfigStorage::load_config(&global_storage)
+            .map_err(|e| anyhow!(t!("config.load_global_failed", error = e)))?;
+        layered.push_layer(
+            config::ConfigOrigin::Global(global_storage.path().to_path_buf()),
+            values,
+        );
+    }
+
+    let project_storage = storage::LocalConfigStorage::new()
+        .map_err(|e| anyhow!(t!("config.read_project_failed", error = e)))?;
+    let values = storage::ConfigStorage::load_config(&project_storage)
+        .map_err(|e| anyhow!(t!("config.load_project_failed", error = e)))?;
+    layered.push_layer(
+        config::ConfigOrigin::Project(project_storage.path().to_path_buf()),
+        values,
+    );
+
+    for (key, value) in config::env_overrides(|name| std::env::var(name).ok())? {
+        layered.push_layer(
+            config::ConfigOrigin::Env(key.env_var()),
+            std::collections::HashMap::from([(key, value)]),
+        );
+    }
+    layered.push_layer(
+        config::ConfigOrigin::Flag,
+        config::flag_overrides(&global_options.config)?,
+    );
+    Ok(layered)
+}
+
+fn config_key(name: &str) -> anyhow::Result<config::ConfigKey> {
+    config::ConfigKey::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = config::ConfigKey::all()
+            .iter()
+            .map(config::ConfigKey::as_str)
+            .collect();
+        anyhow!(t!(
+            "config.unknown_key",
+            key = name,
+            keys = names.join(", ")
+        ))
+    })
+}
+
+/// The config file `config set`/`unset` write: the global one with `--global`, else the project's
+fn config_storage(global: bool) -> anyhow::Result<Box<dyn storage::ConfigStorage>> {
+    if global {
+        Ok(Box::new(storage::GlobalConfigStorage::new()?))
+    } else {
+        Ok(Box::new(storage::LocalConfigStorage::new()?))
+    }
+}
+
+/// Storage for an account's `atat login` token: the configured credential helper, or a token file.
+fn token_storage(
+    global_options: &cli::parser::GlobalOptions,
+    account: &str,
+) -> anyhow::Result<Box<dyn storage::TokenStorage + Send>> {
+    let config_map = load_config(global_options)?.values();
+
+    match config::credential_helper(&config_map) {
+        Some(helper) => Ok(Box::new(storage::CredentialHelperTokenStorage::new(
+            &helper,
+            cli::parser::DEFAULT_HOSTNAME,
+            account,
+        )?)),
+        None => Ok(Box::new(storage::FileTokenStorage::for_account(account)?)),
+    }
+}
+
+/// Login name of the user the token belongs to
+async fn fetch_login(client: &reqwest::Client, host: &str, token: &str) -> anyhow::Result<String> {
+    let response = send_authorized(
+        client
+            .get(format!("{}/user", github::api_base_url(host)))
+            .header("Accept", "application/json")
+            .header("User-Agent", "atat-cli"),
+        token,
+    )
+    .await?;
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(anyhow!(t!("token.invalid")));
+    }
+    if !response.status().is_success() {
+        return Err(anyhow!(t!(
+            "github.request_failed",
+            status = response.status()
+        )));
+    }
+    let text = response.text().await?;
+    whoami::extract_login_from_user_response(&text).map_err(|e| anyhow!(e))
+}
+
+/// Print `auth status` checks, returning whether any of them failed
+fn print_checks(
+    checks: &[auth_status::Check],
+    account: Option<&str>,
+    repository: Option<&str>,
+    out: &mut output::Output<'_>,
+) -> std::io::Result<bool> {
+    for check in checks {
+        out.event(
+            Some(&check.lines().join("\n")),
+            &output::Event::Check {
+                account: account.map(str::to_string),
+                repository: repository.map(str::to_string),
+                status: check.status.as_str(),
+                message: check.message.clone(),
+                fix: check.fix.clone(),
+            },
+        )?;
+    }
+    Ok(checks
+        .iter()
+        .any(|check| check.status == auth_status::Status::Error))
+}
+
+/// Credentials saved by `atat login` for the account, without refreshing them
+fn stored_credentials(
+    global_options: &cli::parser::GlobalOptions,
+    account: &str,
+) -> anyhow::Result<Option<auth::Credentials>> {
+    let storage = token_storage(global_options, account)?;
+    storage::TokenStorage::load(storage.as_ref())?
+        .map(|content| auth::Credentials::parse(&content).map_err(|e| anyhow!(e)))
+        .transpose()
+}
+
+/// What the token may do in the repository
+async fn fetch_repo_access(
+    client: &reqwest::Client,
+    repository: &config::Repository,
+    token: &str,
+) -> anyhow::Result<auth_status::RepoAccess> {
+    let response = send_authorized(
+        client
+            .get(repository.api_url())
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "atat-cli"),
+        token,
+    )
+    .await?;
+    match response.status() {
+        reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::FORBIDDEN => {
+            Ok(auth_status::RepoAccess::NotFound)
+        }
+        status if status.is_success() => {
+            let text = response.text().await?;
+            auth_status::parse_repo_access(&text).map_err(|e| anyhow!(e))
+        }
+        status => Err(anyhow!(t!(
+            "github.repository_check_failed",
+            repo = repository.name,
+            status = status
+        ))),
+    }
+}
+
+/// atat's GitHub App installations the user can access
+async fn fetch_installations(
+    client: &reqwest::Client,
+    token: &str,
+) -> anyhow::Result<Vec<auth::Installation>> {
+    let response = send_authorized(
+        client
+            .get(endpoints::USER_INSTALLATIONS)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "atat-cli"),
+        token,
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(anyhow!(t!(
+            "github.request_failed",
+            status = response.status()
+        )));
+    }
+    let text = response.text().await?;
+    auth::parse_installations(&text).map_err(|e| anyhow!(e))
+}
+
+/// Open `url` in the browser once the user presses Enter.
+///
+/// Reads stdin on a detached thread so polling continues meanwhile and an
+/// unanswered prompt does not keep the process alive after login finishes.
+fn offer_browser(url: String) {
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        if std::io::stdin()
+            .read_line(&mut line)
+            .is_ok_and(|read| read > 0)
+            && let Err(e) = open_browser(&url)
+        {
+            eprintln!("{}", t!("login.browser_failed", error = e));
+        }
+    });
+}
+
+fn open_browser(url: &str) -> std::io::Result<()> {
+    let mut command = match std::env::var("BROWSER") {
+        Ok(browser) if !browser.trim().is_empty() => std::process::Command::new(browser.trim()),
+        _ if cfg!(target_os = "macos") => std::process::Command::new("open"),
+        _ if cfg!(windows) => {
+            let mut command = std::process::Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        }
+        _ => std::process::Command::new("xdg-open"),
+    };
+    command
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(drop)
+}
+
+/// Load an account's token saved by `atat login`, refreshing it first if it is about to expire.
+async fn load_stored_token(
+    global_options: &cli::parser::GlobalOptions,
+    account: &str,
+) -> anyhow::Result<Option<String>> {
+    let storage = token_storage(global_options, account)?;
+    let Some(content) = storage::TokenStorage::load(storage.as_ref())? else {
+        return Ok(None);
+    };
+    let credentials = auth::Credentials::parse(&content).map_err(|e| anyhow!(e))?;
+
+    let now = chrono::Utc::now();
+    if !credentials.is_expiring(now) {
+        return Ok(Some(credentials.access_token));
+    }
+
+    let refreshed = match credentials.usable_refresh_token(now) {
+        Some(refresh_token) => refresh_access_token(refresh_token).await,
+        None => Err(anyhow!(t!("token.no_refresh_token"))),
+    };
+    match refreshed {
+        Ok(refreshed) => {
+            anyhow::Context::context(
+                storage::TokenStorage::save(storage.as_ref(), &refreshed.to_json()),
+                t!("token.save_failed"),
+            )?;
+            Ok(Some(refreshed.access_token))
+        }
+        Err(e) if !credentials.is_expired(now) => {
+            log::warn!("Could not refresh the access token, using it until it expires: {e:#}");
+            Ok(Some(credentials.access_token))
+        }
+        Err(e) => Err(anyhow!(t!("token.refresh_expired", error = e))),
+    }
+}
+
+/// A token saved by `atat login` that was handed out in this run
+struct StoredToken {
+    /// Token the command was given
+    issued: String,
+    /// Token to use now, replacing `issued` once it has been refreshed
+    current: String,
+    storage: Box<dyn storage::TokenStorage + Send>,
+}
+
+/// Stored tokens in use, so that a token GitHub rejects can be refreshed and retried
+static STORED_TOKENS: std::sync::Mutex<Vec<StoredToken>> = std::sync::Mutex::new(Vec::new());
+
+/// Send a request authorized with `token`.
+///
+/// If GitHub rejects a token saved by `atat login` (it was revoked or expired
+/// early), the token is refreshed and the request is sent once more. Later
+/// requests given the old token use the refreshed one.
+async fn send_authorized(
+    request: reqwest::RequestBuilder,
+    token: &str,
+) -> anyhow::Result<reqwest::Response> {
+    let token = STORED_TOKENS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .iter()
+        .find(|stored| stored.issued == token)
+        .map_or_else(|| token.to_string(), |stored| stored.current.clone());
+
+    let retry = request.try_clone();
+    let response = logging::send(request.bearer_auth(&token)).await?;
+    if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+        return Ok(response);
+    }
+    let Some(retry) = retry else {
+        return Ok(response);
+    };
+    match refresh_rejected_token(&token).await {
+        Ok(Some(refreshed)) => Ok(logging::send(retry.bearer_auth(refreshed)).await?),
+        Ok(None) => Ok(response),
+        Err(e) => {
+            log::warn!("Could not refresh the access token GitHub rejected: {e:#}");
+            Ok(response)
+        }
+    }
+}
+
+/// Refresh the stored token `token` after GitHub rejected it.
+///
+/// Returns `None` if the token was not saved by `atat login`.
+async fn refresh_rejected_token(token: &str) -> anyhow::Result<Option<String>> {
+    let content = {
+        let stored_tokens = STORED_TOKENS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Some(stored) = stored_tokens.iter().find(|stored| stored.current == token) else {
+            return Ok(None);
+        };
+        storage::TokenStorage::load(stored.storage.as_ref())?
+    };
+    let Some(content) = content else {
+        return Ok(None);
+    };
+    let credentials = auth::Credentials::parse(&content).map_err(|e| anyhow!(e))?;
+    let refresh_token = credentials
+        .usable_refresh_token(chrono::Utc::now())
+        .ok_or_else(|| anyhow!(t!("token.no_refresh_token")))?;
+    let refreshed = refresh_access_token(refresh_token).await?;
+
+    let mut stored_tokens = STORED_TOKENS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let Some(stored) = stored_tokens
+        .iter_mut()
+        .find(|stored| stored.current == token)
+    else {
+        return Ok(None);
+    };
+    anyhow::Context::context(
+        storage::TokenStorage::save(stored.storage.as_ref(), &refreshed.to_json()),
+        t!("token.save_failed"),
+    )?;
+    stored.current = refreshed.access_token.clone();
+    Ok(Some(refreshed.access_token))
+}
+
+/// Exchange a refresh token for a new access token and refresh token.
+async fn refresh_access_token(refresh_token: &str) -> anyhow::Result<auth::Credentials> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+
+    let mut query = vec![
+        ("client_id", CLIENT_ID),
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+    ];
+    // Only needed if the app requires the secret for refreshing; device flow apps do not
+    if let Some(client_secret) = CLIENT_SECRET {
+        query.push(("client_secret", client_secret));
+    }
+
+    let response = logging::send(
+        client
+            .post(endpoints::ACCESS_TOKEN)
+            .header("Accept", "application/json")
+            .query(&query),
+    )
+    .await?;
+    if !response.status().is_success() {
+        return Err(anyhow!(t!(
+            "token.refresh_failed",
+            error = format!("HTTP {}", response.status())
+        )));
+    }
+
+    let token_response = response.json::<auth::AccessTokenResponse>().await?;
+    if let Some(error) = &token_response.error {
+        return Err(anyhow!(t!(
+            "token.refresh_failed",
+            error = token_response.error_description.as_deref().unwrap_or(error)
+        )));
+    }
+    auth::Credentials::from_response(&token_response, chrono::Utc::now())
+        .ok_or_else(|| anyhow!(t!("token.refresh_failed", error = t!("github.no_token"))))
+}
+
+/// Revoke the app's authorization of the user the token belongs to, invalidating the token.
+///
+/// GitHub authenticates this call with the app's client ID and secret.
+async fn revoke_authorization(client_secret: &str, access_token: &str) -> anyhow::Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+
+    let response = logging::send(
+        client
+            .delete(format!("{}/{CLIENT_ID}/grant", endpoints::APPLICATIONS))
+            .basic_auth(CLIENT_ID, Some(client_secret))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "atat-cli")
+            .json(&serde_json::json!({ "access_token": access_token })),
+    )
+    .await?;
+    // Not found: the token is already invalid, so there is nothing left to revoke
+    if response.status().is_success() || response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(());
+    }
+    Err(anyhow!(t!(
+        "logout.revoke_failed",
+        error = format!("HTTP {}", response.status())
+    )))
+}
+
+/// Token of a `gh` CLI logged in to `host`, if `gh` is installed and authenticated.
+fn gh_auth_token(host: &str) -> Option<String> {
+    let output = std::process::Command::new("gh")
+        .args(["auth", "token", "--hostname", host])
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+async fn apply_github_operations(
+    client: &reqwest::Client,
+    repository: &config::Repository,
+    token: &str,
+    config_map: &std::collections::HashMap<config::ConfigKey, serde_json::Value>,
+    operations: impl IntoIterator<Item = (todo::TodoItem, github::push::GitHubOperation)>,
+    out: &mut output::Output<'_>,
+) -> anyhow::Result<Vec<anyhow::Result<(todo::TodoItem, Option<u64>)>>> {
+    let mut results = Vec::new();
+    // Milestones found or created for due dates, shared by the issues of this run
+    let mut milestones = std::collections::HashMap::new();
+
+    for (todo_item, operation) in operations {
+        let result = match &operation {
+            github::push::GitHubOperation::CreateIssue { title } => create_issue_for_item(
+                client,
+                repository,
+                title,
+                &todo_item,
+                &config::labels(config_map),
+                &mut milestones,
+                token,
+            )
+            .await
+            .map(|issue_number| (todo_item.clone(), Some(issue_number))),
+            github::push::GitHubOperation::CloseIssue { number } => close_github_issue(
+                client,
+                repository,
+                *number,
+                config::close_reason(config_map).as_deref(),
+                token,
+            )
+            .await
+            .map(|_| (todo_item.clone(), None)),
+            github::push::GitHubOperation::RenameIssue { number, title } => {
+                rename_github_issue(client, repository, *number, title, token)
+                    .await
+                    .map(|_| (todo_item.clone(), None))
+            }
+        };
+
+        match (&result, &operation) {
+            (Ok((_, Some(issue_number))), _) => {
+                out.event(
+                    Some(&output::status(
+                        output::Mark::Success,
+                        &t!(
+                            "push.created",
+                            number = issue_number,
+                            title = todo_item.text
+                        ),
+                    )),
+                    &output::Event::IssueCreated {
+                        repository: repository.name.clone(),
+                        number: *issue_number,
+                        title: todo_item.text.clone(),
+                    },
+                )?;
+            }
+            (Ok((_, None)), github::push::GitHubOperation::CloseIssue { number }) => {
+                out.event(
+                    Some(&output::status(
+                        output::Mark::Success,
+                        &t!("push.closed", number = number),
+                    )),
+                    &output::Event::IssueClosed {
+                        repository: repository.name.clone(),
+                        number: *number,
+                    },
+                )?;
+            }
+            (Ok((_, None)), github::push::GitHubOperation::RenameIssue { number, title }) => {
+                out.event(
+                    Some(&output::status(
+                        output::Mark::Success,
+                        &t!("push.renamed", number = number, title = title),
+                    )),
+                    &output::Event::IssueRenamed {
+                        repository: repository.name.clone(),
+                        number: *number,
+                        title: title.clone(),
+                    },
                 )?;
             }
+            _ => {}
+        }
 
-            let updated_todo_items = github::pull::synchronize_with_github_issues(
-                &title_synchronization.items,
-                &github_issues,
-            );
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+async fn create_issue_for_item(
+    client: &reqwest::Client,
+    repository: &config::Repository,
+    title: &str,
+    todo_item: &todo::TodoItem,
+    default_labels: &[String],
+    milestones: &mut std::collections::HashMap<chrono::NaiveDate, u64>,
+    token: &str,
+) -> anyhow::Result<u64> {
+    let mut labels: Vec<String> = todo_item
+        .labels
+        .iter()
+        .cloned()
+        .chain(
+            todo_item
+                .metadata
+                .priority
+                .map(|priority| priority.label().to_string()),
+        )
+        .collect();
+    for label in default_labels {
+        if !labels.contains(label) {
+            labels.push(label.clone());
+        }
+    }
+
+    let milestone = match todo_item.metadata.due {
+        Some(due) => match milestones.get(&due) {
+            Some(&number) => Some(number),
+            None => {
+                let number = find_or_create_milestone(client, repository, due, token).await?;
+                milestones.insert(due, number);
+                Some(number)
+            }
+        },
+        None => None,
+    };
+
+    let request = CreateIssueRequest {
+        title: title.to_string(),
+        labels,
+        assignees: todo_item.assignees.clone(),
+        body: todo_item.body.clone(),
+        milestone,
+    };
+    create_github_issue(client, repository, &request, token).await
+}
+
+async fn find_or_create_milestone(
+    client: &reqwest::Client,
+    repository: &config::Repository,
+    due: chrono::NaiveDate,
+    token: &str,
+) -> anyhow::Result<u64> {
+    let url = format!("{}/milestones", repository.api_url());
+    let mut page = 1;
+    let per_page = 100;
+    // A closed milestone with the title to create, reopened instead of creating a duplicate
+    let mut closed = None;
+
+    loop {
+        let response = send_authorized(
+            client
+                .get(&url)
+                .header("Accept", "application/vnd.github.v3+json")
+                .header("User-Agent", "atat-cli")
+                .query(&[
+                    ("state", "all"),
+                    ("page", &page.to_string()),
+                    ("per_page", &per_page.to_string()),
+                ]),
+            token,
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(t!(
+                "github.milestones_failed",
+                status = response.status()
+            )));
+        }
 
-            let updated_content = markdown_parser::serialize_todo_markdown(&updated_todo_items);
-            std::fs::write("TODO.md", updated_content)
-                .map_err(|e| anyhow!("Failed to write TODO.md: {e}"))?;
+        let milestones_json: Vec<serde_json::Value> = response.json().await?;
+
+        if milestones_json.is_empty() {
+            break;
         }
-        cli::parser::Command::Unknown(message) => return Err(anyhow!(message)),
-        _ => {
-            return Err(anyhow::anyhow!(
-                "Invalid command or arguments. Use --help for usage."
-            ));
+
+        if let Some(number) = github::milestones::find_milestone_for_due_date(&milestones_json, due)
+        {
+            return Ok(number);
         }
+        closed = closed.or(github::milestones::find_closed_milestone_titled(
+            &milestones_json,
+            due,
+        ));
+        page += 1;
     }
-    Ok(())
+
+    if let Some(number) = closed {
+        let response = send_authorized(
+            client
+                .patch(format!("{url}/{number}"))
+                .header("Accept", "application/vnd.github.v3+json")
+                .header("User-Agent", "atat-cli")
+                .json(&serde_json::json!({ "state": "open" })),
+            token,
+        )
+        .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(t!(
+                "github.create_milestone_failed",
+                status = response.status()
+            )));
+        }
+        return Ok(number);
+    }
+
+    #[derive(serde::Serialize)]
+    struct CreateMilestoneRequest {
+        title: String,
+        due_on: String,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct CreateMilestoneResponse {
+        number: u64,
+    }
+
+    let request = CreateMilestoneRequest {
+        title: github::milestones::milestone_title(due),
+        due_on: github::milestones::milestone_due_on(due),
+    };
+
+    let response = send_authorized(
+        client
+            .post(&url)
+            .header("Accept", "application/vnd.github.v3+json")
+            .header("User-Agent", "atat-cli")
+            .json(&request),
+        token,
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!(t!(
+            "github.create_milestone_failed",
+            status = response.status()
+        )));
+    }
+
+    let create_response: CreateMilestoneResponse = response.json().await?;
+    Ok(create_response.number)
 }
 
 async fn request_device_code(
     client: &reqwest::Client,
     client_id: &str,
 ) -> anyhow::Result<auth::DeviceCodeResponse> {
-    let response = client
-        .post(endpoints::DEVICE_CODE)
-        .query(&[("client_id", client_id)])
-        .header("Accept", "application/json")
-        .send()
-        .await?;
+    let response = logging::send(
+        client
+            .post(endpoints::DEVICE_CODE)
+            .query(&[("client_id", client_id)])
+            .header("Accept", "application/json"),
+    )
+    .await?;
 
     if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "Failed to get device code: HTTP {}",
-            response.status()
-        ));
+        return Err(anyhow!(t!(
+            "github.device_code_failed",
+            status = response.status()
+        )));
     }
 
     let device_code_response = response.json::<auth::DeviceCodeResponse>().await?;
@@ -484,33 +2139,34 @@ async fn poll_for_token(
     client: &reqwest::Client,
     device_code: &auth::DeviceCodeResponse,
     timeout: std::time::Duration,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<auth::Credentials> {
     let start_time = std::time::Instant::now();
     let mut interval = std::time::Duration::from_secs(device_code.interval);
 
     loop {
         if start_time.elapsed() > timeout {
-            return Err(anyhow::anyhow!(
-                "Authentication timed out after {} seconds. Please try `login` again.",
-                timeout.as_secs()
-            ));
+            return Err(anyhow!(t!("login.timed_out", seconds = timeout.as_secs())));
         }
 
-        let response = client
-            .post(endpoints::ACCESS_TOKEN)
-            .header("Accept", "application/json")
-            .query(&[
-                ("client_id", CLIENT_ID),
-                ("device_code", &device_code.device_code),
-                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
-            ])
-            .send()
-            .await?;
+        let response = logging::send(
+            client
+                .post(endpoints::ACCESS_TOKEN)
+                .header("Accept", "application/json")
+                .query(&[
+                    ("client_id", CLIENT_ID),
+                    ("device_code", &device_code.device_code),
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ]),
+        )
+        .await?;
 
         if response.status().is_success() {
             let token_response = response.json::<auth::AccessTokenResponse>().await?;
             match auth::handle_polling_response(&token_response) {
-                auth::PollingResult::Success(token) => return Ok(token),
+                auth::PollingResult::Success(_) => {
+                    return auth::Credentials::from_response(&token_response, chrono::Utc::now())
+                        .ok_or_else(|| anyhow!(t!("github.invalid_response")));
+                }
                 auth::PollingResult::Wait(Some(new_interval)) => {
                     interval = std::time::Duration::from_secs(new_interval);
                 }
@@ -518,7 +2174,10 @@ async fn poll_for_token(
                 auth::PollingResult::Error(err) => return Err(anyhow::anyhow!(err)),
             }
         } else {
-            return Err(anyhow::anyhow!("API request error: {}", response.status()));
+            return Err(anyhow!(t!(
+                "github.request_failed",
+                status = response.status()
+            )));
         }
 
         tokio::time::sleep(interval).await;
@@ -527,31 +2186,29 @@ async fn poll_for_token(
 
 async fn check_repo_exists(
     client: &reqwest::Client,
-    repo_name: &str,
+    repository: &config::Repository,
     token: Option<&str>,
 ) -> anyhow::Result<bool> {
-    let url = format!("{}/{}", endpoints::REPO_DETAILS, repo_name);
+    let url = repository.api_url();
     let mut request_builder = client.get(&url).header("User-Agent", "atat-cli");
 
     if let Some(t) = token {
         request_builder = request_builder.bearer_auth(t);
     }
 
-    let response = request_builder.send().await?;
+    let response = logging::send(request_builder).await?;
 
     match response.status() {
         reqwest::StatusCode::OK => Ok(true),
         reqwest::StatusCode::NOT_FOUND => Ok(false),
         reqwest::StatusCode::FORBIDDEN => Ok(false),
-        status => Err(anyhow::anyhow!(
-            "Failed to check repository: GitHub API returned HTTP {status}"
-        )),
+        status => Err(anyhow!(t!("github.check_failed", status = status))),
     }
 }
 
 async fn fetch_github_issues_async(
     client: &reqwest::Client,
-    repo: &str,
+    repository: &config::Repository,
     token: &str,
 ) -> anyhow::Result<Vec<github::issues::GitHubIssue>> {
     let mut all_issues = Vec::new();
@@ -559,27 +2216,28 @@ async fn fetch_github_issues_async(
     let per_page = 100;
 
     loop {
-        let url = format!("{}/{}/issues", endpoints::ISSUES, repo);
-        let response = client
-            .get(&url)
-            .bearer_auth(token)
-            .header("Accept", "application/vnd.github.v3+json")
-            .header("User-Agent", "atat-cli")
-            .query(&[
-                ("state", "all"),
-                ("page", &page.to_string()),
-                ("per_page", &per_page.to_string()),
-                ("sort", "created"),
-                ("direction", "desc"),
-            ])
-            .send()
-            .await?;
+        let url = format!("{}/issues", repository.api_url());
+        let response = send_authorized(
+            client
+                .get(&url)
+                .header("Accept", "application/vnd.github.v3+json")
+                .header("User-Agent", "atat-cli")
+                .query(&[
+                    ("state", "all"),
+                    ("page", &page.to_string()),
+                    ("per_page", &per_page.to_string()),
+                    ("sort", "created"),
+                    ("direction", "desc"),
+                ]),
+            token,
+        )
+        .await?;
 
         if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Failed to get issues: HTTP {}",
-                response.status()
-            ));
+            return Err(anyhow!(t!(
+                "github.issues_failed",
+                status = response.status()
+            )));
         }
 
         let issues_json: Vec<serde_json::Value> = response.json().await?;
@@ -598,7 +2256,7 @@ async fn fetch_github_issues_async(
 
 async fn fetch_issue_events_async(
     client: &reqwest::Client,
-    repo: &str,
+    repository: &config::Repository,
     issue_number: u64,
     token: &str,
 ) -> anyhow::Result<Vec<serde_json::Value>> {
@@ -607,29 +2265,25 @@ async fn fetch_issue_events_async(
     let per_page = 100;
 
     loop {
-        let url = format!(
-            "{}/{}/issues/{}/events",
-            endpoints::ISSUES,
-            repo,
-            issue_number
-        );
-        let response = client
-            .get(&url)
-            .bearer_auth(token)
-            .header("Accept", "application/vnd.github.v3+json")
-            .header("User-Agent", "atat-cli")
-            .query(&[
-                ("page", &page.to_string()),
-                ("per_page", &per_page.to_string()),
-            ])
-            .send()
-            .await?;
+        let url = format!("{}/issues/{}/events", repository.api_url(), issue_number);
+        let response = send_authorized(
+            client
+                .get(&url)
+                .header("Accept", "application/vnd.github.v3+json")
+                .header("User-Agent", "atat-cli")
+                .query(&[
+                    ("page", &page.to_string()),
+                    ("per_page", &per_page.to_string()),
+                ]),
+            token,
+        )
+        .await?;
 
         if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Failed to get issue events: HTTP {}",
-                response.status()
-            ));
+            return Err(anyhow!(t!(
+                "github.issue_events_failed",
+                status = response.status()
+            )));
         }
 
         let events_json: Vec<serde_json::Value> = response.json().await?;
@@ -645,42 +2299,48 @@ async fn fetch_issue_events_async(
     Ok(all_events)
 }
 
+/// Body of `POST /repos/{owner}/{repo}/issues`
+#[derive(serde::Serialize)]
+struct CreateIssueRequest {
+    title: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    labels: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    assignees: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    milestone: Option<u64>,
+}
+
 async fn create_github_issue(
     client: &reqwest::Client,
-    repo: &str,
-    title: &str,
+    repository: &config::Repository,
+    request: &CreateIssueRequest,
     token: &str,
 ) -> anyhow::Result<u64> {
-    let url = format!("{}/{}/issues", endpoints::ISSUES, repo);
-
-    #[derive(serde::Serialize)]
-    struct CreateIssueRequest {
-        title: String,
-    }
+    let url = format!("{}/issues", repository.api_url());
 
     #[derive(serde::Deserialize)]
     struct CreateIssueResponse {
         number: u64,
     }
 
-    let request = CreateIssueRequest {
-        title: title.to_string(),
-    };
-
-    let response = client
-        .post(&url)
-        .bearer_auth(token)
-        .header("Accept", "application/vnd.github.v3+json")
-        .header("User-Agent", "atat-cli")
-        .json(&request)
-        .send()
-        .await?;
+    let response = send_authorized(
+        client
+            .post(&url)
+            .header("Accept", "application/vnd.github.v3+json")
+            .header("User-Agent", "atat-cli")
+            .json(request),
+        token,
+    )
+    .await?;
 
     if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "Failed to create issue: HTTP {}",
-            response.status()
-        ));
+        return Err(anyhow!(t!(
+            "github.create_issue_failed",
+            status = response.status()
+        )));
     }
 
     let create_response: CreateIssueResponse = response.json().await?;
@@ -689,12 +2349,12 @@ async fn create_github_issue(
 
 async fn rename_github_issue(
     client: &reqwest::Client,
-    repo: &str,
+    repository: &config::Repository,
     issue_number: u64,
     title: &str,
     token: &str,
 ) -> anyhow::Result<()> {
-    let url = format!("{}/{}/issues/{}", endpoints::ISSUES, repo, issue_number);
+    let url = format!("{}/issues/{}", repository.api_url(), issue_number);
 
     #[derive(serde::Serialize)]
     struct UpdateIssueRequest {
@@ -705,20 +2365,21 @@ async fn rename_github_issue(
         title: title.to_string(),
     };
 
-    let response = client
-        .patch(&url)
-        .bearer_auth(token)
-        .header("Accept", "application/vnd.github.v3+json")
-        .header("User-Agent", "atat-cli")
-        .json(&request)
-        .send()
-        .await?;
+    let response = send_authorized(
+        client
+            .patch(&url)
+            .header("Accept", "application/vnd.github.v3+json")
+            .header("User-Agent", "atat-cli")
+            .json(&request),
+        token,
+    )
+    .await?;
 
     if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "Failed to rename issue: HTTP {}",
-            response.status()
-        ));
+        return Err(anyhow!(t!(
+            "github.rename_issue_failed",
+            status = response.status()
+        )));
     }
 
     Ok(())
@@ -726,35 +2387,40 @@ async fn rename_github_issue(
 
 async fn close_github_issue(
     client: &reqwest::Client,
-    repo: &str,
+    repository: &config::Repository,
     issue_number: u64,
+    state_reason: Option<&str>,
     token: &str,
 ) -> anyhow::Result<()> {
-    let url = format!("{}/{}/issues/{}", endpoints::ISSUES, repo, issue_number);
+    let url = format!("{}/issues/{}", repository.api_url(), issue_number);
 
     #[derive(serde::Serialize)]
-    struct UpdateIssueRequest {
+    struct UpdateIssueRequest<'a> {
         state: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        state_reason: Option<&'a str>,
     }
 
     let request = UpdateIssueRequest {
         state: "closed".to_string(),
+        state_reason,
     };
 
-    let response = client
-        .patch(&url)
-        .bearer_auth(token)
-        .header("Accept", "application/vnd.github.v3+json")
-        .header("User-Agent", "atat-cli")
-        .json(&request)
-        .send()
-        .await?;
+    let response = send_authorized(
+        client
+            .patch(&url)
+            .header("Accept", "application/vnd.github.v3+json")
+            .header("User-Agent", "atat-cli")
+            .json(&request),
+        token,
+    )
+    .await?;
 
     if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "Failed to close issue: HTTP {}",
-            response.status()
-        ));
+        return Err(anyhow!(t!(
+            "github.close_issue_failed",
+            status = response.status()
+        )));
     }
 
     Ok(())
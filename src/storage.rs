@@ -1,8 +1,11 @@
 use crate::config;
-use anyhow::{Context, Result};
+use crate::i18n::t;
+use anyhow::{Context, Result, anyhow};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::env;
+use std::ffi::OsString;
+use std::fmt;
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -28,66 +31,410 @@ pub trait ConfigStorage {
     fn save_config(&self, config_data: &HashMap<config::ConfigKey, Value>) -> Result<()>;
 }
 
-/// File-based token persistence implementation
+/// Directory under the user configuration directory that holds the token
+const TOKEN_DIR: &str = "atat";
+const TOKEN_FILENAME: &str = "token";
+/// Directory used by earlier versions, relative to `HOME`
+const LEGACY_TOKEN_DIR: &str = ".atat";
+/// Directory holding the tokens of named accounts, one file per account
+const ACCOUNT_TOKENS_DIR: &str = "tokens";
+/// File listing the named accounts
+const ACCOUNTS_FILENAME: &str = "accounts.json";
+
+/// Account used when no account is selected
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// Check that an account name can be used as a file name and credential username.
+pub fn validate_account_name(account: &str) -> Result<()> {
+    let valid = !account.is_empty()
+        && !account.starts_with('.')
+        && account
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(t!("storage.invalid_account", account = account)))
+    }
+}
+
+/// File-based token persistence implementation.
+///
+/// The token is stored in `$XDG_CONFIG_HOME/atat/token` (`~/.config/atat/token`
+/// by default) with mode 600. A token in the legacy `~/.atat/token` is moved
+/// there on first load.
 pub struct FileTokenStorage {
     path: PathBuf,
+    legacy_path: Option<PathBuf>,
 }
 
 impl FileTokenStorage {
-    pub fn new() -> Self {
-        let mut dir = std::env::var_os("HOME")
-            .map(PathBuf::from)
-            .expect("HOME environment variable not set");
-        dir.push(".atat");
-        let _ = fs::create_dir_all(&dir);
-        dir.push("token");
-        FileTokenStorage { path: dir }
+    pub fn new() -> Result<Self> {
+        let home = env::var_os("HOME").filter(|home| !home.is_empty());
+        let path = token_path(env::var_os("XDG_CONFIG_HOME"), home.clone())?;
+        let legacy_path = home.map(|home| {
+            PathBuf::from(home)
+                .join(LEGACY_TOKEN_DIR)
+                .join(TOKEN_FILENAME)
+        });
+        Ok(FileTokenStorage { path, legacy_path })
+    }
+
+    /// Storage for a named account. The default account uses the token file of `new`.
+    pub fn for_account(account: &str) -> Result<Self> {
+        if account == DEFAULT_ACCOUNT {
+            return Self::new();
+        }
+        validate_account_name(account)?;
+        Ok(FileTokenStorage {
+            path: user_config_dir()?.join(ACCOUNT_TOKENS_DIR).join(account),
+            legacy_path: None,
+        })
+    }
+
+    /// Move the legacy token file to the current location if only the legacy one exists.
+    fn migrate_legacy_token(&self) -> Result<()> {
+        let Some(legacy_path) = &self.legacy_path else {
+            return Ok(());
+        };
+        if self.path.exists() || !legacy_path.exists() {
+            return Ok(());
+        }
+        let token = fs::read_to_string(legacy_path)
+            .with_context(|| t!("storage.read_legacy_token_failed"))?;
+        self.save(token.trim())?;
+        fs::remove_file(legacy_path).with_context(|| t!("storage.remove_legacy_token_failed"))?;
+        Ok(())
+    }
+}
+
+/// Resolve atat's user configuration directory from `XDG_CONFIG_HOME` and `HOME`.
+///
+/// A relative or empty `XDG_CONFIG_HOME` is ignored, as the XDG Base Directory
+/// specification requires.
+fn atat_config_dir(xdg_config_home: Option<OsString>, home: Option<OsString>) -> Result<PathBuf> {
+    let config_dir = xdg_config_home
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| {
+            home.filter(|home| !home.is_empty())
+                .map(|home| PathBuf::from(home).join(".config"))
+        })
+        .ok_or_else(|| anyhow!(t!("storage.no_config_dir")))?;
+    Ok(config_dir.join(TOKEN_DIR))
+}
+
+/// Resolve the default token path from `XDG_CONFIG_HOME` and `HOME`.
+fn token_path(xdg_config_home: Option<OsString>, home: Option<OsString>) -> Result<PathBuf> {
+    Ok(atat_config_dir(xdg_config_home, home)?.join(TOKEN_FILENAME))
+}
+
+fn user_config_dir() -> Result<PathBuf> {
+    atat_config_dir(env::var_os("XDG_CONFIG_HOME"), env::var_os("HOME"))
+}
+
+/// Names of the accounts logged in with `--account`, kept independently of
+/// where the tokens are stored so that they can be listed for every storage.
+pub struct AccountRegistry {
+    path: PathBuf,
+}
+
+impl AccountRegistry {
+    pub fn new() -> Result<Self> {
+        Ok(AccountRegistry {
+            path: user_config_dir()?.join(ACCOUNTS_FILENAME),
+        })
+    }
+
+    /// Named accounts in alphabetical order. The default account is not included.
+    pub fn list(&self) -> Result<Vec<String>> {
+        let content = read_file_bytes(&self.path)?;
+        if content.iter().all(|b| b.is_ascii_whitespace()) {
+            return Ok(Vec::new());
+        }
+        serde_json::from_slice(&content).with_context(|| t!("storage.parse_accounts_failed"))
+    }
+
+    pub fn add(&self, account: &str) -> Result<()> {
+        let mut accounts = self.list()?;
+        if account == DEFAULT_ACCOUNT || accounts.iter().any(|a| a == account) {
+            return Ok(());
+        }
+        accounts.push(account.to_string());
+        accounts.sort();
+        self.write(&accounts)
+    }
+
+    pub fn remove(&self, account: &str) -> Result<()> {
+        let mut accounts = self.list()?;
+        accounts.retain(|a| a != account);
+        self.write(&accounts)
+    }
+
+    fn write(&self, accounts: &[String]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).with_context(|| t!("storage.create_dir_failed"))?;
+        }
+        let content = serde_json::to_string_pretty(accounts)
+            .with_context(|| t!("storage.serialize_accounts_failed"))?;
+        fs::write(&self.path, content).with_context(|| t!("storage.write_accounts_failed"))
     }
 }
 
-impl Default for FileTokenStorage {
-    fn default() -> Self {
-        Self::new()
+/// Fail if the token file can be read or written by the group or other users.
+#[cfg(unix)]
+fn check_token_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path)
+        .with_context(|| t!("storage.token_metadata_failed"))?
+        .permissions()
+        .mode()
+        & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(anyhow!(t!(
+            "storage.insecure_token_file",
+            path = path.display(),
+            mode = format!("{mode:o}")
+        )));
     }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_token_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Write `content` to a temporary file created with mode 600 and rename it over `path`.
+fn write_private_file_atomically(path: &Path, content: &[u8]) -> Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| anyhow!(t!("storage.invalid_token_path", path = path.display())))?;
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder
+        .create(dir)
+        .with_context(|| t!("storage.create_dir_failed"))?;
+
+    let tmp_path = dir.join(format!(".{TOKEN_FILENAME}.{}.tmp", std::process::id()));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let result = options
+        .open(&tmp_path)
+        .with_context(|| t!("storage.open_token_failed"))
+        .and_then(|mut file| {
+            file.write_all(content)
+                .with_context(|| t!("storage.write_token_failed"))?;
+            file.sync_all()
+                .with_context(|| t!("storage.write_token_failed"))
+        })
+        .and_then(|_| {
+            fs::rename(&tmp_path, path).with_context(|| t!("storage.replace_token_failed"))
+        });
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
 }
 
 impl TokenStorage for FileTokenStorage {
     fn load(&self) -> Result<Option<String>> {
+        self.migrate_legacy_token()?;
         if !self.path.exists() {
             return Ok(None);
         }
-        let content = fs::read_to_string(&self.path).context("Failed to read token file")?;
+        check_token_permissions(&self.path)?;
+        let content =
+            fs::read_to_string(&self.path).with_context(|| t!("storage.read_token_failed"))?;
         Ok(Some(content.trim().to_string()))
     }
 
     fn save(&self, token: &str) -> Result<()> {
-        if let Some(parent) = self.path.parent() {
-            fs::create_dir_all(parent).context("Failed to create storage directory")?;
+        write_private_file_atomically(&self.path, token.as_bytes())
+    }
+
+    fn delete(&self) -> Result<()> {
+        for path in std::iter::once(&self.path).chain(&self.legacy_path) {
+            if path.exists() {
+                fs::remove_file(path).with_context(|| t!("storage.delete_token_failed"))?;
+            }
         }
-        let mut file = File::create(&self.path).context("Failed to open token file for writing")?;
-        file.write_all(token.as_bytes())
-            .context("Failed to write token to file")?;
         Ok(())
     }
+}
+
+/// Token persistence through a git credential helper (`get`/`store`/`erase` protocol).
+///
+/// The helper is specified as in git's `credential.helper`: `!command` runs a
+/// shell command, an absolute path runs that program, and any other name runs
+/// `git credential-<name>`. The token is stored as the password of user `atat`
+/// (`atat-<account>` for named accounts) on the host, so it does not replace
+/// git's own credentials for the host.
+pub struct CredentialHelperTokenStorage {
+    helper: String,
+    host: String,
+    username: String,
+}
+
+/// Username the default account's token is stored under
+const CREDENTIAL_USERNAME: &str = "atat";
+
+impl CredentialHelperTokenStorage {
+    pub fn new(helper: &str, host: &str, account: &str) -> Result<Self> {
+        let username = if account == DEFAULT_ACCOUNT {
+            CREDENTIAL_USERNAME.to_string()
+        } else {
+            validate_account_name(account)?;
+            format!("{CREDENTIAL_USERNAME}-{account}")
+        };
+        Ok(CredentialHelperTokenStorage {
+            helper: helper.to_string(),
+            host: host.to_string(),
+            username,
+        })
+    }
+
+    /// Run the helper with `action`, writing `password` in the request if given.
+    fn run(&self, action: &str, password: Option<&str>) -> Result<String> {
+        use std::process::{Command, Stdio};
+
+        let (program, args) = credential_helper_command(&self.helper, action);
+        let mut child = Command::new(&program)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| t!("storage.helper_run_failed", helper = self.helper))?;
+
+        let request = credential_request(&self.host, &self.username, password);
+        if let Some(mut stdin) = child.stdin.take() {
+            // Helpers may exit without reading the request, as git allows
+            match stdin.write_all(request.as_bytes()) {
+                Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                    return Err(e).with_context(|| t!("storage.helper_write_failed"));
+                }
+                _ => {}
+            }
+        }
+        let output = child
+            .wait_with_output()
+            .with_context(|| t!("storage.helper_wait_failed"))?;
+        if !output.status.success() {
+            return Err(anyhow!(t!(
+                "storage.helper_failed",
+                helper = self.helper,
+                action = action,
+                status = output.status
+            )));
+        }
+        String::from_utf8(output.stdout).with_context(|| t!("storage.helper_output_invalid"))
+    }
+}
+
+impl TokenStorage for CredentialHelperTokenStorage {
+    fn load(&self) -> Result<Option<String>> {
+        let output = self.run("get", None)?;
+        Ok(credential_password(&output))
+    }
+
+    fn save(&self, token: &str) -> Result<()> {
+        if token.contains(['\n', '\0']) {
+            return Err(anyhow!(t!("storage.helper_invalid_token")));
+        }
+        self.run("store", Some(token)).map(|_| ())
+    }
 
     fn delete(&self) -> Result<()> {
-        if self.path.exists() {
-            fs::remove_file(&self.path).context("Failed to delete token file")?;
+        self.run("erase", None).map(|_| ())
+    }
+}
+
+/// Program and arguments for a helper, following git's `credential.helper` rules.
+fn credential_helper_command(helper: &str, action: &str) -> (String, Vec<String>) {
+    if let Some(command) = helper.strip_prefix('!') {
+        (
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                format!("{command} \"$@\""),
+                helper.to_string(),
+                action.to_string(),
+            ],
+        )
+    } else {
+        let mut words = helper.split_whitespace().map(str::to_string);
+        let name = words.next().unwrap_or_default();
+        let rest: Vec<String> = words.chain(std::iter::once(action.to_string())).collect();
+        if Path::new(&name).is_absolute() {
+            (name, rest)
+        } else {
+            let mut args = vec![format!("credential-{name}")];
+            args.extend(rest);
+            ("git".to_string(), args)
         }
-        Ok(())
     }
 }
 
+/// Request written to the helper's standard input
+fn credential_request(host: &str, username: &str, password: Option<&str>) -> String {
+    let mut request = format!("protocol=https\nhost={host}\nusername={username}\n");
+    if let Some(password) = password {
+        request.push_str(&format!("password={password}\n"));
+    }
+    request.push('\n');
+    request
+}
+
+/// The `password` attribute of a helper response
+fn credential_password(output: &str) -> Option<String> {
+    output
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .find_map(|line| line.strip_prefix("password="))
+        .filter(|password| !password.is_empty())
+        .map(str::to_string)
+}
+
 /// File-based local configuration persistence implementation
 pub struct LocalConfigStorage {
     config_path: PathBuf,
     config_dir: PathBuf,
 }
 
+/// Find the project root: the nearest ancestor of `start` (or `start` itself) that has
+/// `.atat/config.json` or `.git`, like git finds its repository. Falls back to `start`.
+///
+/// A `.atat` directory without `config.json` is skipped, so the token directory of
+/// earlier versions in `~/.atat` does not make the home directory a project.
+pub fn find_project_root(start: &Path) -> PathBuf {
+    start
+        .ancestors()
+        .find(|dir| {
+            dir.join(config::PROJECT_CONFIG_DIR)
+                .join(config::PROJECT_CONFIG_FILENAME)
+                .is_file()
+                || dir.join(".git").exists()
+        })
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+/// Project root of the current directory (see `find_project_root`)
+pub fn project_root() -> Result<PathBuf> {
+    let current_dir = env::current_dir().with_context(|| t!("storage.current_dir_failed"))?;
+    Ok(find_project_root(&current_dir))
+}
+
 impl LocalConfigStorage {
     pub fn new() -> Result<Self> {
-        let current_dir = env::current_dir().context("Failed to get current directory")?;
-        let config_dir = current_dir.join(config::PROJECT_CONFIG_DIR);
+        let config_dir = project_root()?.join(config::PROJECT_CONFIG_DIR);
         let config_path = config_dir.join(config::PROJECT_CONFIG_FILENAME);
         Ok(LocalConfigStorage {
             config_path,
@@ -96,42 +443,158 @@ impl LocalConfigStorage {
     }
 }
 
+impl LocalConfigStorage {
+    pub fn path(&self) -> &Path {
+        &self.config_path
+    }
+}
+
 impl ConfigStorage for LocalConfigStorage {
     fn load_config(&self) -> Result<HashMap<config::ConfigKey, Value>> {
-        let content = read_file_bytes(&self.config_path).context(format!(
-            "Failed to read project config file at {:?}",
-            self.config_path
-        ))?;
-        config::parse_config(&content)
+        load_config_file(&self.config_path, ConfigKind::Project)
     }
 
     fn save_config(&self, config_data: &HashMap<config::ConfigKey, Value>) -> Result<()> {
-        if !self.config_dir.exists() {
-            fs::create_dir_all(&self.config_dir).context(format!(
-                "Failed to create project config directory at {:?}",
-                self.config_dir
-            ))?;
-        }
+        save_config_file(
+            &self.config_dir,
+            &self.config_path,
+            config_data,
+            ConfigKind::Project,
+        )
+    }
+}
+
+/// User-wide defaults in `$XDG_CONFIG_HOME/atat/config.json`, overridden by the project config
+pub struct GlobalConfigStorage {
+    config_path: PathBuf,
+    config_dir: PathBuf,
+}
+
+impl GlobalConfigStorage {
+    pub fn new() -> Result<Self> {
+        let config_dir = user_config_dir()?;
+        Ok(GlobalConfigStorage {
+            config_path: config_dir.join(config::PROJECT_CONFIG_FILENAME),
+            config_dir,
+        })
+    }
 
-        let mut json_map = serde_json::Map::new();
-        for (key, value) in config_data {
-            json_map.insert(key.as_str().to_string(), value.clone());
+    pub fn path(&self) -> &Path {
+        &self.config_path
+    }
+}
+
+impl ConfigStorage for GlobalConfigStorage {
+    fn load_config(&self) -> Result<HashMap<config::ConfigKey, Value>> {
+        load_config_file(&self.config_path, ConfigKind::Global)
+    }
+
+    fn save_config(&self, config_data: &HashMap<config::ConfigKey, Value>) -> Result<()> {
+        save_config_file(
+            &self.config_dir,
+            &self.config_path,
+            config_data,
+            ConfigKind::Global,
+        )
+    }
+}
+
+/// Load a config file; a missing file is an empty configuration.
+///
+/// A file written by an older version of atat is upgraded in memory only, and
+/// takes the current format the next time a command saves it.
+fn load_config_file(
+    config_path: &Path,
+    kind: ConfigKind,
+) -> Result<HashMap<config::ConfigKey, Value>> {
+    let content = read_file_bytes(config_path).with_context(|| {
+        t!(
+            "storage.read_config_failed",
+            kind = kind,
+            path = format!("{config_path:?}")
+        )
+    })?;
+    let document = config::parse_config_document(&content).map_err(|e| {
+        anyhow::anyhow!(t!(
+            "storage.invalid_config",
+            kind = kind,
+            path = config_path.display(),
+            error = e
+        ))
+    })?;
+
+    if let Some(version) = document.migrated_from {
+        log::debug!(
+            "{} is in config format {version}, upgraded to {} when next saved",
+            config_path.display(),
+            config::CONFIG_VERSION
+        );
+    }
+    Ok(document.values)
+}
+
+/// Which config file is read or written, named in messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigKind {
+    /// `$XDG_CONFIG_HOME/atat/config.json`
+    Global,
+    /// `.atat/config.json` of the project
+    Project,
+}
+
+impl fmt::Display for ConfigKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigKind::Global => write!(f, "{}", t!("storage.kind.global")),
+            ConfigKind::Project => write!(f, "{}", t!("storage.kind.project")),
         }
-        let content_str = serde_json::to_string_pretty(&json_map)
-            .context("Failed to serialize config to JSON for saving")?;
-
-        let mut file = File::create(&self.config_path).context(format!(
-            "Failed to open project config file for writing at {:?}",
-            self.config_path
-        ))?;
-        file.write_all(content_str.as_bytes()).context(format!(
-            "Failed to write to project config file at {:?}",
-            self.config_path
-        ))?;
-        Ok(())
     }
 }
 
+fn save_config_file(
+    config_dir: &Path,
+    config_path: &Path,
+    config_data: &HashMap<config::ConfigKey, Value>,
+    kind: ConfigKind,
+) -> Result<()> {
+    if !config_dir.exists() {
+        fs::create_dir_all(config_dir).with_context(|| {
+            t!(
+                "storage.create_config_dir_failed",
+                kind = kind,
+                path = format!("{config_dir:?}")
+            )
+        })?;
+    }
+
+    let mut json_map = serde_json::Map::new();
+    json_map.insert(
+        config::VERSION_KEY.to_string(),
+        Value::from(config::CONFIG_VERSION),
+    );
+    for (key, value) in config_data {
+        json_map.insert(key.as_str().to_string(), value.clone());
+    }
+    let content_str = serde_json::to_string_pretty(&json_map)
+        .with_context(|| t!("storage.serialize_config_failed"))?;
+
+    let mut file = File::create(config_path).with_context(|| {
+        t!(
+            "storage.open_config_failed",
+            kind = kind,
+            path = format!("{config_path:?}")
+        )
+    })?;
+    file.write_all(content_str.as_bytes()).with_context(|| {
+        t!(
+            "storage.write_config_failed",
+            kind = kind,
+            path = format!("{config_path:?}")
+        )
+    })?;
+    Ok(())
+}
+
 /// Reads the content of the file at the specified path into a byte vector.
 ///
 /// - Returns `Ok(Vec::new())` if the file does not exist.
@@ -140,6 +603,230 @@ fn read_file_bytes(path: &Path) -> Result<Vec<u8>> {
     match fs::read(path) {
         Ok(content) => Ok(content),
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
-        Err(e) => Err(e).context(format!("Failed to read file: {path:?}")),
+        Err(e) => {
+            Err(e).with_context(|| t!("storage.read_file_failed", path = format!("{path:?}")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("atat-storage-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_token_path_prefers_xdg_config_home() {
+        assert_eq!(
+            token_path(Some("/xdg".into()), Some("/home/alice".into())).unwrap(),
+            PathBuf::from("/xdg/atat/token")
+        );
+        assert_eq!(
+            token_path(Some("relative".into()), Some("/home/alice".into())).unwrap(),
+            PathBuf::from("/home/alice/.config/atat/token")
+        );
+        assert_eq!(
+            token_path(None, Some("/home/alice".into())).unwrap(),
+            PathBuf::from("/home/alice/.config/atat/token")
+        );
+        assert!(token_path(None, None).is_err());
+        assert!(token_path(Some("".into()), Some("".into())).is_err());
+    }
+
+    #[test]
+    fn test_save_and_migrate_token() {
+        let dir = temp_dir("migrate");
+        let legacy_path = dir.join(".atat/token");
+        fs::create_dir_all(legacy_path.parent().unwrap()).unwrap();
+        fs::write(&legacy_path, "legacy-token\n").unwrap();
+        let storage = FileTokenStorage {
+            path: dir.join(".config/atat/token"),
+            legacy_path: Some(legacy_path.clone()),
+        };
+
+        assert_eq!(storage.load().unwrap().as_deref(), Some("legacy-token"));
+        assert!(!legacy_path.exists());
+
+        storage.save("new-token").unwrap();
+        assert_eq!(storage.load().unwrap().as_deref(), Some("new-token"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&storage.path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        storage.delete().unwrap();
+        assert_eq!(storage.load().unwrap(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_refuses_readable_token() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("permissions");
+        let storage = FileTokenStorage {
+            path: dir.join("atat/token"),
+            legacy_path: None,
+        };
+        storage.save("secret").unwrap();
+        fs::set_permissions(&storage.path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let error = storage.load().unwrap_err().to_string();
+        assert!(error.contains("chmod 600"), "{error}");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_credential_helper_command() {
+        assert_eq!(
+            credential_helper_command("osxkeychain", "get"),
+            (
+                "git".to_string(),
+                vec!["credential-osxkeychain".to_string(), "get".to_string()]
+            )
+        );
+        assert_eq!(
+            credential_helper_command("/usr/local/bin/vault-helper --team a", "store"),
+            (
+                "/usr/local/bin/vault-helper".to_string(),
+                vec!["--team".to_string(), "a".to_string(), "store".to_string()]
+            )
+        );
+        let (program, args) = credential_helper_command("!pass-helper --dir x", "erase");
+        assert_eq!(program, "sh");
+        assert_eq!(args[1], "pass-helper --dir x \"$@\"");
+        assert_eq!(args.last().map(String::as_str), Some("erase"));
+    }
+
+    #[test]
+    fn test_credential_request_and_response() {
+        assert_eq!(
+            credential_request("github.com", "atat", None),
+            "protocol=https\nhost=github.com\nusername=atat\n\n"
+        );
+        assert_eq!(
+            credential_request("github.com", "atat-work", Some("secret")),
+            "protocol=https\nhost=github.com\nusername=atat-work\npassword=secret\n\n"
+        );
+        assert_eq!(
+            credential_password("username=atat\npassword=secret\n").as_deref(),
+            Some("secret")
+        );
+        assert_eq!(credential_password("username=atat\n"), None);
+        assert_eq!(credential_password(""), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_credential_helper_storage_roundtrip() {
+        let dir = temp_dir("helper");
+        fs::create_dir_all(&dir).unwrap();
+        let store = dir.join("store");
+        // Minimal helper keeping one password in a file
+        let helper = format!(
+            "!f() {{ case \"$1\" in get) if [ -f {0} ]; then printf 'password=%s\\n' \"$(cat {0})\"; fi;; store) sed -n 's/^password=//p' > {0};; erase) rm -f {0};; esac; }}; f",
+            store.display()
+        );
+        let storage = CredentialHelperTokenStorage::new(&helper, "github.com", "work").unwrap();
+
+        assert_eq!(storage.load().unwrap(), None);
+        storage.save("ghu_token").unwrap();
+        assert_eq!(storage.load().unwrap().as_deref(), Some("ghu_token"));
+        storage.delete().unwrap();
+        assert_eq!(storage.load().unwrap(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_validate_account_name() {
+        assert!(validate_account_name("work").is_ok());
+        assert!(validate_account_name("alice.corp-2_x").is_ok());
+        assert!(validate_account_name("").is_err());
+        assert!(validate_account_name("..").is_err());
+        assert!(validate_account_name("a/b").is_err());
+        assert!(CredentialHelperTokenStorage::new("store", "github.com", "a b").is_err());
+    }
+
+    #[test]
+    fn test_account_registry() {
+        let dir = temp_dir("accounts");
+        let registry = AccountRegistry {
+            path: dir.join("atat/accounts.json"),
+        };
+
+        assert!(registry.list().unwrap().is_empty());
+        registry.add("work").unwrap();
+        registry.add("personal").unwrap();
+        registry.add("work").unwrap();
+        registry.add(DEFAULT_ACCOUNT).unwrap();
+        assert_eq!(registry.list().unwrap(), vec!["personal", "work"]);
+
+        registry.remove("work").unwrap();
+        assert_eq!(registry.list().unwrap(), vec!["personal"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_find_project_root() {
+        let dir = temp_dir("project-root");
+        let nested = dir.join("repo/src/deep");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir_all(dir.join("repo/.git")).unwrap();
+
+        // The git root when there is no .atat/config.json
+        assert_eq!(find_project_root(&nested), dir.join("repo"));
+
+        // A nearer .atat/config.json wins over the git root
+        fs::create_dir_all(dir.join("repo/src/.atat")).unwrap();
+        assert_eq!(find_project_root(&nested), dir.join("repo"));
+        fs::write(dir.join("repo/src/.atat/config.json"), "{}").unwrap();
+        assert_eq!(find_project_root(&nested), dir.join("repo/src"));
+
+        // Without any marker the start directory is the root
+        let plain = dir.join("plain/sub");
+        fs::create_dir_all(&plain).unwrap();
+        assert_eq!(find_project_root(&plain), plain);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_config_file_migrates_old_versions() {
+        let dir = temp_dir("config-migrate");
+        let path = dir.join(config::PROJECT_CONFIG_FILENAME);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, r#"["owner/repo"]"#).unwrap();
+
+        let values = load_config_file(&path, ConfigKind::Project).unwrap();
+        assert_eq!(
+            values.get(&config::ConfigKey::Repositories),
+            Some(&serde_json::json!(["owner/repo"]))
+        );
+        // Reading leaves the file alone; saving writes the current format
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"["owner/repo"]"#);
+        save_config_file(&dir, &path, &values, ConfigKind::Project).unwrap();
+        let rewritten: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(
+            rewritten,
+            serde_json::json!({"version": config::CONFIG_VERSION, "repositories": ["owner/repo"]})
+        );
+
+        fs::write(&path, r#"{"repositorys": ["owner/repo"]}"#).unwrap();
+        let error = load_config_file(&path, ConfigKind::Project)
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with(&format!(
+            "Invalid project config file {}: Unknown config key: repositorys.",
+            path.display()
+        )));
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
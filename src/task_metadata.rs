@@ -0,0 +1,274 @@
+use chrono::NaiveDate;
+
+/// Date format used by the Obsidian Tasks plugin
+const DATE_FORMAT: &str = "%Y-%m-%d";
+/// Emoji variation selector that may follow a signifier
+const VARIATION_SELECTOR: char = '\u{FE0F}';
+
+/// Task priority, ordered from highest to lowest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    Highest,
+    High,
+    Medium,
+    Low,
+    Lowest,
+}
+
+impl Priority {
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Priority::Highest => "🔺",
+            Priority::High => "⏫",
+            Priority::Medium => "🔼",
+            Priority::Low => "🔽",
+            Priority::Lowest => "⏬",
+        }
+    }
+
+    /// GitHub label applied to issues with this priority
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::Highest => "priority: highest",
+            Priority::High => "priority: high",
+            Priority::Medium => "priority: medium",
+            Priority::Low => "priority: low",
+            Priority::Lowest => "priority: lowest",
+        }
+    }
+
+    fn all() -> &'static [Priority] {
+        &[
+            Priority::Highest,
+            Priority::High,
+            Priority::Medium,
+            Priority::Low,
+            Priority::Lowest,
+        ]
+    }
+}
+
+/// Obsidian Tasks fields written after the task description
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskMetadata {
+    pub priority: Option<Priority>,
+    pub recurrence: Option<String>,
+    pub created: Option<NaiveDate>,
+    pub start: Option<NaiveDate>,
+    pub scheduled: Option<NaiveDate>,
+    pub due: Option<NaiveDate>,
+    pub done: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Priority(Priority),
+    Recurrence,
+    Created,
+    Start,
+    Scheduled,
+    Due,
+    Done,
+}
+
+/// Signifier emoji and the field they introduce. Alternatives accepted by the plugin are included.
+fn signifiers() -> Vec<(&'static str, Field)> {
+    let mut signifiers = vec![
+        ("🔁", Field::Recurrence),
+        ("➕", Field::Created),
+        ("🛫", Field::Start),
+        ("⏳", Field::Scheduled),
+        ("⌛", Field::Scheduled),
+        ("📅", Field::Due),
+        ("📆", Field::Due),
+        ("🗓", Field::Due),
+        ("✅", Field::Done),
+    ];
+    signifiers.extend(
+        Priority::all()
+            .iter()
+            .map(|priority| (priority.emoji(), Field::Priority(*priority))),
+    );
+    signifiers
+}
+
+impl TaskMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == TaskMetadata::default()
+    }
+
+    /// Apply one field. Returns false if the value is not valid for the field.
+    fn set(&mut self, field: Field, value: &str) -> bool {
+        let date = || NaiveDate::parse_from_str(value, DATE_FORMAT).ok();
+        match field {
+            Field::Priority(priority) if value.is_empty() => self.priority = Some(priority),
+            Field::Recurrence if !value.is_empty() => self.recurrence = Some(value.to_string()),
+            Field::Created => match date() {
+                Some(date) => self.created = Some(date),
+                None => return false,
+            },
+            Field::Start => match date() {
+                Some(date) => self.start = Some(date),
+                None => return false,
+            },
+            Field::Scheduled => match date() {
+                Some(date) => self.scheduled = Some(date),
+                None => return false,
+            },
+            Field::Due => match date() {
+                Some(date) => self.due = Some(date),
+                None => return false,
+            },
+            Field::Done => match date() {
+                Some(date) => self.done = Some(date),
+                None => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Split trailing Obsidian Tasks fields off a task description.
+///
+/// Fields are read from the end of the text, as the plugin does; parsing stops
+/// at the first value that is not valid for its signifier.
+pub fn split_task_metadata(text: &str) -> (String, TaskMetadata) {
+    let signifiers = signifiers();
+    let mut metadata = TaskMetadata::default();
+    let mut description = text.trim_end();
+
+    while let Some((pos, emoji, field)) = signifiers
+        .iter()
+        .filter_map(|(emoji, field)| description.rfind(emoji).map(|pos| (pos, *emoji, *field)))
+        .max_by_key(|(pos, _, _)| *pos)
+    {
+        let value = description[pos + emoji.len()..]
+            .trim_start_matches(VARIATION_SELECTOR)
+            .trim();
+        if !metadata.set(field, value) {
+            break;
+        }
+        description = description[..pos].trim_end();
+    }
+
+    (description.to_string(), metadata)
+}
+
+/// Render metadata in the plugin's order, with a leading space. Empty metadata renders as "".
+pub fn format_task_metadata(metadata: &TaskMetadata) -> String {
+    let date = |emoji: &str, date: &Option<NaiveDate>| {
+        date.map(|d| format!(" {emoji} {}", d.format(DATE_FORMAT)))
+    };
+
+    [
+        metadata.priority.map(|p| format!(" {}", p.emoji())),
+        metadata.recurrence.as_ref().map(|r| format!(" 🔁 {r}")),
+        date("➕", &metadata.created),
+        date("🛫", &metadata.start),
+        date("⏳", &metadata.scheduled),
+        date("📅", &metadata.due),
+        date("✅", &metadata.done),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_split_without_metadata() {
+        let (text, metadata) = split_task_metadata("Plain task");
+        assert_eq!(text, "Plain task");
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn test_split_all_fields() {
+        let (text, metadata) = split_task_metadata(
+            "Rotate credentials ⏫ 🔁 every week ➕ 2026-10-01 🛫 2026-10-02 ⏳ 2026-10-03 📅 2026-10-20 ✅ 2026-10-10",
+        );
+
+        assert_eq!(text, "Rotate credentials");
+        assert_eq!(
+            metadata,
+            TaskMetadata {
+                priority: Some(Priority::High),
+                recurrence: Some("every week".to_string()),
+                created: Some(date("2026-10-01")),
+                start: Some(date("2026-10-02")),
+                scheduled: Some(date("2026-10-03")),
+                due: Some(date("2026-10-20")),
+                done: Some(date("2026-10-10")),
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_fields_in_any_order() {
+        let (text, metadata) = split_task_metadata("Task 📅 2026-10-20 🔽");
+
+        assert_eq!(text, "Task");
+        assert_eq!(metadata.due, Some(date("2026-10-20")));
+        assert_eq!(metadata.priority, Some(Priority::Low));
+    }
+
+    #[test]
+    fn test_split_stops_at_invalid_value() {
+        let (text, metadata) = split_task_metadata("Ship 📅 next friday");
+
+        assert_eq!(text, "Ship 📅 next friday");
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn test_split_keeps_emoji_in_description() {
+        let (text, metadata) = split_task_metadata("Release 🚀 and ⏫ celebrate 📅 2026-10-20");
+
+        assert_eq!(text, "Release 🚀 and ⏫ celebrate");
+        assert_eq!(metadata.due, Some(date("2026-10-20")));
+        assert_eq!(metadata.priority, None);
+    }
+
+    #[test]
+    fn test_split_accepts_variation_selector() {
+        let (text, metadata) = split_task_metadata("Task 🗓\u{FE0F} 2026-10-20");
+
+        assert_eq!(text, "Task");
+        assert_eq!(metadata.due, Some(date("2026-10-20")));
+    }
+
+    #[test]
+    fn test_format_task_metadata() {
+        let metadata = TaskMetadata {
+            priority: Some(Priority::Highest),
+            recurrence: Some("every day".to_string()),
+            due: Some(date("2026-10-20")),
+            done: Some(date("2026-10-21")),
+            ..TaskMetadata::default()
+        };
+
+        assert_eq!(
+            format_task_metadata(&metadata),
+            " 🔺 🔁 every day 📅 2026-10-20 ✅ 2026-10-21"
+        );
+        assert_eq!(format_task_metadata(&TaskMetadata::default()), "");
+    }
+
+    #[test]
+    fn test_format_then_split_roundtrip() {
+        let (_, metadata) = split_task_metadata("Task 🔼 🔁 every month 📅 2026-11-01");
+        let (text, reparsed) =
+            split_task_metadata(&format!("Task{}", format_task_metadata(&metadata)));
+
+        assert_eq!(text, "Task");
+        assert_eq!(reparsed, metadata);
+    }
+}
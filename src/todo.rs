@@ -1,6 +1,158 @@
-#[derive(Debug, Clone, PartialEq)]
+use crate::markdown_parser;
+use crate::org_parser;
+use crate::task_metadata::{self, TaskMetadata};
+use anyhow::Result;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct TodoItem {
     pub text: String,
     pub is_checked: bool,
     pub issue_number: Option<u64>,
+    pub labels: Vec<String>,
+    /// Issue body used when the issue is created. Not stored in the task file.
+    pub body: Option<String>,
+    /// GitHub users assigned when the issue is created. Not stored in the task file.
+    pub assignees: Vec<String>,
+    /// Obsidian Tasks fields (dates, priority, recurrence)
+    pub metadata: TaskMetadata,
+}
+
+/// File formats that can be used as a task source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoFormat {
+    Markdown,
+    Org,
+}
+
+impl TodoFormat {
+    /// Select the format from the file extension. Anything other than `.org` is Markdown.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("org") => TodoFormat::Org,
+            _ => TodoFormat::Markdown,
+        }
+    }
+
+    /// Content of a new task file created by `atat init`.
+    ///
+    /// Markdown needs no header, so new files start empty.
+    pub fn starter_content(&self) -> &'static str {
+        match self {
+            TodoFormat::Markdown => "",
+            TodoFormat::Org => "#+TITLE: TODO\n",
+        }
+    }
+
+    pub fn parse(&self, content: &str) -> Result<Vec<TodoItem>> {
+        match self {
+            TodoFormat::Markdown => markdown_parser::parse_todo_markdown(content),
+            TodoFormat::Org => org_parser::parse_todo_org(content),
+        }
+    }
+
+    /// Add `item` to `content` under the `section` heading, leaving the rest of the file as it is.
+    pub fn insert(&self, content: &str, section: Option<&str>, item: &TodoItem) -> String {
+        match self {
+            TodoFormat::Markdown => markdown_parser::insert_todo_markdown(content, section, item),
+            TodoFormat::Org => org_parser::insert_todo_org(content, section, item),
+        }
+    }
+
+    /// Open task for `text` given on the command line.
+    ///
+    /// In Markdown, Obsidian Tasks fields in the text (`📅 2026-10-20`) are read as metadata.
+    pub fn new_item(&self, text: &str) -> TodoItem {
+        let (text, metadata) = match self {
+            TodoFormat::Markdown => task_metadata::split_task_metadata(text.trim()),
+            TodoFormat::Org => (text.trim().to_string(), TaskMetadata::default()),
+        };
+        TodoItem {
+            text,
+            is_checked: false,
+            issue_number: None,
+            metadata,
+            ..Default::default()
+        }
+    }
+
+    /// Render `items` as file content.
+    ///
+    /// `original` is the content the items were parsed from. Tasks are patched
+    /// into it in place, so headings, notes and other text are kept.
+    pub fn serialize(&self, original: &str, items: &[TodoItem]) -> String {
+        match self {
+            TodoFormat::Markdown => markdown_parser::update_todo_markdown(original, items),
+            TodoFormat::Org => org_parser::serialize_todo_org(original, items),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            TodoFormat::from_path(Path::new("TODO.md")),
+            TodoFormat::Markdown
+        );
+        assert_eq!(
+            TodoFormat::from_path(Path::new("todo.org")),
+            TodoFormat::Org
+        );
+        assert_eq!(
+            TodoFormat::from_path(Path::new("notes/TODO.ORG")),
+            TodoFormat::Org
+        );
+        assert_eq!(
+            TodoFormat::from_path(Path::new("TODO")),
+            TodoFormat::Markdown
+        );
+    }
+
+    #[test]
+    fn test_starter_content_parses_empty() {
+        for format in [TodoFormat::Markdown, TodoFormat::Org] {
+            assert!(format.parse(format.starter_content()).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_new_item() {
+        let item = TodoFormat::Markdown.new_item(" Ship 📅 2026-10-20 ");
+        assert_eq!(item.text, "Ship");
+        assert_eq!(
+            item.metadata.due.map(|date| date.to_string()).as_deref(),
+            Some("2026-10-20")
+        );
+        assert!(!item.is_checked);
+
+        let item = TodoFormat::Org.new_item("Ship 📅 2026-10-20");
+        assert_eq!(item.text, "Ship 📅 2026-10-20");
+        assert!(item.metadata.is_empty());
+    }
+
+    #[test]
+    fn test_markdown_section_survives_push() {
+        let format = TodoFormat::Markdown;
+        let content = "# Project\n\n- [ ] Write docs (#1)\n";
+
+        // `atat add "Fix crash" --section Bugs`, with the issue not created yet
+        let item = format.new_item("Fix crash");
+        let content = format.insert(content, Some("Bugs"), &item);
+
+        // `atat push` creates the issue and writes its number back
+        let mut items = format.parse(&content).unwrap();
+        let created = items.iter_mut().find(|item| item.text == "Fix crash");
+        created.unwrap().issue_number = Some(2);
+        let content = format.serialize(&content, &items);
+
+        assert_eq!(
+            content,
+            "# Project\n\n- [ ] Write docs (#1)\n\n## Bugs\n\n- [ ] Fix crash (#2)\n"
+        );
+        assert_eq!(format.parse(&content).unwrap(), items);
+    }
 }
@@ -0,0 +1,315 @@
+use crate::i18n::t;
+use crate::storage::DEFAULT_ACCOUNT;
+use anyhow::Result;
+use std::fmt;
+
+/// Environment variables checked for a token, in order (the same order as the `gh` CLI)
+pub const TOKEN_ENV_VARS: &[&str] = &["GH_TOKEN", "GITHUB_TOKEN"];
+
+/// Environment variables checked for a GitHub Enterprise Server token, in order (as the `gh` CLI does)
+pub const ENTERPRISE_TOKEN_ENV_VARS: &[&str] = &["GH_ENTERPRISE_TOKEN", "GITHUB_ENTERPRISE_TOKEN"];
+
+/// Where the token in use came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSource {
+    /// `--token` command line flag
+    Flag,
+    /// One of `TOKEN_ENV_VARS` or `ENTERPRISE_TOKEN_ENV_VARS`
+    Env(&'static str),
+    /// `gh auth token`
+    GhCli,
+    /// Token saved by `atat login`
+    Stored,
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenSource::Flag => write!(f, "{}", t!("token_source.flag")),
+            TokenSource::Env(name) => write!(f, "{}", t!("token_source.env", name = name)),
+            TokenSource::GhCli => write!(f, "gh CLI"),
+            TokenSource::Stored => write!(f, "atat login"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedToken {
+    pub token: String,
+    pub source: TokenSource,
+}
+
+/// Pick the token from the first source that provides one.
+///
+/// Sources are tried in order: the explicit flag, the environment variables,
+/// the `gh` CLI, then the stored token of `account`. Blank values are skipped.
+/// Later sources are not consulted once a token is found, so the stored token
+/// is only loaded (and refreshed) when nothing else provides one.
+///
+/// A named account (any but the default one) stands for its own login, so only
+/// the flag comes before its stored token and the environment and `gh` CLI are
+/// not used for it.
+pub async fn resolve_token<E, G, S, Fut>(
+    flag: Option<&str>,
+    account: &str,
+    env: E,
+    gh_cli: G,
+    stored: S,
+) -> Result<Option<ResolvedToken>>
+where
+    E: Fn(&str) -> Option<String>,
+    G: FnOnce() -> Option<String>,
+    S: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<Option<String>>>,
+{
+    if let Some(resolved) = found(flag.map(str::to_string), TokenSource::Flag) {
+        return Ok(Some(resolved));
+    }
+    if account != DEFAULT_ACCOUNT {
+        return Ok(found(stored().await?, TokenSource::Stored));
+    }
+    if let Some(resolved) = TOKEN_ENV_VARS
+        .iter()
+        .find_map(|name| found(env(name), TokenSource::Env(name)))
+    {
+        return Ok(Some(resolved));
+    }
+    if let Some(resolved) = found(gh_cli(), TokenSource::GhCli) {
+        return Ok(Some(resolved));
+    }
+    Ok(found(stored().await?, TokenSource::Stored))
+}
+
+/// Pick the token for a repository on a GitHub Enterprise Server.
+///
+/// Sources are tried in order: the explicit flag, `ENTERPRISE_TOKEN_ENV_VARS`, then
+/// the `gh` CLI logged in to the host. `atat login` only signs in to github.com, so
+/// no stored token is used.
+pub fn resolve_enterprise_token<E, G>(
+    flag: Option<&str>,
+    env: E,
+    gh_cli: G,
+) -> Option<ResolvedToken>
+where
+    E: Fn(&str) -> Option<String>,
+    G: FnOnce() -> Option<String>,
+{
+    found(flag.map(str::to_string), TokenSource::Flag)
+        .or_else(|| {
+            ENTERPRISE_TOKEN_ENV_VARS
+                .iter()
+                .find_map(|name| found(env(name), TokenSource::Env(name)))
+        })
+        .or_else(|| found(gh_cli(), TokenSource::GhCli))
+}
+
+/// `token` from `source`, unless it is missing or blank
+fn found(token: Option<String>, source: TokenSource) -> Option<ResolvedToken> {
+    token
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .map(|token| ResolvedToken { token, source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_with(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())
+    }
+
+    #[tokio::test]
+    async fn test_flag_takes_precedence() {
+        let resolved = resolve_token(
+            Some("flag-token"),
+            DEFAULT_ACCOUNT,
+            env_with(&[("GH_TOKEN", "env-token")]),
+            || panic!("gh should not be called"),
+            || async { panic!("storage should not be read") },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            resolved,
+            Some(ResolvedToken {
+                token: "flag-token".to_string(),
+                source: TokenSource::Flag
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_env_vars_in_order() {
+        let resolved = resolve_token(
+            None,
+            DEFAULT_ACCOUNT,
+            env_with(&[("GITHUB_TOKEN", "github-token"), ("GH_TOKEN", "gh-token")]),
+            || None,
+            || async { Ok(None) },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(resolved.token, "gh-token");
+        assert_eq!(resolved.source, TokenSource::Env("GH_TOKEN"));
+
+        let resolved = resolve_token(
+            None,
+            DEFAULT_ACCOUNT,
+            env_with(&[("GH_TOKEN", " "), ("GITHUB_TOKEN", "github-token")]),
+            || None,
+            || async { Ok(None) },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(resolved.source, TokenSource::Env("GITHUB_TOKEN"));
+    }
+
+    #[tokio::test]
+    async fn test_gh_cli_before_stored_token() {
+        let resolved = resolve_token(
+            None,
+            DEFAULT_ACCOUNT,
+            env_with(&[]),
+            || Some("gho_cli\n".to_string()),
+            || async { panic!("storage should not be read") },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(resolved.token, "gho_cli");
+        assert_eq!(resolved.source, TokenSource::GhCli);
+    }
+
+    #[tokio::test]
+    async fn test_stored_token_last() {
+        let resolved = resolve_token(
+            None,
+            DEFAULT_ACCOUNT,
+            env_with(&[]),
+            || None,
+            || async { Ok(Some("stored".to_string())) },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(resolved.source, TokenSource::Stored);
+
+        assert_eq!(
+            resolve_token(
+                None,
+                DEFAULT_ACCOUNT,
+                env_with(&[]),
+                || None,
+                || async { Ok(None) }
+            )
+            .await
+            .unwrap(),
+            None
+        );
+        assert!(
+            resolve_token(
+                None,
+                DEFAULT_ACCOUNT,
+                env_with(&[]),
+                || None,
+                || async { Err(anyhow::anyhow!("unreadable")) }
+            )
+            .await
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_named_account_uses_its_stored_token() {
+        let resolved = resolve_token(
+            None,
+            "work",
+            env_with(&[("GH_TOKEN", "env-token")]),
+            || panic!("gh should not be called"),
+            || async { Ok(Some("work-token".to_string())) },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(resolved.token, "work-token");
+        assert_eq!(resolved.source, TokenSource::Stored);
+
+        // Not falling back to the environment, which may belong to another account
+        assert_eq!(
+            resolve_token(
+                None,
+                "work",
+                env_with(&[("GH_TOKEN", "env-token")]),
+                || Some("gho_cli".to_string()),
+                || async { Ok(None) },
+            )
+            .await
+            .unwrap(),
+            None
+        );
+
+        let resolved = resolve_token(
+            Some("flag-token"),
+            "work",
+            env_with(&[("GH_TOKEN", "env-token")]),
+            || panic!("gh should not be called"),
+            || async { panic!("storage should not be read") },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(resolved.source, TokenSource::Flag);
+    }
+
+    #[test]
+    fn test_enterprise_token() {
+        let resolved = resolve_enterprise_token(
+            None,
+            env_with(&[
+                ("GH_TOKEN", "github-token"),
+                ("GITHUB_ENTERPRISE_TOKEN", "enterprise-token"),
+            ]),
+            || panic!("gh should not be called"),
+        )
+        .unwrap();
+        assert_eq!(resolved.token, "enterprise-token");
+        assert_eq!(resolved.source, TokenSource::Env("GITHUB_ENTERPRISE_TOKEN"));
+
+        // github.com tokens are not sent to another host
+        let resolved =
+            resolve_enterprise_token(None, env_with(&[("GH_TOKEN", "github-token")]), || {
+                Some("gho_enterprise\n".to_string())
+            })
+            .unwrap();
+        assert_eq!(resolved.token, "gho_enterprise");
+        assert_eq!(resolved.source, TokenSource::GhCli);
+
+        assert_eq!(
+            resolve_enterprise_token(Some("flag-token"), env_with(&[]), || None)
+                .unwrap()
+                .source,
+            TokenSource::Flag
+        );
+        assert_eq!(resolve_enterprise_token(None, env_with(&[]), || None), None);
+    }
+
+    #[test]
+    fn test_source_display() {
+        assert_eq!(TokenSource::Flag.to_string(), "--token flag");
+        assert_eq!(
+            TokenSource::Env("GH_TOKEN").to_string(),
+            "GH_TOKEN environment variable"
+        );
+        assert_eq!(TokenSource::GhCli.to_string(), "gh CLI");
+        assert_eq!(TokenSource::Stored.to_string(), "atat login");
+    }
+}
@@ -13,9 +13,13 @@ pub struct AtatWorld {
     pub original_todo_content: String,
 }
 
-#[tokio::main]
-async fn main() {
-    AtatWorld::run("features").await;
+fn main() {
+    // Scenarios expect English messages whatever the locale of the machine running them
+    // SAFETY: set before the runtime starts any other thread
+    unsafe { std::env::set_var("ATAT_LANGUAGE", "en") };
+    tokio::runtime::Runtime::new()
+        .expect("Failed to start the tokio runtime")
+        .block_on(AtatWorld::run("features"));
 }
 
 mod steps;
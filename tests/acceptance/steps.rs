@@ -23,6 +23,20 @@ struct InstallationAccessTokenResponse {
     token: String,
 }
 
+/// Token file location used by `FileTokenStorage`
+fn token_path() -> std::path::PathBuf {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(std::path::PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .unwrap_or_else(|| {
+            std::path::PathBuf::from(
+                std::env::var("HOME").expect("HOME environment variable not set"),
+            )
+            .join(".config")
+        });
+    config_dir.join("atat").join("token")
+}
+
 #[given("the user is logged in via GitHub App for tests")]
 async fn user_is_logged_in_via_github_app(_world: &mut AtatWorld) {
     const GITHUB_API_BASE_URL: &str = "https://api.github.com";
@@ -120,13 +134,20 @@ async fn user_is_logged_in_via_github_app(_world: &mut AtatWorld) {
         .await
         .expect("Valid JSON response");
 
-    let home_dir = std::env::var("HOME").expect("HOME env var not set for test token storage");
-    let token_dir = std::path::PathBuf::from(home_dir).join(".atat");
-    let token_path = token_dir.join("token");
+    let token_path = token_path();
+    let token_dir = token_path
+        .parent()
+        .expect("Token path has a parent directory");
 
-    std::fs::create_dir_all(&token_dir).expect("Failed to create token dir for test token storage");
+    std::fs::create_dir_all(token_dir).expect("Failed to create token dir for test token storage");
     std::fs::write(&token_path, &token_response.token)
         .expect("Failed to write token for test setup");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&token_path, std::fs::Permissions::from_mode(0o600))
+            .expect("Failed to restrict token file permissions");
+    }
 }
 
 #[given(regex = r#"^the config file content is '(.*)'$"#)]
@@ -175,6 +196,8 @@ async fn when_run_atat_remote(world: &mut AtatWorld) {
 
 #[then(regex = r#"^the output should be "(.*)"$"#)]
 async fn then_output_should_be(world: &mut AtatWorld, expected_output: String) {
+    // `\n` in the expected output separates lines
+    let expected_output = expected_output.replace("\\n", "\n");
     let output = String::from_utf8(world.captured_output.clone()).expect("Invalid UTF-8");
     assert_eq!(
         output.trim_end(),
@@ -192,6 +215,8 @@ async fn then_output_should_be(world: &mut AtatWorld, expected_output: String) {
 
 #[then(regex = r#"^the error should be "(.*)"$"#)]
 async fn then_error_should_be(world: &mut AtatWorld, expected_output: String) {
+    // `\n` in the expected output separates lines
+    let expected_output = expected_output.replace("\\n", "\n");
     let output = String::from_utf8(world.captured_output.clone()).expect("Invalid UTF-8");
     assert_eq!(
         output.trim_end(),
@@ -224,14 +249,56 @@ async fn then_output_should_be_empty(world: &mut AtatWorld) {
 
 #[given("the user is not logged in")]
 async fn user_is_not_logged_in(_world: &mut AtatWorld) {
-    let home_dir =
-        std::env::var("HOME").expect("HOME environment variable not set for login test setup.");
-    let token_path = std::path::PathBuf::from(home_dir)
-        .join(".atat")
-        .join("token");
+    let token_path = token_path();
     let _ = std::fs::remove_file(&token_path);
 }
 
+#[given("a token is stored")]
+async fn a_token_is_stored(_world: &mut AtatWorld) {
+    let token_path = token_path();
+    std::fs::create_dir_all(
+        token_path
+            .parent()
+            .expect("Token path has a parent directory"),
+    )
+    .expect("Failed to create token dir for test token storage");
+    std::fs::write(&token_path, "test-token").expect("Failed to write token for test setup");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&token_path, std::fs::Permissions::from_mode(0o600))
+            .expect("Failed to restrict token file permissions");
+    }
+}
+
+#[when("I run `atat logout`")]
+async fn when_run_atat_logout(world: &mut AtatWorld) {
+    let target_dir = std::env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".to_string());
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    let atat_path = std::path::PathBuf::from(&target_dir)
+        .join(profile)
+        .join("atat");
+    let output = std::process::Command::new(&atat_path)
+        .arg("logout")
+        .output()
+        .expect("Failed to run atat logout");
+    world.captured_output = [output.stdout, output.stderr].concat();
+    world.command_status = Some(output.status);
+}
+
+#[then("no token should be stored")]
+async fn then_no_token_should_be_stored(_world: &mut AtatWorld) {
+    let token_path = token_path();
+    assert!(
+        !token_path.exists(),
+        "Token file {token_path:?} should have been deleted"
+    );
+}
+
 #[when("the user executes the `atat login` command")]
 async fn user_executes_login(world: &mut AtatWorld) {
     let mut buffer: Vec<u8> = Vec::new();
@@ -428,10 +495,7 @@ async fn given_update_todo_md_with_actual_issue_number(world: &mut AtatWorld) {
 #[when("I run `atat push`")]
 async fn when_run_atat_push(world: &mut AtatWorld) {
     if !world.created_issues.is_empty() {
-        let home_dir = std::env::var("HOME").expect("HOME environment variable not set");
-        let token_path = std::path::PathBuf::from(home_dir)
-            .join(".atat")
-            .join("token");
+        let token_path = token_path();
         let token = std::fs::read_to_string(&token_path)
             .expect("Failed to read GitHub token for tests")
             .trim()
@@ -455,23 +519,22 @@ async fn when_run_atat_push(world: &mut AtatWorld) {
                 .ok()
                 .filter(|r| r.status().is_success());
 
-            if let Some(response) = response {
-                if let Ok(issues) = response.json::<serde_json::Value>().await {
-                    if let Some(issues_array) = issues.as_array() {
-                        let existing_numbers: Vec<u64> = issues_array
-                            .iter()
-                            .filter_map(|issue| issue["number"].as_u64())
-                            .collect();
-
-                        let all_exist = world
-                            .created_issues
-                            .iter()
-                            .all(|&created_number| existing_numbers.contains(&created_number));
-
-                        if all_exist {
-                            break;
-                        }
-                    }
+            if let Some(response) = response
+                && let Ok(issues) = response.json::<serde_json::Value>().await
+                && let Some(issues_array) = issues.as_array()
+            {
+                let existing_numbers: Vec<u64> = issues_array
+                    .iter()
+                    .filter_map(|issue| issue["number"].as_u64())
+                    .collect();
+
+                let all_exist = world
+                    .created_issues
+                    .iter()
+                    .all(|&created_number| existing_numbers.contains(&created_number));
+
+                if all_exist {
+                    break;
                 }
             }
 
@@ -520,10 +583,7 @@ async fn then_new_github_issue_should_be_created(world: &mut AtatWorld, expected
 
     world.created_issues.push(issue_number);
 
-    let home_dir = std::env::var("HOME").expect("HOME environment variable not set");
-    let token_path = std::path::PathBuf::from(home_dir)
-        .join(".atat")
-        .join("token");
+    let token_path = token_path();
     let token = std::fs::read_to_string(&token_path)
         .expect("Failed to read GitHub token for tests")
         .trim()
@@ -607,10 +667,7 @@ async fn then_github_issue_should_be_closed(world: &mut AtatWorld, _issue_number
         output
     );
 
-    let home_dir = std::env::var("HOME").expect("HOME environment variable not set");
-    let token_path = std::path::PathBuf::from(home_dir)
-        .join(".atat")
-        .join("token");
+    let token_path = token_path();
     let token = std::fs::read_to_string(&token_path)
         .expect("Failed to read GitHub token for tests")
         .trim()
@@ -668,10 +725,7 @@ async fn then_created_issue_should_be_closed(world: &mut AtatWorld) {
         output
     );
 
-    let home_dir = std::env::var("HOME").expect("HOME environment variable not set");
-    let token_path = std::path::PathBuf::from(home_dir)
-        .join(".atat")
-        .join("token");
+    let token_path = token_path();
     let token = std::fs::read_to_string(&token_path)
         .expect("Failed to read GitHub token for tests")
         .trim()
@@ -718,10 +772,7 @@ async fn cleanup_remaining_open_issues(world: &mut AtatWorld) {
         return;
     }
 
-    let home_dir = std::env::var("HOME").expect("HOME environment variable not set");
-    let token_path = std::path::PathBuf::from(home_dir)
-        .join(".atat")
-        .join("token");
+    let token_path = token_path();
     let token = std::fs::read_to_string(&token_path)
         .expect("Failed to read GitHub token for cleanup")
         .trim()
@@ -745,31 +796,28 @@ async fn cleanup_remaining_open_issues(world: &mut AtatWorld) {
             .send()
             .await;
 
-        if let Ok(response) = check_response {
-            if response.status().is_success() {
-                if let Ok(issue_data) = response.json::<serde_json::Value>().await {
-                    if let Some(state) = issue_data["state"].as_str() {
-                        if state == "open" {
-                            let close_url = format!(
-                                "https://api.github.com/repos/{}/issues/{}",
-                                repo, issue_number
-                            );
-                            let close_body = serde_json::json!({
-                                "state": "closed"
-                            });
-
-                            let _close_response = client
-                                .patch(&close_url)
-                                .bearer_auth(&token)
-                                .header("Accept", "application/vnd.github.v3+json")
-                                .header("User-Agent", "atat-cli")
-                                .json(&close_body)
-                                .send()
-                                .await;
-                        }
-                    }
-                }
-            }
+        if let Ok(response) = check_response
+            && response.status().is_success()
+            && let Ok(issue_data) = response.json::<serde_json::Value>().await
+            && let Some(state) = issue_data["state"].as_str()
+            && state == "open"
+        {
+            let close_url = format!(
+                "https://api.github.com/repos/{}/issues/{}",
+                repo, issue_number
+            );
+            let close_body = serde_json::json!({
+                "state": "closed"
+            });
+
+            let _close_response = client
+                .patch(&close_url)
+                .bearer_auth(&token)
+                .header("Accept", "application/vnd.github.v3+json")
+                .header("User-Agent", "atat-cli")
+                .json(&close_body)
+                .send()
+                .await;
         }
     }
 }
@@ -793,12 +841,31 @@ async fn when_run_atat_pull(world: &mut AtatWorld) {
     world.command_status = Some(output.status);
 }
 
+#[when(regex = r#"^I run `atat add "([^"]*)"(.*)`$"#)]
+async fn when_run_atat_add(world: &mut AtatWorld, text: String, options: String) {
+    let target_dir = std::env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".to_string());
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    let atat_path = std::path::PathBuf::from(&target_dir)
+        .join(profile)
+        .join("atat");
+    // Option values in scenarios contain no spaces
+    let output = std::process::Command::new(&atat_path)
+        .arg("add")
+        .arg(text)
+        .args(options.split_whitespace())
+        .output()
+        .expect("Failed to run atat add");
+    world.captured_output = [output.stdout, output.stderr].concat();
+    world.command_status = Some(output.status);
+}
+
 #[given(regex = r#"^GitHub issue #(\d+) with title "(.+)"$"#)]
 async fn given_github_issue_exists(world: &mut AtatWorld, issue_number: u64, title: String) {
-    let home_dir = std::env::var("HOME").expect("HOME environment variable not set");
-    let token_path = std::path::PathBuf::from(home_dir)
-        .join(".atat")
-        .join("token");
+    let token_path = token_path();
     let token = std::fs::read_to_string(&token_path)
         .expect("Failed to read GitHub token for tests")
         .trim()
@@ -854,10 +921,10 @@ async fn given_github_issue_exists(world: &mut AtatWorld, issue_number: u64, tit
                     .send()
                     .await;
 
-                if let Ok(response) = check_response {
-                    if response.status().is_success() {
-                        break;
-                    }
+                if let Ok(response) = check_response
+                    && response.status().is_success()
+                {
+                    break;
                 }
             }
 
@@ -876,17 +943,14 @@ async fn given_github_issue_exists(world: &mut AtatWorld, issue_number: u64, tit
                     .send()
                     .await;
 
-                if let Ok(response) = check_response {
-                    if let Ok(issues) = response.json::<serde_json::Value>().await {
-                        if let Some(arr) = issues.as_array() {
-                            if arr
-                                .iter()
-                                .any(|i| i["number"].as_u64() == Some(actual_number))
-                            {
-                                break;
-                            }
-                        }
-                    }
+                if let Ok(response) = check_response
+                    && let Ok(issues) = response.json::<serde_json::Value>().await
+                    && let Some(arr) = issues.as_array()
+                    && arr
+                        .iter()
+                        .any(|i| i["number"].as_u64() == Some(actual_number))
+                {
+                    break;
                 }
             }
         }
@@ -901,10 +965,7 @@ async fn then_github_issue_should_have_title(
     issue_number: u64,
     expected_title: String,
 ) {
-    let home_dir = std::env::var("HOME").expect("HOME environment variable not set");
-    let token_path = std::path::PathBuf::from(home_dir)
-        .join(".atat")
-        .join("token");
+    let token_path = token_path();
     let token = std::fs::read_to_string(&token_path)
         .expect("Failed to read GitHub token for tests")
         .trim()
@@ -918,10 +979,12 @@ async fn then_github_issue_should_have_title(
         .issue_number_mapping
         .get(&issue_number)
         .copied()
-        .expect(&format!(
-            "Issue number #{} not found in mapping. Available mappings: {:?}",
-            issue_number, world.issue_number_mapping
-        ));
+        .unwrap_or_else(|| {
+            panic!(
+                "Issue number #{} not found in mapping. Available mappings: {:?}",
+                issue_number, world.issue_number_mapping
+            )
+        });
 
     let issue_url = format!(
         "https://api.github.com/repos/{}/issues/{}",
@@ -976,10 +1039,7 @@ async fn given_github_issue_is_renamed(
     issue_number: u64,
     new_title: String,
 ) {
-    let home_dir = std::env::var("HOME").expect("HOME environment variable not set");
-    let token_path = std::path::PathBuf::from(home_dir)
-        .join(".atat")
-        .join("token");
+    let token_path = token_path();
     let token = std::fs::read_to_string(&token_path)
         .expect("Failed to read GitHub token for tests")
         .trim()
@@ -993,10 +1053,12 @@ async fn given_github_issue_is_renamed(
         .issue_number_mapping
         .get(&issue_number)
         .copied()
-        .expect(&format!(
-            "Issue number #{} not found in mapping. Available mappings: {:?}",
-            issue_number, world.issue_number_mapping
-        ));
+        .unwrap_or_else(|| {
+            panic!(
+                "Issue number #{} not found in mapping. Available mappings: {:?}",
+                issue_number, world.issue_number_mapping
+            )
+        });
 
     let update_url = format!(
         "https://api.github.com/repos/{}/issues/{}",
@@ -1038,19 +1100,17 @@ async fn given_github_issue_is_renamed(
             .send()
             .await;
 
-        if let Ok(response) = check_response {
-            if response.status().is_success() {
-                if let Ok(events) = response.json::<serde_json::Value>().await {
-                    if let Some(events_array) = events.as_array() {
-                        let rename_visible = events_array.iter().any(|event| {
-                            event["event"].as_str() == Some("renamed")
-                                && event["rename"]["to"].as_str() == Some(new_title.as_str())
-                        });
-                        if rename_visible {
-                            break;
-                        }
-                    }
-                }
+        if let Ok(response) = check_response
+            && response.status().is_success()
+            && let Ok(events) = response.json::<serde_json::Value>().await
+            && let Some(events_array) = events.as_array()
+        {
+            let rename_visible = events_array.iter().any(|event| {
+                event["event"].as_str() == Some("renamed")
+                    && event["rename"]["to"].as_str() == Some(new_title.as_str())
+            });
+            if rename_visible {
+                break;
             }
         }
 
@@ -1063,10 +1123,7 @@ async fn given_github_issue_is_renamed(
 
 #[given(regex = r#"^GitHub issue #(\d+) is closed$"#)]
 async fn given_github_issue_is_closed(world: &mut AtatWorld, issue_number: u64) {
-    let home_dir = std::env::var("HOME").expect("HOME environment variable not set");
-    let token_path = std::path::PathBuf::from(home_dir)
-        .join(".atat")
-        .join("token");
+    let token_path = token_path();
     let token = std::fs::read_to_string(&token_path)
         .expect("Failed to read GitHub token for tests")
         .trim()
@@ -1080,10 +1137,12 @@ async fn given_github_issue_is_closed(world: &mut AtatWorld, issue_number: u64)
         .issue_number_mapping
         .get(&issue_number)
         .copied()
-        .expect(&format!(
-            "Issue number #{} not found in mapping. Available mappings: {:?}",
-            issue_number, world.issue_number_mapping
-        ));
+        .unwrap_or_else(|| {
+            panic!(
+                "Issue number #{} not found in mapping. Available mappings: {:?}",
+                issue_number, world.issue_number_mapping
+            )
+        });
 
     let close_url = format!(
         "https://api.github.com/repos/{}/issues/{}",
@@ -1125,14 +1184,12 @@ async fn given_github_issue_is_closed(world: &mut AtatWorld, issue_number: u64)
             .send()
             .await;
 
-        if let Ok(response) = check_response {
-            if response.status().is_success() {
-                if let Ok(issue_data) = response.json::<serde_json::Value>().await {
-                    if issue_data["state"].as_str() == Some("closed") {
-                        break;
-                    }
-                }
-            }
+        if let Ok(response) = check_response
+            && response.status().is_success()
+            && let Ok(issue_data) = response.json::<serde_json::Value>().await
+            && issue_data["state"].as_str() == Some("closed")
+        {
+            break;
         }
 
         if attempt < max_attempts {